@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use std::time::Duration;
-use streams_benches::{author, bookmarks, kind, reach, sorting, tag, user};
+use streams_benches::{author, bookmarks, home, kind, reach, sorting, tag, user};
 
 mod setup;
 mod streams_benches;
@@ -27,6 +27,9 @@ criterion_group! {
               reach::bench_stream_followers_total_engagement,
               reach::bench_stream_following_total_engagement,
               reach::bench_stream_friends_total_engagement,
+              home::bench_home_timeline_following,
+              home::bench_home_timeline_friends,
+              home::bench_home_timeline_fan_out,
               sorting::bench_stream_all_timeline,
               sorting::bench_stream_all_total_engagement,
               tag::bench_stream_tag_timeline,
@@ -26,10 +26,17 @@ pub fn bench_stream_author_timeline(c: &mut Criterion) {
             };
 
             // Run the benchmark
-            let post_stream =
-                PostStream::get_posts(source, LIMIT_20, StreamSorting::Timeline, None, None, None)
-                    .await
-                    .unwrap();
+            let post_stream = PostStream::get_posts(
+                source,
+                LIMIT_20,
+                StreamSorting::Timeline,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
             criterion::black_box(post_stream);
         });
     });
@@ -59,6 +66,7 @@ pub fn bench_stream_author_total_engagement(c: &mut Criterion) {
                 None,
                 None,
                 None,
+                None,
             )
             .await
             .unwrap();
@@ -84,10 +92,17 @@ pub fn bench_stream_author_replies_timeline(c: &mut Criterion) {
             };
 
             // Run the benchmark
-            let post_stream =
-                PostStream::get_posts(source, LIMIT_20, StreamSorting::Timeline, None, None, None)
-                    .await
-                    .unwrap();
+            let post_stream = PostStream::get_posts(
+                source,
+                LIMIT_20,
+                StreamSorting::Timeline,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
             criterion::black_box(post_stream);
         });
     });
@@ -0,0 +1,72 @@
+use crate::run_setup;
+use criterion::Criterion;
+use pubky_nexus::models::post::HomeTimeline;
+use tokio::runtime::Runtime;
+
+const OBSERVER_ID: &str = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+const BENCH_POST_ID: &str = "0000000000000";
+
+/// HOME TIMELINE BENCHMARKS
+/// Compare the reads with `stream_posts_following` and `stream_posts_friends`, that merge the posts of the
+/// followed users on read while `HOME_TIMELINES` is not enabled
+pub fn bench_home_timeline_following(c: &mut Criterion) {
+    println!("******************************************************************************");
+    println!("Benchmarking the precomputed 'Following' home timeline.");
+    println!("******************************************************************************");
+
+    run_setup();
+
+    let rt = Runtime::new().unwrap();
+    rt.block_on(HomeTimeline::reindex(OBSERVER_ID)).unwrap();
+
+    c.bench_function("home_timeline_following", |b| {
+        b.to_async(&rt).iter(|| async {
+            let post_keys = HomeTimeline::get_posts(OBSERVER_ID, false, None, None, None, Some(20))
+                .await
+                .unwrap();
+            criterion::black_box(post_keys);
+        });
+    });
+}
+
+pub fn bench_home_timeline_friends(c: &mut Criterion) {
+    println!("******************************************************************************");
+    println!("Benchmarking the precomputed 'Friends' home timeline.");
+    println!("******************************************************************************");
+
+    run_setup();
+
+    let rt = Runtime::new().unwrap();
+    rt.block_on(HomeTimeline::reindex(OBSERVER_ID)).unwrap();
+
+    c.bench_function("home_timeline_friends", |b| {
+        b.to_async(&rt).iter(|| async {
+            let post_keys = HomeTimeline::get_posts(OBSERVER_ID, true, None, None, None, Some(20))
+                .await
+                .unwrap();
+            criterion::black_box(post_keys);
+        });
+    });
+}
+
+/// The cost moved to the indexing of each post
+pub fn bench_home_timeline_fan_out(c: &mut Criterion) {
+    println!("******************************************************************************");
+    println!("Benchmarking the fan out of a post to the home timelines of the followers.");
+    println!("******************************************************************************");
+
+    run_setup();
+
+    let rt = Runtime::new().unwrap();
+
+    c.bench_function("home_timeline_fan_out", |b| {
+        b.to_async(&rt).iter(|| async {
+            HomeTimeline::fan_out(OBSERVER_ID, BENCH_POST_ID, 0)
+                .await
+                .unwrap();
+        });
+    });
+
+    rt.block_on(HomeTimeline::remove_post(OBSERVER_ID, BENCH_POST_ID))
+        .unwrap();
+}
@@ -29,6 +29,7 @@ pub fn bench_stream_post_kind_short(c: &mut Criterion) {
                 None,
                 None,
                 Some(PubkyAppPostKind::Short),
+                None,
             )
             .await
             .unwrap();
@@ -59,6 +60,7 @@ pub fn bench_stream_post_kind_long(c: &mut Criterion) {
                 None,
                 None,
                 Some(PubkyAppPostKind::Long),
+                None,
             )
             .await
             .unwrap();
@@ -89,6 +91,7 @@ pub fn bench_stream_post_kind_image(c: &mut Criterion) {
                 None,
                 None,
                 Some(PubkyAppPostKind::Image),
+                None,
             )
             .await
             .unwrap();
@@ -119,6 +122,7 @@ pub fn bench_stream_post_kind_video(c: &mut Criterion) {
                 None,
                 None,
                 Some(PubkyAppPostKind::Video),
+                None,
             )
             .await
             .unwrap();
@@ -149,6 +153,7 @@ pub fn bench_stream_post_kind_link(c: &mut Criterion) {
                 None,
                 None,
                 Some(PubkyAppPostKind::Link),
+                None,
             )
             .await
             .unwrap();
@@ -179,6 +184,7 @@ pub fn bench_stream_post_kind_file(c: &mut Criterion) {
                 None,
                 None,
                 Some(PubkyAppPostKind::File),
+                None,
             )
             .await
             .unwrap();
@@ -1,5 +1,6 @@
 pub mod author;
 pub mod bookmarks;
+pub mod home;
 pub mod kind;
 pub mod reach;
 pub mod sorting;
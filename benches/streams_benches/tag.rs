@@ -28,7 +28,8 @@ pub fn bench_stream_tag_timeline(c: &mut Criterion) {
                 LIMIT_20,
                 StreamSorting::Timeline,
                 None,
-                Some(vec![TAG.to_string()]),
+                Some(vec![TAG.to_string()].into()),
+                None,
                 None,
             )
             .await
@@ -58,7 +59,8 @@ pub fn bench_stream_tag_total_engagement(c: &mut Criterion) {
                 LIMIT_20,
                 StreamSorting::TotalEngagement,
                 None,
-                Some(vec![TAG.to_string()]),
+                Some(vec![TAG.to_string()].into()),
+                None,
                 None,
             )
             .await
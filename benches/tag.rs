@@ -264,6 +264,7 @@ fn bench_get_following_reach_hot_tags(c: &mut Criterion) {
                     limit: 10,
                     taggers_limit: 20,
                     tagged_type: None,
+                    min_reputation: None,
                 };
                 let profile = HotTags::get_hot_tags(
                     Some(String::from(params[0])),
@@ -305,6 +306,7 @@ fn bench_get_followers_reach_hot_tags(c: &mut Criterion) {
                     limit: 10,
                     taggers_limit: 20,
                     tagged_type: None,
+                    min_reputation: None,
                 };
                 let profile = HotTags::get_hot_tags(
                     Some(String::from(params[0])),
@@ -346,6 +348,7 @@ fn bench_get_friends_reach_hot_tags(c: &mut Criterion) {
                     limit: 10,
                     taggers_limit: 20,
                     tagged_type: None,
+                    min_reputation: None,
                 };
                 let profile = HotTags::get_hot_tags(
                     Some(String::from(params[0])),
@@ -21,6 +21,13 @@ pub struct Config {
     pub watcher_sleep: u64,
     pub max_retries: u64,
     pub migrations_backfill_ready: Vec<String>,
+    pub webhooks_file: Option<String>,
+    pub tag_aliases_file: Option<String>,
+    pub reputation_seeds: Vec<String>,
+    pub reputation_refresh_interval: u64,
+    pub home_timelines: bool,
+    pub home_timeline_fanout_limit: usize,
+    pub home_timeline_size: usize,
 }
 
 impl Config {
@@ -66,6 +73,30 @@ impl Config {
                 .split(",")
                 .map(|s| s.trim().to_string())
                 .collect::<Vec<String>>(),
+            webhooks_file: env::var("WEBHOOKS_FILE").ok().filter(|s| !s.is_empty()),
+            tag_aliases_file: env::var("TAG_ALIASES_FILE").ok().filter(|s| !s.is_empty()),
+            reputation_seeds: env::var("REPUTATION_SEEDS")
+                .unwrap_or("".to_string())
+                .split(",")
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<String>>(),
+            reputation_refresh_interval: env::var("REPUTATION_REFRESH_INTERVAL")
+                .unwrap_or("3600".to_string())
+                .parse()
+                .unwrap_or(3600),
+            home_timelines: env::var("HOME_TIMELINES")
+                .unwrap_or("false".to_string())
+                .parse()
+                .unwrap_or(false),
+            home_timeline_fanout_limit: env::var("HOME_TIMELINE_FANOUT_LIMIT")
+                .unwrap_or("10000".to_string())
+                .parse()
+                .unwrap_or(10000),
+            home_timeline_size: env::var("HOME_TIMELINE_SIZE")
+                .unwrap_or("1000".to_string())
+                .parse()
+                .unwrap_or(1000),
         }
     }
 
@@ -86,9 +86,11 @@ pub fn delete_tag(user_id: &str, tag_id: &str) -> Query {
               CASE WHEN target:Post THEN target.id ELSE null END AS post_id,
               CASE WHEN target:Post THEN author.id ELSE null END AS author_id,
               tag.label AS label,
+              coalesce(tag.original_label, tag.label) AS original_label,
+              tag.indexed_at AS indexed_at,
               tag
          DELETE tag
-         RETURN user_id, post_id, author_id, label",
+         RETURN user_id, post_id, author_id, label, original_label, indexed_at",
     )
     .param("user_id", user_id)
     .param("tag_id", tag_id)
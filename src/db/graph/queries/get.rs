@@ -1,8 +1,11 @@
-use crate::models::post::StreamSource;
+use crate::models::follow::Network;
+use crate::models::post::{StreamSource, TagFilter, TRENDING_DECAY_SECONDS};
 use crate::models::tag::stream::TagStreamReach;
+use crate::models::tag::TaggedType;
+use crate::models::trust::TrustScores;
 use crate::routes::v0::tag::HotTagsInput;
-use crate::types::Pagination;
 use crate::types::StreamSorting;
+use crate::types::{Pagination, Timeframe};
 use log::debug;
 use neo4rs::{query, Query};
 use pubky_app_specs::PubkyAppPostKind;
@@ -115,6 +118,31 @@ pub fn get_post_tags(author_id: &str, post_id: &str) -> Query {
     .param("post_id", post_id)
 }
 
+// Get the root post of a thread and the users that authored a post in that thread (used for thread reply notifications).
+// The threads deeper than `max_depth` replies are not walked and at most `limit` participants are returned
+pub fn get_thread_participants(
+    author_id: &str,
+    post_id: &str,
+    max_depth: u8,
+    limit: usize,
+) -> Query {
+    query(&format!(
+        "MATCH (:User {{id: $author_id}})-[:AUTHORED]->(parent:Post {{id: $post_id}})
+         // Walk up the REPLIED chain until the post that is not a reply
+         MATCH (parent)-[:REPLIED*0..{max_depth}]->(root:Post)<-[:AUTHORED]-(root_author:User)
+         WHERE NOT (root)-[:REPLIED]->(:Post)
+         // Every user that authored the root post or any reply below it
+         MATCH (root)<-[:REPLIED*0..{max_depth}]-(:Post)<-[:AUTHORED]-(participant:User)
+         WITH root_author, root, COLLECT(DISTINCT participant.id) AS participant_ids
+         RETURN root_author.id AS root_author_id,
+                root.id AS root_post_id,
+                participant_ids[..$limit] AS participant_ids"
+    ))
+    .param("author_id", author_id)
+    .param("post_id", post_id)
+    .param("limit", limit as i64)
+}
+
 pub fn post_relationships(author_id: &str, post_id: &str) -> Query {
     query(
         "MATCH (u:User {id: $author_id})-[:AUTHORED]->(p:Post {id: $post_id})
@@ -164,6 +192,43 @@ pub fn global_tags_by_post() -> neo4rs::Query {
     )
 }
 
+/// Retrieves the number of tags of each label on every user, as a sorted set of user IDs per label.
+pub fn global_tags_by_user() -> neo4rs::Query {
+    query(
+        "
+        MATCH (tagger:User)-[t:TAGGED]->(user:User)
+        WITH t.label AS label, user.id AS user_id, COUNT(t) AS score
+        WITH label, COLLECT([toFloat(score), user_id]) AS sorted_set
+        RETURN label, sorted_set
+        ",
+    )
+}
+
+/// Retrieves the distinct labels of the tags on posts and users
+pub fn global_tag_labels() -> neo4rs::Query {
+    query(
+        "
+        MATCH (:User)-[t:TAGGED]->(tagged)
+        WHERE tagged:Post OR tagged:User
+        RETURN DISTINCT t.label AS label
+        ",
+    )
+}
+
+/// Retrieves every tag on posts and users with its tagger and creation time, to rebuild the tag history.
+pub fn global_tags_history() -> neo4rs::Query {
+    query(
+        "
+        MATCH (tagger:User)-[t:TAGGED]->(tagged)
+        WHERE (tagged:Post OR tagged:User) AND t.indexed_at IS NOT NULL
+        RETURN t.label AS label,
+               CASE WHEN tagged:Post THEN 'Post' ELSE 'User' END AS tagged_type,
+               tagger.id AS tagger_id,
+               t.indexed_at AS indexed_at
+        ",
+    )
+}
+
 // TODO: Do not traverse all the graph again to get the engagement score. Rethink how to share that info in the indexer
 /// Retrieves unique global tags for posts, calculating an engagement score based on tag counts,
 /// replies, reposts and mentions. The query returns a `key` by combining author's ID
@@ -187,6 +252,25 @@ pub fn global_tags_by_post_engagement() -> neo4rs::Query {
     )
 }
 
+/// Total engagement created since `since` of every root post with some engagement, as a sorted set
+/// # Arguments
+/// * `since` - Start of the window as a Unix timestamp in milliseconds
+pub fn global_posts_engagement_since(since: i64) -> neo4rs::Query {
+    query(
+        "
+        MATCH (author:User)-[:AUTHORED]->(p:Post)
+        WHERE NOT (p)-[:REPLIED]->(:Post)
+        OPTIONAL MATCH (p)<-[tag:TAGGED]-(:User) WHERE tag.indexed_at >= $since
+        OPTIONAL MATCH (p)<-[:REPLIED]-(reply:Post) WHERE reply.indexed_at >= $since
+        OPTIONAL MATCH (p)<-[:REPOSTED]-(repost:Post) WHERE repost.indexed_at >= $since
+        WITH author, p, COUNT(DISTINCT tag) + COUNT(DISTINCT reply) + COUNT(DISTINCT repost) AS engagement
+        WHERE engagement > 0
+        RETURN COLLECT([toFloat(engagement), author.id + ':' + p.id]) AS sorted_set
+        ",
+    )
+    .param("since", since)
+}
+
 // Retrieve all the tags of the post
 pub fn post_tags(user_id: &str, post_id: &str) -> neo4rs::Query {
     query(
@@ -284,6 +368,133 @@ pub fn get_viewer_trusted_network_tags(user_id: &str, viewer_id: &str, depth: u8
         .param("viewer_id", viewer_id)
 }
 
+/// Retrieve tags for a post within the viewer's trusted network
+/// # Arguments
+///
+/// - `author_id` - A string slice representing the ID of the author of the post.
+/// - `post_id` - A string slice representing the ID of the post whose tags are being queried.
+/// - `viewer_id` - A string slice representing the ID of the viewer whose trusted network is used as a filter.
+/// - `depth` - A `u8` value specifying the depth of the viewer's trusted network
+///
+/// Returns the same `exists` and `tags` values as `get_viewer_trusted_network_tags`
+pub fn get_viewer_trusted_network_post_tags(
+    author_id: &str,
+    post_id: &str,
+    viewer_id: &str,
+    depth: u8,
+) -> neo4rs::Query {
+    let graph_query = format!(
+        "
+        MATCH (viewer:User {{id: $viewer_id}})
+        MATCH (:User {{id: $author_id}})-[:AUTHORED]->(tagged:Post {{id: $post_id}})
+        CALL {{
+            WITH viewer
+            MATCH (viewer)-[:FOLLOWS*1..{depth}]->(tagger:User)
+            RETURN DISTINCT tagger
+        }}
+        MATCH (tagger)-[tag:TAGGED]->(tagged)
+        WITH tag.label AS label, collect(tagger.id) AS taggerIds
+        RETURN 
+            taggerIds IS NOT NULL AS exists,
+            collect({{
+                label: label,
+                taggers: taggerIds,
+                taggers_count: SIZE(taggerIds)
+        }}) AS tags
+        ",
+        depth = depth
+    );
+
+    query(graph_query.as_str())
+        .param("author_id", author_id)
+        .param("post_id", post_id)
+        .param("viewer_id", viewer_id)
+}
+
+// Retrieve the users tagged by the tagger grouped by label, only the users of `label` if provided
+pub fn user_lists(tagger_id: &str, label: Option<&str>) -> Query {
+    let label_condition = match label {
+        Some(_) => "WHERE tag.label = $label",
+        None => "",
+    };
+    query(&format!(
+        "
+        MATCH (tagger:User {{id: $tagger_id}})-[tag:TAGGED]->(tagged:User)
+        {label_condition}
+        RETURN tag.label AS label, COLLECT(DISTINCT tagged.id) AS members
+        "
+    ))
+    .param("tagger_id", tagger_id)
+    .param("label", label.unwrap_or_default())
+}
+
+// Latest time the tagger tagged the post with any label, null if the tagger does not tag the post anymore
+pub fn post_tagged_at(tagger_id: &str, author_id: &str, post_id: &str) -> Query {
+    query(
+        "
+        OPTIONAL MATCH (:User {id: $tagger_id})-[tag:TAGGED]->(:Post {id: $post_id})<-[:AUTHORED]-(:User {id: $author_id})
+        RETURN max(tag.indexed_at) AS tagged_at
+        ",
+    )
+    .param("tagger_id", tagger_id)
+    .param("author_id", author_id)
+    .param("post_id", post_id)
+}
+
+// Posts tagged by the tagger, a row for each label
+pub fn tagged_posts(tagger_id: &str) -> Query {
+    query(
+        "
+        MATCH (:User {id: $tagger_id})-[tag:TAGGED]->(p:Post)<-[:AUTHORED]-(author:User)
+        RETURN tag.label AS label, author.id + ':' + p.id AS post_key, tag.indexed_at AS tagged_at
+        ",
+    )
+    .param("tagger_id", tagger_id)
+}
+
+// Users tagged with the label, ranked by the number of different users that tagged them with any label
+pub fn users_by_tag_unique_taggers(label: &str, skip: usize, limit: usize) -> Query {
+    query(
+        "
+        MATCH (:User)-[:TAGGED {label: $label}]->(user:User)
+        WITH DISTINCT user
+        MATCH (tagger:User)-[:TAGGED]->(user)
+        WITH user, COUNT(DISTINCT tagger) AS unique_taggers
+        RETURN user.id AS user_id
+        ORDER BY unique_taggers DESC, user_id ASC
+        SKIP $skip LIMIT $limit
+        ",
+    )
+    .param("label", label)
+    .param("skip", skip as i64)
+    .param("limit", limit as i64)
+}
+
+// Users tagged with the label, ranked by the number of taggers within `depth` follows of the viewer
+pub fn users_by_tag_in_reach(
+    label: &str,
+    viewer_id: &str,
+    depth: u8,
+    skip: usize,
+    limit: usize,
+) -> Query {
+    query(&format!(
+        "
+        MATCH (viewer:User {{id: $viewer_id}})-[:FOLLOWS*1..{depth}]->(tagger:User)
+        WITH DISTINCT tagger
+        MATCH (tagger)-[:TAGGED {{label: $label}}]->(user:User)
+        WITH user, COUNT(DISTINCT tagger) AS reach_taggers
+        RETURN user.id AS user_id
+        ORDER BY reach_taggers DESC, user_id ASC
+        SKIP $skip LIMIT $limit
+        "
+    ))
+    .param("label", label)
+    .param("viewer_id", viewer_id)
+    .param("skip", skip as i64)
+    .param("limit", limit as i64)
+}
+
 pub fn user_counts(user_id: &str) -> neo4rs::Query {
     query(
         "
@@ -420,6 +631,38 @@ pub fn get_tag_taggers_by_reach(
     .param("limit", limit as i64)
 }
 
+/// Retrieves the users that tagged posts with the label inside the timeframe, the latest taggers first
+/// # Arguments
+/// * `label` - The label of the tags
+/// * `timeframe` - The timeframe the tags were created in
+/// * `skip` - The number of taggers to skip for pagination
+/// * `limit` - The maximum number of taggers to retrieve
+pub fn get_global_tag_taggers(
+    label: &str,
+    timeframe: &Timeframe,
+    skip: usize,
+    limit: usize,
+) -> Query {
+    let (from, to) = timeframe.to_timestamp_range();
+    query(
+        "
+        MATCH (tagger:User)-[tag:TAGGED]->(tagged:Post)
+        WHERE tag.label = $label AND tag.indexed_at >= $from AND tag.indexed_at < $to
+
+        // Get the latest tagged timestamp per tagger
+        WITH tagger, MAX(tag.indexed_at) AS latest_tag_time
+        ORDER BY latest_tag_time DESC
+
+        RETURN COLLECT(tagger.id)[$skip..$skip + $limit] AS tagger_ids
+        ",
+    )
+    .param("label", label)
+    .param("from", from)
+    .param("to", to)
+    .param("skip", skip as i64)
+    .param("limit", limit as i64)
+}
+
 pub fn get_hot_tags_by_reach(
     user_id: &str,
     reach: TagStreamReach,
@@ -436,7 +679,7 @@ pub fn get_hot_tags_by_reach(
             "
         {}
         MATCH (reach)-[tag:TAGGED]->(tagged:{})
-        WHERE user.id = $user_id AND tag.indexed_at >= $from AND tag.indexed_at < $to
+        WHERE user.id = $user_id AND tag.indexed_at >= $from AND tag.indexed_at < $to{}
         WITH 
             tag.label AS label,
             COLLECT(DISTINCT reach.id)[..{}] AS taggers,
@@ -454,6 +697,7 @@ pub fn get_hot_tags_by_reach(
     ",
             tag_stream_reach_to_graph_subquery(&reach),
             input_tagged_type,
+            min_reputation_filter("reach", tags_query.min_reputation),
             tags_query.taggers_limit
         )
         .as_str(),
@@ -463,6 +707,63 @@ pub fn get_hot_tags_by_reach(
     .param("limit", tags_query.limit as i64)
     .param("from", from)
     .param("to", to)
+    .param(
+        "min_reputation",
+        tags_query.min_reputation.unwrap_or_default(),
+    )
+}
+
+/// Labels that co-occur with `label` on the same posts or users, counting only the tags of the users
+/// in the reach of `user_id`
+pub fn get_related_tags_by_reach(
+    label: &str,
+    tagged_type: &TaggedType,
+    user_id: &str,
+    reach: TagStreamReach,
+    skip: usize,
+    limit: usize,
+) -> Query {
+    query(
+        format!(
+            "
+        {}
+        WHERE user.id = $user_id
+        WITH COLLECT(DISTINCT reach) AS reached
+        UNWIND reached AS tagger
+        MATCH (tagger)-[:TAGGED {{label: $label}}]->(tagged:{})<-[related:TAGGED]-(related_tagger:User)
+        WHERE related.label <> $label AND related_tagger IN reached
+        WITH related.label AS label, COUNT(DISTINCT tagged) AS strength
+        ORDER BY strength DESC, label ASC
+        SKIP $skip LIMIT $limit
+        RETURN COLLECT({{ label: label, strength: toFloat(strength) }}) AS related_tags
+    ",
+            tag_stream_reach_to_graph_subquery(&reach),
+            tagged_type
+        )
+        .as_str(),
+    )
+    .param("label", label)
+    .param("user_id", user_id)
+    .param("skip", skip as i64)
+    .param("limit", limit as i64)
+}
+
+/// Every pair of labels that co-occur on the same posts or users, with the number of co-occurrences
+pub fn global_related_tags(tagged_type: &TaggedType) -> Query {
+    query(
+        format!(
+            "
+        MATCH (:User)-[tag:TAGGED]->(tagged:{})<-[related:TAGGED]-(:User)
+        WHERE tag.label <> related.label
+        WITH tag.label AS label, related.label AS related_label, COUNT(DISTINCT tagged) AS strength
+        WITH label, COLLECT([toFloat(strength), related_label]) AS sorted_set
+        RETURN label, sorted_set
+        ORDER BY label
+    ",
+            tagged_type
+        )
+        .as_str(),
+    )
 }
 
 pub fn get_global_hot_tags(tags_query: &HotTagsInput) -> Query {
@@ -476,7 +777,7 @@ pub fn get_global_hot_tags(tags_query: &HotTagsInput) -> Query {
         format!(
             "
         MATCH (user: User)-[tag:TAGGED]->(tagged:{}) 
-        WHERE tag.indexed_at >= $from AND tag.indexed_at < $to
+        WHERE tag.indexed_at >= $from AND tag.indexed_at < $to{}
         WITH 
             tag.label AS label,
             COLLECT(DISTINCT user.id)[..{}] AS taggers,
@@ -492,7 +793,9 @@ pub fn get_global_hot_tags(tags_query: &HotTagsInput) -> Query {
         SKIP $skip LIMIT $limit
         RETURN COLLECT(hot_tag) as hot_tags
     ",
-            input_tagged_type, tags_query.taggers_limit
+            input_tagged_type,
+            min_reputation_filter("user", tags_query.min_reputation),
+            tags_query.taggers_limit
         )
         .as_str(),
     )
@@ -500,6 +803,18 @@ pub fn get_global_hot_tags(tags_query: &HotTagsInput) -> Query {
     .param("limit", tags_query.limit as i64)
     .param("from", from)
     .param("to", to)
+    .param(
+        "min_reputation",
+        tags_query.min_reputation.unwrap_or_default(),
+    )
+}
+
+// Filter out the taggers below the global reputation threshold, if any
+fn min_reputation_filter(tagger: &str, min_reputation: Option<f64>) -> String {
+    match min_reputation {
+        Some(_) => format!(" AND coalesce({tagger}.reputation, 0.0) >= $min_reputation"),
+        None => String::new(),
+    }
 }
 
 pub fn get_files_by_ids(key_pair: &[&[&str]]) -> Query {
@@ -517,9 +832,11 @@ pub fn get_files_by_ids(key_pair: &[&[&str]]) -> Query {
 pub fn post_stream(
     source: StreamSource,
     sorting: StreamSorting,
-    tags: &Option<Vec<String>>,
+    tags: &Option<TagFilter>,
     pagination: Pagination,
     kind: Option<PubkyAppPostKind>,
+    window: Option<Timeframe>,
+    trust: Option<&TrustScores>,
 ) -> Query {
     // Initialize the cypher query
     let mut cypher = String::new();
@@ -534,7 +851,15 @@ pub fn post_stream(
     }
 
     // Base match for posts and authors
-    cypher.push_str("MATCH (p:Post)<-[:AUTHORED]-(author:User)\n");
+    if trust.is_some() {
+        // Only the posts of the trusted users, keeping the position of the author in the trust lists
+        cypher.push_str(
+            "UNWIND range(0, size($trusted_ids) - 1) AS trust_index
+            MATCH (p:Post)<-[:AUTHORED]-(author:User {id: $trusted_ids[trust_index]})\n",
+        );
+    } else {
+        cypher.push_str("MATCH (p:Post)<-[:AUTHORED]-(author:User)\n");
+    }
 
     // Apply source MATCH clause
     if let Some(query) = match source {
@@ -544,19 +869,40 @@ pub fn post_stream(
             Some("MATCH (observer)-[:FOLLOWS]->(author)-[:FOLLOWS]->(observer)\n")
         }
         StreamSource::Bookmarks { .. } => Some("MATCH (observer)-[:BOOKMARKED]->(p)\n"),
+        StreamSource::Mentions { .. } => {
+            Some("MATCH (p)-[:MENTIONED]->(mentioned:User {id: $mentioned_id})\n")
+        }
+        StreamSource::Tagged { .. } => {
+            Some("MATCH (tagger:User {id: $tagger_id})-[tagged:TAGGED]->(p)\n")
+        }
         _ => None,
     } {
         cypher.push_str(query);
     }
 
-    // Apply tags
-    if tags.is_some() {
-        cypher.push_str("MATCH (User)-[tag:TAGGED]->(p)\n");
-        append_condition(
-            &mut cypher,
-            "tag.label IN $labels",
-            &mut where_clause_applied,
-        );
+    // Apply tags: any of, all of and none of the labels
+    if let Some(tags) = tags {
+        if !tags.any.is_empty() {
+            append_condition(
+                &mut cypher,
+                "EXISTS { MATCH (:User)-[tag:TAGGED]->(p) WHERE tag.label IN $any_labels }",
+                &mut where_clause_applied,
+            );
+        }
+        if !tags.all.is_empty() {
+            append_condition(
+                &mut cypher,
+                "ALL(label IN $all_labels WHERE EXISTS { MATCH (:User)-[:TAGGED {label: label}]->(p) })",
+                &mut where_clause_applied,
+            );
+        }
+        if !tags.none.is_empty() {
+            append_condition(
+                &mut cypher,
+                "NOT EXISTS { MATCH (:User)-[tag:TAGGED]->(p) WHERE tag.label IN $none_labels }",
+                &mut where_clause_applied,
+            );
+        }
     }
 
     // If source has an author, add where clause. It is related with source pattern matching
@@ -569,18 +915,73 @@ pub fn post_stream(
         );
     }
 
+    // The posts of the mentioned user are left out, as in its mentions index
+    if let StreamSource::Mentions { .. } = &source {
+        append_condition(
+            &mut cypher,
+            "author.id <> $mentioned_id",
+            &mut where_clause_applied,
+        );
+    }
+
+    // Only the posts tagged with the label, when provided
+    if let StreamSource::Tagged { label: Some(_), .. } = &source {
+        append_condition(
+            &mut cypher,
+            "tagged.label = $tagged_label",
+            &mut where_clause_applied,
+        );
+    }
+
+    // The authors listed by the observer, or by its friends too
+    if let StreamSource::TagList {
+        include_friends, ..
+    } = &source
+    {
+        let condition = match include_friends {
+            Some(true) => {
+                "EXISTS {
+                    MATCH (lister:User)-[:TAGGED {label: $list_label}]->(author)
+                    WHERE lister = observer
+                        OR ((observer)-[:FOLLOWS]->(lister) AND (lister)-[:FOLLOWS]->(observer))
+                }"
+            }
+            _ => "(observer)-[:TAGGED {label: $list_label}]->(author)",
+        };
+        append_condition(&mut cypher, condition, &mut where_clause_applied);
+    }
+
+    // The authors within the follow depth of the observer. The depth of a pattern cannot be a parameter
+    if let StreamSource::Network { depth, .. } = &source {
+        let depth = Network::clamp_depth(*depth);
+        append_condition(
+            &mut cypher,
+            &format!(
+                "author <> observer AND EXISTS {{ MATCH (observer)-[:FOLLOWS*1..{depth}]->(author) }}"
+            ),
+            &mut where_clause_applied,
+        );
+    }
+
     // If post kind is provided, add the corresponding condition
     if kind.is_some() {
         append_condition(&mut cypher, "p.kind = $kind", &mut where_clause_applied);
     }
 
+    // The posts tagged by a user are sorted by the time of its latest tag
+    let is_tagged = matches!(source, StreamSource::Tagged { .. });
+    let timeline_field = match is_tagged {
+        true => "tagged.indexed_at",
+        false => "p.indexed_at",
+    };
+
     // Apply time interval conditions. Only can be applied with timeline sorting
     // The engagament score has to be computed
     if sorting == StreamSorting::Timeline {
         if pagination.start.is_some() {
             append_condition(
                 &mut cypher,
-                "p.indexed_at <= $start",
+                &format!("{timeline_field} <= $start"),
                 &mut where_clause_applied,
             );
         }
@@ -588,29 +989,76 @@ pub fn post_stream(
         if pagination.end.is_some() {
             append_condition(
                 &mut cypher,
-                "p.indexed_at >= $end",
+                &format!("{timeline_field} >= $end"),
                 &mut where_clause_applied,
             );
         }
     }
 
     // Make unique the posts, cannot be repeated
-    cypher.push_str("WITH DISTINCT p, author\n");
+    if trust.is_some() {
+        cypher.push_str("WITH DISTINCT p, author, $trust_scores[trust_index] AS trust\n");
+    } else if is_tagged {
+        cypher.push_str("WITH p, author, max(tagged.indexed_at) AS tagged_at\n");
+    } else {
+        cypher.push_str("WITH DISTINCT p, author\n");
+    }
 
     // Apply StreamSorting
-    // Conditionally compute engagement counts only for TotalEngagement sorting
+    // Conditionally compute engagement counts only for TotalEngagement and Trending sorting
     let order_clause = match sorting {
+        StreamSorting::Timeline if is_tagged => "ORDER BY tagged_at DESC".to_string(),
         StreamSorting::Timeline => "ORDER BY p.indexed_at DESC".to_string(),
-        StreamSorting::TotalEngagement => {
+        // A post of an author ten times more trusted ranks as a post TRENDING_DECAY_SECONDS newer
+        StreamSorting::TrustWeighted => {
+            cypher.push_str(&format!(
+                "WITH p, author, log10(trust) + p.indexed_at / 1000.0 / {} AS trust_score\n",
+                TRENDING_DECAY_SECONDS
+            ));
+
+            // Initialise again
+            where_clause_applied = false;
+
+            if pagination.start.is_some() {
+                append_condition(
+                    &mut cypher,
+                    "trust_score <= $start",
+                    &mut where_clause_applied,
+                );
+            }
+            if pagination.end.is_some() {
+                append_condition(
+                    &mut cypher,
+                    "trust_score >= $end",
+                    &mut where_clause_applied,
+                );
+            }
+
+            "ORDER BY trust_score DESC".to_string()
+        }
+        StreamSorting::TotalEngagement | StreamSorting::Trending => {
+            // Only the engagement created inside the timeframe window is counted
+            let window_range = window
+                .as_ref()
+                .map(|timeframe| timeframe.to_timestamp_range());
+            let (tag_condition, reply_condition, repost_condition) = match window_range {
+                Some((start, end)) => (
+                    format!("WHERE tag.indexed_at >= {start} AND tag.indexed_at < {end}"),
+                    format!("WHERE reply_post.indexed_at >= {start} AND reply_post.indexed_at < {end}"),
+                    format!("WHERE repost_post.indexed_at >= {start} AND repost_post.indexed_at < {end}"),
+                ),
+                None => (String::new(), String::new(), String::new()),
+            };
+
             // TODO: These optional matches could potentially be combined/collected to improve performance
-            cypher.push_str(
+            cypher.push_str(&format!(
                 "
                 // Count tags
-                OPTIONAL MATCH (p)<-[tag:TAGGED]-(:User)  
+                OPTIONAL MATCH (p)<-[tag:TAGGED]-(:User) {tag_condition}
                 // Count replies
-                OPTIONAL MATCH (p)<-[reply:REPLIED]-(:Post)
+                OPTIONAL MATCH (p)<-[reply:REPLIED]-(reply_post:Post) {reply_condition}
                 // Count reposts
-                OPTIONAL MATCH (p)<-[repost:REPOSTED]-(:Post)
+                OPTIONAL MATCH (p)<-[repost:REPOSTED]-(repost_post:Post) {repost_condition}
 
                 WITH p, author, 
                     COUNT(DISTINCT tag) AS tags_count,
@@ -618,16 +1066,39 @@ pub fn post_stream(
                     COUNT(DISTINCT repost) AS reposts_count,
                     (COUNT(DISTINCT tag) + COUNT(DISTINCT reply) + COUNT(DISTINCT repost)) AS total_engagement
                 ",
-            );
+            ));
+
+            // Same score as the trending index, see `PostStream::trending_score`
+            let score = match sorting {
+                StreamSorting::Trending => {
+                    cypher.push_str(&format!(
+                        "WITH p, author,
+                            log10(CASE WHEN total_engagement > 0 THEN total_engagement ELSE 1 END)
+                            + p.indexed_at / 1000.0 / {} AS trending_score\n",
+                        TRENDING_DECAY_SECONDS
+                    ));
+                    "trending_score"
+                }
+                _ => "total_engagement",
+            };
 
             // Initialise again
             where_clause_applied = false;
 
-            // Add total_engagement to filter by engagement the post
+            // Same as the window indexes, posts without engagement inside the window are left out
+            if window_range.is_some() {
+                append_condition(
+                    &mut cypher,
+                    "total_engagement > 0",
+                    &mut where_clause_applied,
+                );
+            }
+
+            // Add the score to filter the post
             if pagination.start.is_some() {
                 append_condition(
                     &mut cypher,
-                    "total_engagement <= $start",
+                    &format!("{} <= $start", score),
                     &mut where_clause_applied,
                 );
             }
@@ -635,12 +1106,12 @@ pub fn post_stream(
             if pagination.end.is_some() {
                 append_condition(
                     &mut cypher,
-                    "total_engagement >= $end",
+                    &format!("{} >= $end", score),
                     &mut where_clause_applied,
                 );
             }
 
-            "ORDER BY total_engagement DESC".to_string()
+            format!("ORDER BY {} DESC", score)
         }
     };
 
@@ -659,7 +1130,7 @@ pub fn post_stream(
     }
 
     // Build the query and apply parameters using `param` method
-    build_query_with_params(&cypher, &source, tags, kind, &pagination)
+    build_query_with_params(&cypher, &source, tags, kind, &pagination, trust)
 }
 
 /// Appends a condition to the Cypher query, using `WHERE` if no `WHERE` clause
@@ -690,23 +1161,40 @@ fn append_condition(cypher: &mut String, condition: &str, where_clause_applied:
 ///
 /// * `cypher` - The Cypher query string that has been constructed.
 /// * `source` - The `StreamSource` specifying the origin of the posts (e.g., Following, Followers).
-/// * `tags` - An optional list of tag labels to filter the posts.
+/// * `tags` - An optional filter of the posts by any, all or none of a list of tag labels.
 /// * `kind` - An optional `PubkyAppPostKind` to filter the posts by their kind.
 /// * `pagination` - The `Pagination` object containing pagination parameters like `start`, `end`, `skip`, and `limit`.
+/// * `trust` - The optional trust scores of the viewer, as parallel lists of user IDs and scores.
 fn build_query_with_params(
     cypher: &str,
     source: &StreamSource,
-    tags: &Option<Vec<String>>,
+    tags: &Option<TagFilter>,
     kind: Option<PubkyAppPostKind>,
     pagination: &Pagination,
+    trust: Option<&TrustScores>,
 ) -> Query {
     let mut query = query(cypher);
 
     if let Some(observer_id) = source.get_observer() {
         query = query.param("observer_id", observer_id.to_string());
     }
-    if let Some(labels) = tags.clone() {
-        query = query.param("labels", labels);
+    if let StreamSource::TagList { label, .. } = source {
+        query = query.param("list_label", label.to_string());
+    }
+    if let StreamSource::Mentions { user_id } = source {
+        query = query.param("mentioned_id", user_id.to_string());
+    }
+    if let StreamSource::Tagged { tagger_id, label } = source {
+        query = query.param("tagger_id", tagger_id.to_string());
+        if let Some(label) = label {
+            query = query.param("tagged_label", label.to_string());
+        }
+    }
+    if let Some(tags) = tags {
+        query = query
+            .param("any_labels", tags.any.clone())
+            .param("all_labels", tags.all.clone())
+            .param("none_labels", tags.none.clone());
     }
     if let Some(author_id) = source.get_author() {
         query = query.param("author_id", author_id.to_string());
@@ -720,6 +1208,15 @@ fn build_query_with_params(
     if let Some(end_interval) = pagination.end {
         query = query.param("end", end_interval);
     }
+    if let Some(trust) = trust {
+        let (trusted_ids, trust_scores): (Vec<String>, Vec<f64>) = trust
+            .iter()
+            .map(|trust| (trust.user_id.clone(), trust.score))
+            .unzip();
+        query = query
+            .param("trusted_ids", trusted_ids)
+            .param("trust_scores", trust_scores);
+    }
 
     query
 }
@@ -774,21 +1271,159 @@ pub fn post_is_safe_to_delete(author_id: &str, post_id: &str) -> Query {
     .param("post_id", post_id)
 }
 
-pub fn recommend_users(user_id: &str, limit: usize) -> neo4rs::Query {
+/// Retrieves the weighted edges of the network of a viewer to compute its trust scores. Each edge weights
+/// one per follow plus `tag_weight` per label the source put on the target
+/// # Arguments
+/// * `viewer_id` - The user whose network is retrieved
+/// * `depth` - The follow distance (1-3) of the users reached from the viewer
+/// * `tag_weight` - The weight of a positive tag relative to a follow
+pub fn trust_network_edges(viewer_id: &str, depth: u8, tag_weight: f64) -> Query {
+    // The edges start from the users before the last hop
+    let hops = depth.saturating_sub(1);
+    query(&format!(
+        "
+        MATCH (viewer:User {{id: $viewer_id}})
+        MATCH (viewer)-[:FOLLOWS*0..{hops}]->(source:User)
+        WITH DISTINCT source
+        MATCH (source)-[r:FOLLOWS|TAGGED]->(target:User)
+        WHERE target <> source
+        WITH source, target,
+            SUM(CASE type(r) WHEN 'FOLLOWS' THEN 1.0 ELSE $tag_weight END) AS weight
+        RETURN source.id AS source, target.id AS target, weight
+        "
+    ))
+    .param("viewer_id", viewer_id)
+    .param("tag_weight", tag_weight)
+}
+
+/// Retrieves the users one follow further than the `frontier` users, a level of the network of a user. The users
+/// followed by more of the frontier come first, then the most followed ones
+/// # Arguments
+/// * `frontier` - The users reached in the previous level
+/// * `reached` - The users already reached in the previous levels, the user the network starts from included
+/// * `limit` - The maximum number of users to retrieve
+pub fn network_level_users(frontier: &[String], reached: &[String], limit: usize) -> Query {
+    query(
+        "
+        MATCH (source:User)-[:FOLLOWS]->(next:User)
+        WHERE source.id IN $frontier AND NOT next.id IN $reached
+        WITH next, COUNT(DISTINCT source) AS links
+        RETURN next.id AS user_id, links, COUNT { (next)<-[:FOLLOWS]-(:User) } AS followers
+        ORDER BY links DESC, followers DESC, user_id ASC
+        LIMIT $limit
+        ",
+    )
+    .param("frontier", frontier)
+    .param("reached", reached)
+    .param("limit", limit as i64)
+}
+
+// Retrieve the weighted follow and tag edges between all the users
+pub fn global_trust_edges(tag_weight: f64) -> Query {
+    query(
+        "
+        MATCH (source:User)-[r:FOLLOWS|TAGGED]->(target:User)
+        WHERE target <> source
+        WITH source, target,
+            SUM(CASE type(r) WHEN 'FOLLOWS' THEN 1.0 ELSE $tag_weight END) AS weight
+        RETURN source.id AS source, target.id AS target, weight
+        ",
+    )
+    .param("tag_weight", tag_weight)
+}
+
+/// Retrieves the candidates recommended to a user, with the users it follows that follow them and the
+/// labels both tag with. The candidates are followed by the users it follows or tag with the labels it tags
+/// with the most, only the users with at least `min_posts` posts. The users already followed or muted are left out
+/// # Arguments
+/// * `user_id` - The user the users are recommended to
+/// * `max_labels` - The maximum number of labels of the user the candidates are looked up by
+/// * `per_label` - The maximum number of candidates looked up by each label
+/// * `min_posts` - The minimum number of posts of the candidates
+/// * `limit` - The maximum number of candidates to retrieve
+pub fn recommend_users(
+    user_id: &str,
+    max_labels: usize,
+    per_label: usize,
+    min_posts: usize,
+    limit: usize,
+) -> neo4rs::Query {
     query(
         "
         MATCH (user:User {id: $user_id})
-        MATCH (user)-[:FOLLOWS*1..3]->(potential:User)
-        WHERE NOT (user)-[:FOLLOWS]->(potential)
-        AND potential.id <> $user_id
-        WITH DISTINCT potential
-        MATCH (potential)-[:AUTHORED]->(post:Post)
-        WITH potential, COUNT(post) AS post_count
-        WHERE post_count >= 5
-        RETURN potential.id AS recommended_user_id
+        OPTIONAL MATCH (user)-[mine:TAGGED]->()
+        WITH user, mine.label AS label, COUNT(mine) AS uses
+        ORDER BY uses DESC, label ASC
+        WITH user, COLLECT(label)[..$max_labels] AS labels
+        // Friends of friends and users tagging with the same labels
+        CALL {
+            WITH user
+            MATCH (user)-[:FOLLOWS]->(:User)-[:FOLLOWS]->(candidate:User)
+            RETURN DISTINCT candidate
+            UNION
+            WITH labels
+            UNWIND labels AS label
+            CALL {
+                WITH label
+                MATCH (candidate:User)-[:TAGGED {label: label}]->()
+                RETURN DISTINCT candidate
+                LIMIT $per_label
+            }
+            RETURN candidate
+        }
+        WITH DISTINCT user, labels, candidate
+        WHERE candidate <> user
+          AND NOT (user)-[:FOLLOWS]->(candidate)
+          AND NOT (user)-[:MUTED]->(candidate)
+          AND COUNT { (candidate)-[:AUTHORED]->(:Post) } >= $min_posts
+        OPTIONAL MATCH (user)-[:FOLLOWS]->(mutual:User)-[:FOLLOWS]->(candidate)
+        WITH labels, candidate, COUNT(DISTINCT mutual) AS mutual_follows
+        OPTIONAL MATCH (candidate)-[theirs:TAGGED]->()
+        WHERE theirs.label IN labels
+        WITH candidate, mutual_follows, COLLECT(DISTINCT theirs.label) AS common_tags
+        RETURN candidate.id AS user_id, mutual_follows, common_tags
+        ORDER BY mutual_follows + size(common_tags) DESC, user_id ASC
         LIMIT $limit
     ",
     )
     .param("user_id", user_id.to_string())
+    .param("max_labels", max_labels as i64)
+    .param("per_label", per_label as i64)
+    .param("min_posts", min_posts as i64)
+    .param("limit", limit as i64)
+}
+
+// Retrieve the labels a user tags posts and users with the most
+pub fn user_top_labels(user_id: &str, limit: usize) -> Query {
+    query(
+        "
+        MATCH (user:User {id: $user_id})-[tag:TAGGED]->()
+        WITH tag.label AS label, COUNT(tag) AS uses
+        RETURN label
+        ORDER BY uses DESC, label ASC
+        LIMIT $limit
+        ",
+    )
+    .param("user_id", user_id)
+    .param("limit", limit as i64)
+}
+
+/// Retrieves the shortest follow paths from the viewer to the user, with the users in between
+/// # Arguments
+/// * `viewer_id` - The user the follows start from
+/// * `user_id` - The user the follows reach
+/// * `max_depth` - The maximum number of follows of a path
+/// * `limit` - The maximum number of paths to retrieve
+pub fn shortest_follow_paths(viewer_id: &str, user_id: &str, max_depth: u8, limit: usize) -> Query {
+    query(&format!(
+        "
+        MATCH (viewer:User {{id: $viewer_id}}), (user:User {{id: $user_id}})
+        MATCH path = allShortestPaths((viewer)-[:FOLLOWS*..{max_depth}]->(user))
+        RETURN length(path) AS degrees, [node IN nodes(path)[1..-1] | node.id] AS user_ids
+        LIMIT $limit
+        "
+    ))
+    .param("viewer_id", viewer_id)
+    .param("user_id", user_id)
     .param("limit", limit as i64)
 }
@@ -224,7 +224,8 @@ pub fn create_post_bookmark(
 /// * `author_id` - The unique identifier of the user who authored the post.
 /// * `post_id` - The unique identifier of the post being tagged.
 /// * `tag_id` - A unique identifier for the tagging relationship.
-/// * `label` - A string representing the label of the tag.
+/// * `label` - A string representing the normalized label of the tag.
+/// * `original_label` - The label as written by the user, kept for display.
 /// * `indexed_at` - A timestamp representing when the tagging relationship was created or last updated.
 ///
 pub fn create_post_tag(
@@ -233,6 +234,7 @@ pub fn create_post_tag(
     post_id: &str,
     tag_id: &str,
     label: &str,
+    original_label: &str,
     indexed_at: i64,
 ) -> Query {
     query(
@@ -243,7 +245,8 @@ pub fn create_post_tag(
         OPTIONAL MATCH (user)-[existing:TAGGED {label: $label}]->(post) 
         MERGE (user)-[t:TAGGED {label: $label}]->(post)
         SET t.indexed_at = $indexed_at,
-            t.id = $tag_id
+            t.id = $tag_id,
+            t.original_label = $original_label
         // Returns true if the post tag relationship already existed
         RETURN existing IS NOT NULL AS flag;",
     )
@@ -252,6 +255,7 @@ pub fn create_post_tag(
     .param("post_id", post_id)
     .param("tag_id", tag_id)
     .param("label", label)
+    .param("original_label", original_label)
     .param("indexed_at", indexed_at)
 }
 
@@ -260,13 +264,15 @@ pub fn create_post_tag(
 /// * `tagger_user_id` - The unique identifier of the user creating the tag.
 /// * `tagged_user_id` - The unique identifier of the user being tagged.
 /// * `tag_id` - A unique identifier for the tagging relationship.
-/// * `label` - A string representing the label of the tag.
+/// * `label` - A string representing the normalized label of the tag.
+/// * `original_label` - The label as written by the user, kept for display.
 /// * `indexed_at` - A timestamp indicating when the tagging relationship was created or last updated.
 pub fn create_user_tag(
     tagger_user_id: &str,
     tagged_user_id: &str,
     tag_id: &str,
     label: &str,
+    original_label: &str,
     indexed_at: i64,
 ) -> Query {
     query(
@@ -276,7 +282,8 @@ pub fn create_user_tag(
         OPTIONAL MATCH (tagger)-[existing:TAGGED {label: $label}]->(tagged_used) 
         MERGE (tagger)-[t:TAGGED {label: $label}]->(tagged_used)
         SET t.indexed_at = $indexed_at,
-            t.id = $tag_id
+            t.id = $tag_id,
+            t.original_label = $original_label
         // Returns true if the user tag relationship already existed
         RETURN existing IS NOT NULL AS flag;",
     )
@@ -284,9 +291,57 @@ pub fn create_user_tag(
     .param("tagged_user_id", tagged_user_id)
     .param("tag_id", tag_id)
     .param("label", label)
+    .param("original_label", original_label)
     .param("indexed_at", indexed_at)
 }
 
+/// Renames the `TAGGED` relationships with the `alias` label to the `canonical` label. The tags whose
+/// tagger already tagged the same node with the canonical label are deleted. Returns every merged tag
+/// with its target, the label written by the tagger and whether it was deleted as a duplicate
+/// # Arguments
+/// * `alias` - The label merged into the canonical label.
+/// * `canonical` - The normalized label that replaces the alias.
+pub fn merge_tag_label(alias: &str, canonical: &str) -> Query {
+    query(
+        "MATCH (tagger:User)-[t:TAGGED {label: $alias}]->(tagged)
+        WHERE tagged:User OR tagged:Post
+        OPTIONAL MATCH (tagged)<-[:AUTHORED]-(author:User)
+        OPTIONAL MATCH (tagger)-[existing:TAGGED {label: $canonical}]->(tagged)
+        WITH t, existing IS NOT NULL AS duplicated,
+             tagger.id AS tagger_id,
+             CASE WHEN tagged:User THEN tagged.id ELSE null END AS user_id,
+             CASE WHEN tagged:Post THEN tagged.id ELSE null END AS post_id,
+             CASE WHEN tagged:Post THEN author.id ELSE null END AS author_id,
+             coalesce(t.original_label, t.label) AS original_label,
+             t.indexed_at AS indexed_at
+        FOREACH (_ IN CASE WHEN duplicated THEN [1] ELSE [] END | DELETE t)
+        FOREACH (_ IN CASE WHEN duplicated THEN [] ELSE [1] END |
+            SET t.original_label = original_label, t.label = $canonical)
+        RETURN tagger_id, user_id, post_id, author_id, original_label, indexed_at, duplicated;",
+    )
+    .param("alias", alias)
+    .param("canonical", canonical)
+}
+
+/// Replaces the `reputation` property of the users. The users missing from `user_ids` lose their
+/// previous reputation
+/// # Arguments
+/// * `user_ids` - The users with reputation.
+/// * `scores` - The reputation of each user, in the same order as `user_ids`.
+pub fn set_users_reputation(user_ids: Vec<String>, scores: Vec<f64>) -> Query {
+    query(
+        "OPTIONAL MATCH (stale:User) WHERE stale.reputation IS NOT NULL
+        REMOVE stale.reputation
+        WITH COUNT(stale) AS cleared
+        UNWIND range(0, size($user_ids) - 1) AS i
+        MATCH (u:User {id: $user_ids[i]})
+        SET u.reputation = $scores[i]
+        RETURN COUNT(u) AS updated;",
+    )
+    .param("user_ids", user_ids)
+    .param("scores", scores)
+}
+
 // Create a file node
 pub fn create_file(file: &FileDetails) -> Result<Query, DynError> {
     let urls = serde_json::to_string(&file.urls)?;
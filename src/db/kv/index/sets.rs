@@ -313,6 +313,23 @@ pub async fn del(prefix: &str, key: &str, values: &[&str]) -> Result<(), DynErro
     Ok(())
 }
 
+/// Retrieves the members present in every one of the Redis sets.
+///
+/// This function uses the `SINTER` command, the sets may belong to different prefixes.
+///
+/// # Arguments
+///
+/// * `keys` - A slice of strings representing the full keys of the sets, prefix included.
+///
+/// # Errors
+///
+/// Returns an error if the operation fails.
+pub async fn intersect(keys: &[String]) -> Result<Vec<String>, DynError> {
+    let mut redis_conn = get_redis_conn().await?;
+    let members: Vec<String> = redis_conn.sinter(keys).await?;
+    Ok(members)
+}
+
 /// Retrieves random members from a Redis set.
 ///
 /// This function uses the `SRANDMEMBER` command to fetch random elements from the specified Redis set.
@@ -7,6 +7,7 @@ pub enum SortOrder {
     Descending,
 }
 
+#[derive(Clone, Copy, Debug)]
 pub enum ScoreAction {
     Increment(f64),
     Decrement(f64),
@@ -41,6 +42,31 @@ pub async fn check_member(
     Ok(rank)
 }
 
+/// Retrieves the scores of multiple members of a Redis sorted set.
+///
+/// Uses the `ZMSCORE` command, the scores are returned in the same order as the members, `None`
+/// for the members that are not in the sorted set.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis key.
+/// * `key` - A string slice representing the key under which the sorted set is stored.
+/// * `members` - A slice of string slices representing the members to look up.
+pub async fn get_scores(
+    prefix: &str,
+    key: &str,
+    members: &[&str],
+) -> Result<Vec<Option<f64>>, DynError> {
+    if members.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let index_key = format!("{}:{}", prefix, key);
+    let mut redis_conn = get_redis_conn().await?;
+    let scores = redis_conn.zscore_multiple(index_key, members).await?;
+    Ok(scores)
+}
+
 /// Adds elements to a Redis sorted set.
 ///
 /// This function adds elements to the specified Redis sorted set. If the set doesn't exist,
@@ -83,6 +109,202 @@ pub async fn put(
     Ok(())
 }
 
+/// Adds elements to multiple Redis sorted sets, keeping only the `size` highest scored elements of each.
+///
+/// All the sorted sets are written in a single pipeline, the lowest scored elements over the size are
+/// removed right after the new elements are added.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `keys` - The keys under which the sorted sets are stored.
+/// * `items` - A slice of tuples where each tuple contains the score and the element.
+/// * `size` - The maximum number of elements of each sorted set.
+pub async fn put_capped(
+    prefix: &str,
+    keys: &[String],
+    items: &[(f64, &str)],
+    size: usize,
+) -> Result<(), DynError> {
+    if keys.is_empty() || items.is_empty() {
+        return Ok(());
+    }
+
+    let mut redis_conn = get_redis_conn().await?;
+    let mut pipe = redis::pipe();
+    for key in keys {
+        let index_key = format!("{}:{}", prefix, key);
+        pipe.zadd_multiple(&index_key, items).ignore();
+        // The ranks start from the lowest score
+        pipe.zremrangebyrank(&index_key, 0, -(size as isize) - 1)
+            .ignore();
+    }
+
+    let _: () = pipe.query_async(&mut redis_conn).await?;
+    Ok(())
+}
+
+/// Replaces all the elements of a Redis sorted set.
+///
+/// The previous sorted set is deleted and the new elements are added in a single transaction, so
+/// readers never see a partially written sorted set.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `key` - A string slice representing the key under which the sorted set is stored.
+/// * `items` - A slice of tuples where each tuple contains the score and the element.
+pub async fn replace(prefix: &str, key: &str, items: &[(f64, &str)]) -> Result<(), DynError> {
+    let index_key = format!("{}:{}", prefix, key);
+    let mut redis_conn = get_redis_conn().await?;
+
+    let mut pipe = redis::pipe();
+    pipe.atomic().del(&index_key).ignore();
+    if !items.is_empty() {
+        pipe.zadd_multiple(&index_key, items).ignore();
+    }
+
+    let _: () = pipe.query_async(&mut redis_conn).await?;
+    Ok(())
+}
+
+/// Stores a combination of Redis sorted sets in a new sorted set.
+///
+/// The members of any of the `union` sorted sets that are in all the `intersection` sorted sets are
+/// stored with the highest of their scores. With `scores`, the members take their score in that sorted
+/// set instead and the ones missing from it are left out; it is copied as it is when there are no other
+/// sorted sets to combine. Finally the members of any of the `difference` sorted sets are removed.
+/// All the steps run in a single transaction.
+///
+/// # Arguments
+///
+/// * `destination` - The full key of the combined sorted set, prefix included.
+/// * `union` - The full keys of the sorted sets whose members are merged.
+/// * `intersection` - The full keys of the sorted sets every member has to be in.
+/// * `scores` - The optional full key of the sorted set that scores the members.
+/// * `difference` - The full keys of the sorted sets no member can be in.
+/// * `expiration` - The TTL (in seconds) of the combined sorted set.
+///
+/// # Errors
+///
+/// Returns an error if there are no sorted sets to combine or the operation fails.
+pub async fn combine(
+    destination: &str,
+    union: &[String],
+    intersection: &[String],
+    scores: Option<&str>,
+    difference: &[String],
+    expiration: i64,
+) -> Result<(), DynError> {
+    let mut pipe = redis::pipe();
+    pipe.atomic().del(destination).ignore();
+
+    let mut stored = false;
+    if !union.is_empty() {
+        pipe.cmd("ZUNIONSTORE")
+            .arg(destination)
+            .arg(union.len())
+            .arg(union)
+            .arg("AGGREGATE")
+            .arg("MAX")
+            .ignore();
+        stored = true;
+    }
+    if !intersection.is_empty() {
+        let mut keys: Vec<&str> = intersection.iter().map(|key| key.as_str()).collect();
+        if stored {
+            keys.push(destination);
+        }
+        pipe.cmd("ZINTERSTORE")
+            .arg(destination)
+            .arg(keys.len())
+            .arg(keys)
+            .arg("AGGREGATE")
+            .arg("MAX")
+            .ignore();
+        stored = true;
+    }
+    match (scores, stored) {
+        (Some(scores), true) => {
+            pipe.cmd("ZINTERSTORE")
+                .arg(destination)
+                .arg(2)
+                .arg(destination)
+                .arg(scores)
+                .arg("WEIGHTS")
+                .arg(0)
+                .arg(1)
+                .ignore();
+        }
+        (Some(scores), false) => {
+            pipe.cmd("ZUNIONSTORE")
+                .arg(destination)
+                .arg(1)
+                .arg(scores)
+                .ignore();
+        }
+        (None, true) => (),
+        (None, false) => return Err("There are no sorted sets to combine".into()),
+    }
+    if !difference.is_empty() {
+        pipe.cmd("ZDIFFSTORE")
+            .arg(destination)
+            .arg(difference.len() + 1)
+            .arg(destination)
+            .arg(difference)
+            .ignore();
+    }
+    pipe.expire(destination, expiration).ignore();
+
+    let mut redis_conn = get_redis_conn().await?;
+    let _: () = pipe.query_async(&mut redis_conn).await?;
+    Ok(())
+}
+
+/// Registers a key in a registry sorted set and deletes the oldest registered keys beyond `size`.
+///
+/// The registry is scored by the time the keys are registered, so registering a key again makes it the
+/// newest one. The deleted keys are removed from the registry as well.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix of the registry and of the registered keys.
+/// * `registry` - The key under which the registry is stored.
+/// * `key` - The key to register.
+/// * `score` - The time the key is registered.
+/// * `size` - The maximum number of registered keys.
+pub async fn register_capped(
+    prefix: &str,
+    registry: &str,
+    key: &str,
+    score: f64,
+    size: usize,
+) -> Result<(), DynError> {
+    let registry_key = format!("{}:{}", prefix, registry);
+    let mut redis_conn = get_redis_conn().await?;
+
+    // The ranks start from the oldest key
+    let surplus = -(size as isize) - 1;
+    let (evicted,): (Vec<String>,) = redis::pipe()
+        .atomic()
+        .zadd(&registry_key, key, score)
+        .ignore()
+        .zrange(&registry_key, 0, surplus)
+        .zremrangebyrank(&registry_key, 0, surplus)
+        .ignore()
+        .query_async(&mut redis_conn)
+        .await?;
+
+    if !evicted.is_empty() {
+        let evicted_keys: Vec<String> = evicted
+            .iter()
+            .map(|key| format!("{}:{}", prefix, key))
+            .collect();
+        let _: () = redis_conn.del(evicted_keys).await?;
+    }
+    Ok(())
+}
+
 /// Updates the score of a member in a Redis sorted set.
 ///
 /// This function modifies the score of a member in the specified Redis sorted set by incrementing or decrementing it
@@ -94,11 +316,13 @@ pub async fn put(
 /// * `key` - A string slice representing the key under which the sorted set is stored.
 /// * `member` - A string slice representing the member whose score will be updated.
 /// * `score_mutation` - A `ScoreAction` that indicates whether to increment or decrement the score.
+/// * `expiration` - An optional `i64` specifying the TTL (in seconds) for the set. If `None`, no TTL will be set.
 pub async fn put_score(
     prefix: &str,
     key: &str,
     member: &str,
     score_mutation: ScoreAction,
+    expiration: Option<i64>,
 ) -> Result<(), DynError> {
     let index_key = format!("{}:{}", prefix, key);
     let mut redis_conn = get_redis_conn().await?;
@@ -106,8 +330,15 @@ pub async fn put_score(
         ScoreAction::Increment(val) => val,
         ScoreAction::Decrement(val) => -val,
     };
-    let _: () = redis_conn.zincr(&index_key, member, value).await?;
 
+    let mut pipe = redis::pipe();
+    pipe.zincr(&index_key, member, value);
+
+    if let Some(ttl) = expiration {
+        pipe.expire(&index_key, ttl);
+    }
+
+    let _: () = pipe.query_async(&mut redis_conn).await?;
     Ok(())
 }
 
@@ -171,6 +402,49 @@ pub async fn get_range(
     }
 }
 
+/// Retrieves the highest scored elements of multiple Redis sorted sets in a single pipeline.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `keys` - The keys under which the sorted sets are stored.
+/// * `min_score` - The minimum score for the range (inclusive).
+/// * `max_score` - The maximum score for the range (inclusive).
+/// * `limit` - The maximum number of elements to retrieve from each sorted set.
+///
+/// # Returns
+///
+/// The elements and their scores of each sorted set, in the same order as the keys.
+pub async fn get_multiple_ranges(
+    prefix: &str,
+    keys: &[String],
+    min_score: Option<f64>,
+    max_score: Option<f64>,
+    limit: usize,
+) -> Result<Vec<Vec<(String, f64)>>, DynError> {
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let min_score = min_score.unwrap_or(f64::MIN);
+    let max_score = max_score.unwrap_or(f64::MAX);
+
+    let mut redis_conn = get_redis_conn().await?;
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.zrevrangebyscore_limit_withscores(
+            format!("{}:{}", prefix, key),
+            max_score,
+            min_score,
+            0,
+            limit as isize,
+        );
+    }
+
+    let elements: Vec<Vec<(String, f64)>> = pipe.query_async(&mut redis_conn).await?;
+    Ok(elements)
+}
+
 /// Performs a lexicographical range search on the Redis sorted set.
 ///
 /// # Arguments
@@ -246,3 +520,48 @@ pub async fn del(prefix: &str, key: &str, values: &[&str]) -> Result<(), DynErro
     let _: () = redis_conn.zrem(index_key, values).await?;
     Ok(())
 }
+
+/// Removes elements from multiple Redis sorted sets in a single pipeline.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `keys` - The keys under which the sorted sets are stored.
+/// * `values` - A slice of string slices representing the elements to be removed from every sorted set.
+pub async fn del_multiple(prefix: &str, keys: &[String], values: &[&str]) -> Result<(), DynError> {
+    if keys.is_empty() || values.is_empty() {
+        return Ok(());
+    }
+
+    let mut redis_conn = get_redis_conn().await?;
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.zrem(format!("{}:{}", prefix, key), values).ignore();
+    }
+
+    let _: () = pipe.query_async(&mut redis_conn).await?;
+    Ok(())
+}
+
+/// Removes the elements whose score is zero or lower from multiple Redis sorted sets in a single pipeline.
+/// Redis deletes the sorted sets left without elements.
+///
+/// # Arguments
+///
+/// * `prefix` - A string slice representing the prefix for the Redis keys.
+/// * `keys` - The keys under which the sorted sets are stored.
+pub async fn del_non_positive(prefix: &str, keys: &[String]) -> Result<(), DynError> {
+    if keys.is_empty() {
+        return Ok(());
+    }
+
+    let mut redis_conn = get_redis_conn().await?;
+    let mut pipe = redis::pipe();
+    for key in keys {
+        pipe.zrembyscore(format!("{}:{}", prefix, key), "-inf", 0)
+            .ignore();
+    }
+
+    let _: () = pipe.query_async(&mut redis_conn).await?;
+    Ok(())
+}
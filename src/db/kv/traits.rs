@@ -174,6 +174,29 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         json::del_multiple(&prefix, &keys).await
     }
 
+    /// Removes multiple JSON objects stored under a custom prefix, such as the cache entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts_list` - A slice of slices, where each inner slice contains string slices representing
+    ///   the parts used to form the key under which the corresponding value is stored.
+    /// * `prefix` - A string representing the prefix of the Redis keys.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn remove_from_prefixed_index_multiple_json(
+        key_parts_list: &[&[&str]],
+        prefix: &str,
+    ) -> Result<(), DynError> {
+        let keys: Vec<String> = key_parts_list
+            .iter()
+            .map(|key_parts| key_parts.join(":"))
+            .collect();
+
+        json::del_multiple(prefix, &keys).await
+    }
+
     /// Modifies a numeric field in a Redis JSON object by either incrementing or decrementing it.
     ///
     /// This method performs an operation on a numeric field in Redis JSON at the given path,
@@ -358,6 +381,29 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         sets::get_range(&combined_prefix, &key, skip, limit).await
     }
 
+    /// Retrieves the members shared by a Redis set of this type and other Redis sets.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the set is stored.
+    /// * `prefix` - An optional string representing the prefix for the Redis keys. If `Some(String)`, the prefix will be used
+    /// * `other_keys` - The full keys of the other sets, prefix included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the operation fails, such as if the Redis connection is unavailable.
+    async fn intersect_index_sets(
+        key_parts: &[&str],
+        prefix: Option<String>,
+        other_keys: &[String],
+    ) -> Result<Vec<String>, DynError> {
+        let prefix = prefix.unwrap_or(Self::prefix().await);
+        let mut keys = Vec::with_capacity(other_keys.len() + 1);
+        keys.push(format!("{}:{}", prefix, key_parts.join(":")));
+        keys.extend_from_slice(other_keys);
+        sets::intersect(&keys).await
+    }
+
     /// Checks if a member exists in a Redis set and if the set exists using the provided key parts.
     ///
     /// This method checks if a specific member is present in the Redis set stored under the key
@@ -528,6 +574,107 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         sorted_sets::check_member(prefix, &key, &member_key).await
     }
 
+    /// Retrieves the scores of multiple members of a Redis sorted set using the provided key parts.
+    ///
+    /// Unlike `check_sorted_set_member`, the scores are not truncated to integers.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - An optional string representing the prefix for the Redis keys. If `Some(String)`, the prefix will be used
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `members` - A slice of string slices representing the members to look up.
+    ///
+    /// # Returns
+    ///
+    /// The scores in the same order as the members, `None` for the members that are not in the sorted set.
+    async fn get_index_sorted_set_scores(
+        prefix: Option<&str>,
+        key_parts: &[&str],
+        members: &[&str],
+    ) -> Result<Vec<Option<f64>>, DynError> {
+        let prefix = prefix.unwrap_or(SORTED_PREFIX);
+        let key = key_parts.join(":");
+        sorted_sets::get_scores(prefix, &key, members).await
+    }
+
+    /// Stores a combination of Redis sorted sets in a new sorted set using the provided key parts.
+    ///
+    /// The members of any of the `union` sorted sets that are in all the `intersection` sorted sets and in none of
+    /// the `difference` sorted sets are kept. See `sorted_sets::combine` for the scores of the members.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key of the combined sorted set.
+    /// * `prefix` - An optional string representing the prefix of the combined sorted set. The combined sorted sets share the default prefix.
+    /// * `union` - The key parts of the sorted sets whose members are merged.
+    /// * `intersection` - The key parts of the sorted sets every member has to be in.
+    /// * `scores` - The optional key parts of the sorted set that scores the members.
+    /// * `difference` - The key parts of the sorted sets no member can be in.
+    /// * `expiration` - The TTL (in seconds) of the combined sorted set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no sorted sets to combine or the operation fails.
+    async fn combine_index_sorted_sets(
+        key_parts: &[&str],
+        prefix: Option<&str>,
+        union: &[Vec<&str>],
+        intersection: &[Vec<&str>],
+        scores: Option<&[&str]>,
+        difference: &[Vec<&str>],
+        expiration: i64,
+    ) -> Result<(), DynError> {
+        let full_key = |key_parts: &[&str]| format!("{}:{}", SORTED_PREFIX, key_parts.join(":"));
+        let full_keys = |key_parts_list: &[Vec<&str>]| -> Vec<String> {
+            key_parts_list
+                .iter()
+                .map(|key_parts| full_key(key_parts.as_slice()))
+                .collect()
+        };
+        let destination = format!(
+            "{}:{}",
+            prefix.unwrap_or(SORTED_PREFIX),
+            key_parts.join(":")
+        );
+        let scores = scores.map(full_key);
+        sorted_sets::combine(
+            &destination,
+            &full_keys(union),
+            &full_keys(intersection),
+            scores.as_deref(),
+            &full_keys(difference),
+            expiration,
+        )
+        .await
+    }
+
+    /// Registers a cached sorted set in a registry of the most recently stored ones, deleting the oldest
+    /// cached sorted sets beyond `size`.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - A string representing the prefix of the registry and of the cached sorted sets.
+    /// * `registry_key_parts` - The key parts of the registry.
+    /// * `key_parts` - The key parts of the cached sorted set.
+    /// * `score` - The time the cached sorted set is stored.
+    /// * `size` - The maximum number of cached sorted sets in the registry.
+    async fn register_cached_index_sorted_set(
+        prefix: &str,
+        registry_key_parts: &[&str],
+        key_parts: &[&str],
+        score: f64,
+        size: usize,
+    ) -> Result<(), DynError> {
+        sorted_sets::register_capped(
+            prefix,
+            &registry_key_parts.join(":"),
+            &key_parts.join(":"),
+            score,
+            size,
+        )
+        .await
+    }
+
     /// Adds elements to a Redis sorted set using the provided key parts.
     ///
     /// This method adds elements to a Redis sorted set under the key generated from the provided `key_parts`.
@@ -556,6 +703,48 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         sorted_sets::put(prefix, &key, elements, expiration).await
     }
 
+    /// Adds elements to multiple Redis sorted sets using the provided key parts, keeping only the `size` highest
+    /// scored elements of each.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts_list` - The key parts of each sorted set.
+    /// * `elements` - A slice of tuples where each tuple contains the score and the element.
+    /// * `size` - The maximum number of elements of each sorted set.
+    /// * `prefix` - An optional string representing the prefix for the Redis keys. If `Some(String)`, the prefix will be used
+    async fn put_capped_index_sorted_sets(
+        key_parts_list: &[Vec<&str>],
+        elements: &[(f64, &str)],
+        size: usize,
+        prefix: Option<&str>,
+    ) -> Result<(), DynError> {
+        let prefix = prefix.unwrap_or(SORTED_PREFIX);
+        let keys: Vec<String> = key_parts_list
+            .iter()
+            .map(|key_parts| key_parts.join(":"))
+            .collect();
+        sorted_sets::put_capped(prefix, &keys, elements, size).await
+    }
+
+    /// Replaces all the elements of a Redis sorted set using the provided key parts.
+    ///
+    /// The members missing from `elements` are removed from the sorted set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `elements` - A slice of tuples where each tuple contains the score and the element.
+    /// * `prefix` - An optional string representing the prefix for the Redis keys. If `Some(String)`, the prefix will be used
+    async fn replace_index_sorted_set(
+        key_parts: &[&str],
+        elements: &[(f64, &str)],
+        prefix: Option<&str>,
+    ) -> Result<(), DynError> {
+        let prefix = prefix.unwrap_or(SORTED_PREFIX);
+        let key = key_parts.join(":");
+        sorted_sets::replace(prefix, &key, elements).await
+    }
+
     /// Updates the score of a member in a Redis sorted set.
     ///
     /// This method updates the score associated with a specific member in a Redis sorted set
@@ -573,7 +762,35 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
     ) -> Result<(), DynError> {
         let key = key_parts.join(":");
         let member_key = member.join(":");
-        sorted_sets::put_score(SORTED_PREFIX, &key, &member_key, score_mutation).await
+        sorted_sets::put_score(SORTED_PREFIX, &key, &member_key, score_mutation, None).await
+    }
+
+    /// Updates the score of a member in a Redis sorted set that expires after `expiration` seconds.
+    ///
+    /// Same as `put_score_index_sorted_set`, the TTL of the sorted set is refreshed on every update.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts` - A slice of string slices that represent the parts used to form the key under which the sorted set is stored.
+    /// * `member` - A slice of string slices that represent the parts used to form the key identifying the member within the sorted set.
+    /// * `score_mutation` - A `ScoreAction` that defines how the score should be modified (e.g., incremented or decremented).
+    /// * `expiration` - An `i64` specifying the TTL (in seconds) for the set.
+    async fn put_expiring_score_index_sorted_set(
+        key_parts: &[&str],
+        member: &[&str],
+        score_mutation: ScoreAction,
+        expiration: i64,
+    ) -> Result<(), DynError> {
+        let key = key_parts.join(":");
+        let member_key = member.join(":");
+        sorted_sets::put_score(
+            SORTED_PREFIX,
+            &key,
+            &member_key,
+            score_mutation,
+            Some(expiration),
+        )
+        .await
     }
 
     /// Removes elements from a Redis sorted set using the provided key parts.
@@ -606,6 +823,42 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         sorted_sets::del(prefix, &key, items).await
     }
 
+    /// Removes elements from multiple Redis sorted sets using the provided key parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - An optional string representing the prefix for the Redis keys. If `Some(String)`, the prefix will be used
+    /// * `key_parts_list` - The key parts of each sorted set.
+    /// * `items` - A slice of string slices representing the elements to be removed from every sorted set.
+    async fn remove_from_index_sorted_sets(
+        prefix: Option<&str>,
+        key_parts_list: &[Vec<&str>],
+        items: &[&str],
+    ) -> Result<(), DynError> {
+        let prefix = prefix.unwrap_or(SORTED_PREFIX);
+        let keys: Vec<String> = key_parts_list
+            .iter()
+            .map(|key_parts| key_parts.join(":"))
+            .collect();
+        sorted_sets::del_multiple(prefix, &keys, items).await
+    }
+
+    /// Removes the elements whose score dropped to zero or lower from multiple Redis sorted sets.
+    /// The sorted sets left without elements are deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts_list` - The key parts of each sorted set.
+    async fn remove_non_positive_from_index_sorted_sets(
+        key_parts_list: &[Vec<&str>],
+    ) -> Result<(), DynError> {
+        let keys: Vec<String> = key_parts_list
+            .iter()
+            .map(|key_parts| key_parts.join(":"))
+            .collect();
+        sorted_sets::del_non_positive(SORTED_PREFIX, &keys).await
+    }
+
     /// Retrieves a range of elements from a Redis sorted set using the provided key parts.
     ///
     /// This method fetches elements from a Redis sorted set stored under the key generated from the provided `key_parts`.
@@ -642,6 +895,31 @@ pub trait RedisOps: Serialize + DeserializeOwned + Send + Sync {
         sorted_sets::get_range(prefix, &key, end, start, skip, limit, sorting).await
     }
 
+    /// Retrieves the highest scored elements of multiple Redis sorted sets using the provided key parts.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_parts_list` - The key parts of each sorted set.
+    /// * `start` - An optional value representing the highest score of the range. If `None`, no upper bound is applied.
+    /// * `end` - An optional value representing the lowest score of the range. If `None`, no lower bound is applied.
+    /// * `limit` - The maximum number of elements to retrieve from each sorted set.
+    ///
+    /// # Returns
+    ///
+    /// The elements and their scores of each sorted set, in the same order as the key parts.
+    async fn try_from_multiple_index_sorted_sets(
+        key_parts_list: &[Vec<&str>],
+        start: Option<f64>,
+        end: Option<f64>,
+        limit: usize,
+    ) -> Result<Vec<Vec<(String, f64)>>, DynError> {
+        let keys: Vec<String> = key_parts_list
+            .iter()
+            .map(|key_parts| key_parts.join(":"))
+            .collect();
+        sorted_sets::get_multiple_ranges(SORTED_PREFIX, &keys, end, start, limit).await
+    }
+
     /// Retrieves a lexicographical range of elements from a Redis sorted set using the provided key parts.
     ///
     /// This method fetches elements from a Redis sorted set stored under the key generated from the provided `key_parts`.
@@ -1 +1,2 @@
 pub mod tag_counts_reset_1739459180;
+pub mod tag_labels_normalize_1792342806;
@@ -0,0 +1,63 @@
+use crate::db::migrations::manager::Migration;
+use crate::models::tag::label::{canonical_label, merge_label};
+use crate::types::DynError;
+use crate::{get_neo4j_graph, queries};
+use async_trait::async_trait;
+use log::info;
+
+/// Merges the tags stored before the labels were normalized into their canonical label, e.g. `Bitcoin`
+/// into `bitcoin`. The label written by the tagger is kept for display
+pub struct TagLabelsNormalize1792342806;
+
+#[async_trait]
+impl Migration for TagLabelsNormalize1792342806 {
+    fn id(&self) -> &'static str {
+        "TagLabelsNormalize1792342806"
+    }
+
+    fn is_multi_staged(&self) -> bool {
+        false
+    }
+
+    async fn dual_write(_data: Box<dyn std::any::Any + Send + 'static>) -> Result<(), DynError> {
+        // The new tags are already stored with their canonical label
+        Ok(())
+    }
+
+    async fn backfill(&self) -> Result<(), DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::global_tag_labels();
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut labels: Vec<String> = Vec::new();
+        while let Some(row) = result.next().await? {
+            labels.push(row.get("label")?);
+        }
+
+        let mut merged_labels = 0;
+        for label in labels {
+            let canonical = canonical_label(&label).await?;
+            if !canonical.is_empty() && canonical != label {
+                merge_label(&label, &canonical).await?;
+                merged_labels += 1;
+            }
+        }
+        info!("Merged {} labels into their canonical label", merged_labels);
+        Ok(())
+    }
+
+    async fn cutover(&self) -> Result<(), DynError> {
+        // Not necessary
+        Ok(())
+    }
+
+    async fn cleanup(&self) -> Result<(), DynError> {
+        // The merged tags left the indexes of their previous label in the backfill phase
+        Ok(())
+    }
+}
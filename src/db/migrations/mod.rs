@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use migrations_list::tag_counts_reset_1739459180::TagCountsReset1739459180;
+use migrations_list::tag_labels_normalize_1792342806::TagLabelsNormalize1792342806;
 use neo4rs::Graph;
 use tokio::sync::Mutex;
 
@@ -14,6 +15,7 @@ pub fn get_migration_manager(graph: Arc<Mutex<Graph>>) -> MigrationManager {
     let mut migration_manager = MigrationManager::new(graph);
     // Add your migrations here to be picked up by the manager. Example:
     migration_manager.register(Box::new(TagCountsReset1739459180));
+    migration_manager.register(Box::new(TagLabelsNormalize1792342806));
     migration_manager
     //MigrationManager::new(graph)
 }
@@ -5,7 +5,9 @@ use crate::events::retry::event::RetryEvent;
 use crate::handle_indexing_results;
 use crate::models::follow::{Followers, Following, Friends, UserFollows};
 use crate::models::notification::Notification;
-use crate::models::user::UserCounts;
+use crate::models::post::HomeTimeline;
+use crate::models::tag::stream::HotTags;
+use crate::models::user::{UserCounts, UserRecommendations};
 use crate::types::DynError;
 use log::debug;
 use pubky_app_specs::{user_uri_builder, PubkyId};
@@ -33,6 +35,7 @@ pub async fn sync_put(follower_id: PubkyId, followee_id: PubkyId) -> Result<(),
 
             let followers = Followers(vec![follower_id.to_string()]);
             let following = Following(vec![followee_id.to_string()]);
+            let reach_user_ids: &[&str] = &[&follower_id, &followee_id];
 
             // SAVE TO INDEX
             let indexing_results = tokio::join!(
@@ -47,14 +50,28 @@ pub async fn sync_put(follower_id: PubkyId, followee_id: PubkyId) -> Result<(),
                     will_be_friends
                 ),
                 // Notify the followee
-                Notification::new_follow(&follower_id, &followee_id, will_be_friends)
+                Notification::new_follow(&follower_id, &followee_id, will_be_friends),
+                // The reach of both users changed
+                HotTags::del_reach_cache(reach_user_ids),
+                // Backfill the home timelines with the posts of the followed user
+                async {
+                    if HomeTimeline::is_enabled() {
+                        HomeTimeline::follow(&follower_id, &followee_id, will_be_friends).await?;
+                    }
+                    Ok::<(), DynError>(())
+                },
+                // The followee is no longer a recommendation of the follower
+                UserRecommendations::del_cache(&follower_id)
             );
 
             handle_indexing_results!(
                 indexing_results.0,
                 indexing_results.1,
                 indexing_results.2,
-                indexing_results.3
+                indexing_results.3,
+                indexing_results.4,
+                indexing_results.5,
+                indexing_results.6
             );
         }
     };
@@ -80,6 +97,7 @@ pub async fn sync_del(follower_id: PubkyId, followee_id: PubkyId) -> Result<(),
             // REMOVE FROM INDEX
             let followers = Followers(vec![follower_id.to_string()]);
             let following = Following(vec![followee_id.to_string()]);
+            let reach_user_ids: &[&str] = &[&follower_id, &followee_id];
 
             let indexing_results = tokio::join!(
                 // Remove a follower to the followee index
@@ -93,13 +111,27 @@ pub async fn sync_del(follower_id: PubkyId, followee_id: PubkyId) -> Result<(),
                     were_friends,
                 ),
                 // Notify the followee
-                Notification::lost_follow(&follower_id, &followee_id, were_friends)
+                Notification::lost_follow(&follower_id, &followee_id, were_friends),
+                // Remove the followee notifications about the deleted follow
+                Notification::del_follow(&follower_id, &followee_id),
+                // The reach of both users changed
+                HotTags::del_reach_cache(reach_user_ids),
+                // Remove the posts of the unfollowed user from the home timelines
+                async {
+                    if HomeTimeline::is_enabled() {
+                        HomeTimeline::unfollow(&follower_id, &followee_id, were_friends).await?;
+                    }
+                    Ok::<(), DynError>(())
+                }
             );
             handle_indexing_results!(
                 indexing_results.0,
                 indexing_results.1,
                 indexing_results.2,
-                indexing_results.3
+                indexing_results.3,
+                indexing_results.4,
+                indexing_results.5,
+                indexing_results.6
             );
 
             Ok(())
@@ -1,7 +1,7 @@
 use crate::db::graph::exec::OperationOutcome;
 use crate::events::error::EventProcessorError;
 use crate::events::retry::event::RetryEvent;
-use crate::models::user::Muted;
+use crate::models::user::{Muted, UserRecommendations};
 use crate::types::DynError;
 use log::debug;
 use pubky_app_specs::{user_uri_builder, PubkyId};
@@ -27,6 +27,12 @@ pub async fn sync_put(user_id: PubkyId, muted_id: PubkyId) -> Result<(), DynErro
                 .map_err(|e| EventProcessorError::IndexWriteFailed {
                     message: e.to_string(),
                 })?;
+            // The muted user is no longer a recommendation of the user
+            UserRecommendations::del_cache(&user_id)
+                .await
+                .map_err(|e| EventProcessorError::IndexWriteFailed {
+                    message: e.to_string(),
+                })?;
             Ok(())
         }
     }
@@ -3,13 +3,11 @@ use crate::db::kv::index::json::JsonAction;
 use crate::events::error::EventProcessorError;
 use crate::events::retry::event::RetryEvent;
 use crate::models::notification::{Notification, PostChangedSource, PostChangedType};
-use crate::models::post::{
-    PostCounts, PostDetails, PostRelationships, PostStream, POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-};
+use crate::models::post::{HomeTimeline, PostCounts, PostDetails, PostRelationships, PostStream};
 use crate::models::user::UserCounts;
 use crate::queries::get::post_is_safe_to_delete;
 use crate::types::DynError;
-use crate::{handle_indexing_results, queries, RedisOps, ScoreAction};
+use crate::{handle_indexing_results, queries, ScoreAction};
 use log::debug;
 use pubky_app_specs::{
     user_uri_builder, ParsedUri, PubkyAppPost, PubkyAppPostKind, PubkyId, Resource,
@@ -136,10 +134,11 @@ pub async fn sync_put(
             ),
             async {
                 if !post_relationships_is_reply(&parent_author_id, &parent_post_id).await? {
-                    PostStream::put_score_index_sorted_set(
-                        &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                        parent_post_key_parts,
+                    PostStream::update_index_score(
+                        &parent_author_id,
+                        &parent_post_id,
                         ScoreAction::Increment(1.0),
+                        post_details.indexed_at,
                     )
                     .await?;
                 }
@@ -156,6 +155,15 @@ pub async fn sync_put(
                 replied_uri,
                 &post_details.uri,
                 &parent_author_id,
+            ),
+            // Notify the rest of the thread participants
+            Notification::new_thread_reply(
+                &author_id,
+                replied_uri,
+                &post_details.uri,
+                &parent_author_id,
+                &parent_post_id,
+                &post_relationships.mentioned,
             )
         );
 
@@ -163,8 +171,12 @@ pub async fn sync_put(
             indexing_results.0,
             indexing_results.1,
             indexing_results.2,
-            indexing_results.3
+            indexing_results.3,
+            indexing_results.4
         );
+
+        // The trending score depends on the updated counts
+        PostStream::update_root_trending_score(&parent_author_id, &parent_post_id).await?;
     }
 
     // PHASE 3: Process POST REPOSTS indexes
@@ -188,10 +200,11 @@ pub async fn sync_put(
             async {
                 // Post replies cannot be included in the total engagement index after they receive a reply
                 if !post_relationships_is_reply(&parent_author_id, &parent_post_id).await? {
-                    PostStream::put_score_index_sorted_set(
-                        &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                        parent_post_key_parts,
+                    PostStream::update_index_score(
+                        &parent_author_id,
+                        &parent_post_id,
                         ScoreAction::Increment(1.0),
+                        post_details.indexed_at,
                     )
                     .await?;
                 }
@@ -206,15 +219,33 @@ pub async fn sync_put(
         );
 
         handle_indexing_results!(indexing_results.0, indexing_results.1, indexing_results.2);
+
+        // The trending score depends on the updated counts
+        PostStream::update_root_trending_score(&parent_author_id, &parent_post_id).await?;
     }
 
     // PHASE 4: Add post related content
     let indexing_results = tokio::join!(
         post_relationships.put_to_index(&author_id, &post_id),
-        post_details.put_to_index(&author_id, reply_parent_post_key_wrapper, false)
+        post_details.put_to_index(&author_id, reply_parent_post_key_wrapper, false),
+        PostStream::add_to_mentions_sorted_sets(
+            &post_relationships.mentioned,
+            &author_id,
+            &post_id,
+            post_details.indexed_at
+        )
     );
 
-    handle_indexing_results!(indexing_results.0, indexing_results.1);
+    handle_indexing_results!(indexing_results.0, indexing_results.1, indexing_results.2);
+
+    // PHASE 5: Add the post to the trending streams once the counts and details are indexed
+    if !is_reply {
+        PostStream::update_trending_score(&author_id, &post_id).await?;
+        // The root posts are fanned out to the home timelines of the followers of the author
+        if HomeTimeline::is_enabled() {
+            HomeTimeline::fan_out(&author_id, &post_id, post_details.indexed_at).await?;
+        }
+    }
 
     Ok(())
 }
@@ -236,6 +267,11 @@ async fn sync_edit(
         .into());
     };
 
+    // The kind of a root post can change with the edit
+    if post.parent.is_none() {
+        PostStream::update_kind_sorted_sets(&post_details).await?;
+    }
+
     // Notifications
     // Determine the change type
     let change_type = if post_details.content == *"[DELETED]" {
@@ -307,8 +343,12 @@ pub async fn del(author_id: PubkyId, post_id: String) -> Result<(), DynError> {
     // But if there is any (OperationOutcome::Updated), then we simply update the post with keyword content [DELETED].
     // A deleted post is a post whose content is EXACTLY `"[DELETED]"`
     match execute_graph_operation(query).await? {
-        OperationOutcome::CreatedOrDeleted => sync_del(author_id, post_id).await?,
+        OperationOutcome::CreatedOrDeleted => {
+            Notification::deleted_post(&author_id, &post_id).await?;
+            sync_del(author_id, post_id).await?
+        }
         OperationOutcome::Updated => {
+            Notification::deleted_post(&author_id, &post_id).await?;
             let existing_relationships = PostRelationships::get_by_id(&author_id, &post_id).await?;
             let parent = match existing_relationships {
                 Some(relationships) => relationships.replied,
@@ -341,6 +381,11 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), DynErro
     // It could be a situation that relationship would not exist and we will treat the post as a not reply
     let is_reply =
         matches!(&post_relationships, Some(relationship) if relationship.replied.is_some());
+    // Creation time of the reply or repost, it is the time of the engagement with the parent post
+    let indexed_at = PostDetails::get_from_index(&author_id, &post_id)
+        .await?
+        .map(|details| details.indexed_at)
+        .unwrap_or_default();
 
     // DELETE TO INDEX - PHASE 1, decrease post counts
     let indexing_results = tokio::join!(
@@ -358,6 +403,10 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), DynErro
 
     // Use that index wrapper to delete a post reply
     let mut reply_parent_post_key_wrapper: Option<[String; 2]> = None;
+    let mentioned = post_relationships
+        .as_ref()
+        .map(|relationships| relationships.mentioned.clone())
+        .unwrap_or_default();
 
     if let Some(relationships) = post_relationships {
         // PHASE 2: Process POST REPLIES indexes
@@ -384,10 +433,11 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), DynErro
                 async {
                     // Post replies cannot be included in the total engagement index after the reply is deleted
                     if !post_relationships_is_reply(&parent_user_id, &parent_post_id).await? {
-                        PostStream::put_score_index_sorted_set(
-                            &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                            &parent_post_key_parts,
+                        PostStream::update_index_score(
+                            &parent_user_id,
+                            &parent_post_id,
                             ScoreAction::Decrement(1.0),
+                            indexed_at,
                         )
                         .await?;
                     }
@@ -405,6 +455,9 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), DynErro
             );
 
             handle_indexing_results!(indexing_results.0, indexing_results.1, indexing_results.2);
+
+            // The trending score depends on the updated counts
+            PostStream::update_root_trending_score(&parent_user_id, &parent_post_id).await?;
         }
         // PHASE 3: Process POST REPOSTED indexes
         // Decrement counts for resposted post if existed
@@ -427,10 +480,11 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), DynErro
                 async {
                     // Post replies cannot be included in the total engagement index after the repost is deleted
                     if !post_relationships_is_reply(&parsed_uri.user_id, &parent_post_id).await? {
-                        PostStream::put_score_index_sorted_set(
-                            &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                            parent_post_key_parts,
+                        PostStream::update_index_score(
+                            &parsed_uri.user_id,
+                            &parent_post_id,
                             ScoreAction::Decrement(1.0),
+                            indexed_at,
                         )
                         .await?;
                     }
@@ -448,14 +502,29 @@ pub async fn sync_del(author_id: PubkyId, post_id: String) -> Result<(), DynErro
             );
 
             handle_indexing_results!(indexing_results.0, indexing_results.1, indexing_results.2);
+
+            // The trending score depends on the updated counts
+            PostStream::update_root_trending_score(&parsed_uri.user_id, &parent_post_id).await?;
         }
     }
     let indexing_results = tokio::join!(
         PostDetails::delete(&author_id, &post_id, reply_parent_post_key_wrapper),
-        PostRelationships::delete(&author_id, &post_id)
+        PostRelationships::delete(&author_id, &post_id),
+        PostStream::remove_from_mentions_sorted_sets(&mentioned, &author_id, &post_id),
+        async {
+            if !is_reply && HomeTimeline::is_enabled() {
+                HomeTimeline::remove_post(&author_id, &post_id).await?;
+            }
+            Ok::<(), DynError>(())
+        }
     );
 
-    handle_indexing_results!(indexing_results.0, indexing_results.1);
+    handle_indexing_results!(
+        indexing_results.0,
+        indexing_results.1,
+        indexing_results.2,
+        indexing_results.3
+    );
 
     Ok(())
 }
@@ -1,23 +1,17 @@
 use crate::db::graph::exec::OperationOutcome;
-use crate::db::kv::index::json::JsonAction;
 use crate::events::error::EventProcessorError;
 use crate::events::retry::event::RetryEvent;
 use crate::models::notification::Notification;
-use crate::models::post::{PostCounts, PostStream};
+use crate::models::tag::label::canonical_label;
 use crate::models::tag::post::TagPost;
-use crate::models::tag::search::TagSearch;
-use crate::models::tag::traits::{TagCollection, TaggersCollection};
+use crate::models::tag::traits::TagCollection;
 use crate::models::tag::user::TagUser;
-use crate::models::user::UserCounts;
 use crate::types::DynError;
-use crate::{handle_indexing_results, ScoreAction};
 use chrono::Utc;
 use log::debug;
 use pubky_app_specs::{user_uri_builder, Resource};
 use pubky_app_specs::{ParsedUri, PubkyAppTag, PubkyId};
 
-use super::utils::post_relationships_is_reply;
-
 pub async fn sync_put(
     tag: PubkyAppTag,
     tagger_id: PubkyId,
@@ -28,6 +22,8 @@ pub async fn sync_put(
     // Parse the embeded URI to extract author_id and post_id using parse_tagged_post_uri
     let parsed_uri = ParsedUri::try_from(tag.uri.as_str())?;
     let indexed_at = Utc::now().timestamp_millis();
+    // The indexes and the graph share the canonical label, the one written by the tagger is kept for display
+    let tag_label = canonical_label(&tag.label).await?;
 
     match parsed_uri.resource {
         // If post_id is in the tagged URI, we place tag to a post.
@@ -37,15 +33,23 @@ pub async fn sync_put(
                 parsed_uri.user_id,
                 post_id,
                 tag_id,
-                tag.label,
-                tag.uri,
+                tag_label,
+                tag,
                 indexed_at,
             )
             .await
         }
         // If no post_id in the tagged URI, we place tag to a user.
         Resource::User => {
-            put_sync_user(tagger_id, parsed_uri.user_id, tag_id, tag.label, indexed_at).await
+            put_sync_user(
+                tagger_id,
+                parsed_uri.user_id,
+                tag_id,
+                tag_label,
+                tag.label,
+                indexed_at,
+            )
+            .await
         }
         other => Err(format!(
             "The tagged resource is not Post or User resource. Tagged resource: {:?}",
@@ -61,8 +65,8 @@ pub async fn sync_put(
 /// - `author_id` - The `PubkyId` of the author of the tagged post.
 /// - `post_id` - A `String` representing the unique identifier of the post being tagged.
 /// - `tag_id` - A `String` representing the unique identifier of the tag.
-/// - `tag_label` - A `String` representing the label of the tag.
-/// - `post_uri` - A `String` representing the homeserver URI of the tagged post.
+/// - `tag_label` - A `String` representing the canonical label of the tag.
+/// - `tag` - The `PubkyAppTag` with the label as written by the tagger and the URI of the tagged post.
 /// - `indexed_at` - A 64-bit integer representing the timestamp when the post was indexed.
 ///
 async fn put_sync_post(
@@ -71,7 +75,7 @@ async fn put_sync_post(
     post_id: String,
     tag_id: String,
     tag_label: String,
-    post_uri: String,
+    tag: PubkyAppTag,
     indexed_at: i64,
 ) -> Result<(), DynError> {
     match TagPost::put_to_graph(
@@ -80,6 +84,7 @@ async fn put_sync_post(
         Some(&post_id),
         &tag_id,
         &tag_label,
+        &tag.label,
         indexed_at,
     )
     .await?
@@ -91,85 +96,20 @@ async fn put_sync_post(
             Err(EventProcessorError::MissingDependency { dependency }.into())
         }
         OperationOutcome::CreatedOrDeleted => {
-            // SAVE TO INDEXES
-            let post_key_slice: &[&str] = &[&author_id, &post_id];
-
-            let indexing_results = tokio::join!(
-                // Update user counts for tagger
-                UserCounts::update(&tagger_user_id, "tagged", JsonAction::Increment(1), None),
-                // Increment in one the post tags
-                PostCounts::update_index_field(
-                    post_key_slice,
-                    "tags",
-                    JsonAction::Increment(1),
-                    None
-                ),
-                async {
-                    // Increase unique_tags if the tag does not exist already
-                    // NOTE: To update that field, it cannot exist in TagPost SORTED SET the tag. Thats why it has to be executed
-                    // before TagPost operation
-                    PostCounts::update_index_field(
-                        post_key_slice,
-                        "unique_tags",
-                        JsonAction::Increment(1),
-                        Some(&tag_label),
-                    )
-                    .await?;
-                    // Increment the label count to post
-                    TagPost::update_index_score(
-                        &author_id,
-                        Some(&post_id),
-                        &tag_label,
-                        ScoreAction::Increment(1.0),
-                    )
-                    .await?;
-                    Ok::<(), DynError>(())
-                },
-                // Add user tag in post
-                TagPost::add_tagger_to_index(
-                    &author_id,
-                    Some(&post_id),
-                    &tagger_user_id,
-                    &tag_label
-                ),
-                // Add post to label total engagement
-                TagSearch::update_index_score(
-                    &author_id,
-                    &post_id,
-                    &tag_label,
-                    ScoreAction::Increment(1.0)
-                ),
-                async {
-                    // Post replies cannot be included in the total engagement index once they have been tagged
-                    if !post_relationships_is_reply(&author_id, &post_id).await? {
-                        // Increment in one post global engagement
-                        PostStream::update_index_score(
-                            &author_id,
-                            &post_id,
-                            ScoreAction::Increment(1.0),
-                        )
-                        .await?;
-                    }
-                    Ok::<(), DynError>(())
-                },
-                // Add post to global label timeline
-                TagSearch::put_to_index(&author_id, &post_id, &tag_label),
-                // Save new notification
-                Notification::new_post_tag(&tagger_user_id, &author_id, &tag_label, &post_uri)
-            );
-
-            handle_indexing_results!(
-                indexing_results.0,
-                indexing_results.1,
-                indexing_results.2,
-                indexing_results.3,
-                indexing_results.4,
-                indexing_results.5,
-                indexing_results.6,
-                indexing_results.7
-            );
-
-            Ok(())
+            TagPost::put_to_indexes(
+                &tagger_user_id,
+                &author_id,
+                &post_id,
+                &tag_label,
+                &tag.label,
+                indexed_at,
+            )
+            .await
+            .map_err(|e| EventProcessorError::IndexWriteFailed {
+                message: e.to_string(),
+            })?;
+            // Save new notification
+            Notification::new_post_tag(&tagger_user_id, &author_id, &tag_label, &tag.uri).await
         }
     }
 }
@@ -179,6 +119,7 @@ async fn put_sync_user(
     tagged_user_id: PubkyId,
     tag_id: String,
     tag_label: String,
+    original_label: String,
     indexed_at: i64,
 ) -> Result<(), DynError> {
     match TagUser::put_to_graph(
@@ -187,6 +128,7 @@ async fn put_sync_user(
         None,
         &tag_id,
         &tag_label,
+        &original_label,
         indexed_at,
     )
     .await?
@@ -202,48 +144,19 @@ async fn put_sync_user(
             }
         }
         OperationOutcome::CreatedOrDeleted => {
-            // SAVE TO INDEX
-            let indexing_results = tokio::join!(
-                // Update user counts for the tagged user
-                UserCounts::update(&tagged_user_id, "tags", JsonAction::Increment(1), None),
-                // Update user counts for the tagger user
-                UserCounts::update(&tagger_user_id, "tagged", JsonAction::Increment(1), None),
-                async {
-                    // Increase unique_tags if the tag does not exist already
-                    // NOTE: To update that field, it cannot exist in TagUser SORTED SET the tag. Thats why it has to be executed
-                    // before TagUser operation
-                    UserCounts::update(
-                        &tagged_user_id,
-                        "unique_tags",
-                        JsonAction::Increment(1),
-                        Some(&tag_label),
-                    )
-                    .await?;
-                    // Add label count to the user profile tag
-                    TagUser::update_index_score(
-                        &tagged_user_id,
-                        None,
-                        &tag_label,
-                        ScoreAction::Increment(1.0),
-                    )
-                    .await?;
-                    Ok::<(), DynError>(())
-                },
-                // Add tagger to the user taggers list
-                TagUser::add_tagger_to_index(&tagged_user_id, None, &tagger_user_id, &tag_label),
-                // Save new notification
-                Notification::new_user_tag(&tagger_user_id, &tagged_user_id, &tag_label)
-            );
-
-            handle_indexing_results!(
-                indexing_results.0,
-                indexing_results.1,
-                indexing_results.2,
-                indexing_results.3,
-                indexing_results.4
-            );
-
-            Ok(())
+            TagUser::put_to_indexes(
+                &tagger_user_id,
+                &tagged_user_id,
+                &tag_label,
+                &original_label,
+                indexed_at,
+            )
+            .await
+            .map_err(|e| EventProcessorError::IndexWriteFailed {
+                message: e.to_string(),
+            })?;
+            // Save new notification
+            Notification::new_user_tag(&tagger_user_id, &tagged_user_id, &tag_label).await
         }
     }
 }
@@ -252,15 +165,43 @@ pub async fn del(user_id: PubkyId, tag_id: String) -> Result<(), DynError> {
     debug!("Deleting tag: {} -> {}", user_id, tag_id);
     let tag_details = TagUser::del_from_graph(&user_id, &tag_id).await?;
     // CHOOSE THE EVENT TYPE
-    if let Some((tagged_user_id, post_id, author_id, label)) = tag_details {
+    if let Some((tagged_user_id, post_id, author_id, label, original_label, indexed_at)) =
+        tag_details
+    {
         match (tagged_user_id, post_id, author_id) {
             // Delete user related indexes
             (Some(tagged_id), None, None) => {
-                del_sync_user(user_id, &tagged_id, &label).await?;
+                TagUser::del_from_indexes(
+                    &user_id,
+                    &tagged_id,
+                    &label,
+                    &original_label,
+                    indexed_at,
+                )
+                .await
+                .map_err(|e| EventProcessorError::IndexWriteFailed {
+                    message: e.to_string(),
+                })?;
+                // Remove the tagged user notification about the deleted tag
+                Notification::del_user_tag(&user_id, &tagged_id, &label).await?;
             }
             // Delete post related indexes
             (None, Some(post_id), Some(author_id)) => {
-                del_sync_post(user_id, &post_id, &author_id, &label).await?;
+                TagPost::del_from_indexes(
+                    &user_id,
+                    &author_id,
+                    &post_id,
+                    &label,
+                    &original_label,
+                    indexed_at,
+                )
+                .await
+                .map_err(|e| EventProcessorError::IndexWriteFailed {
+                    message: e.to_string(),
+                })?;
+                // Remove the post author notification about the deleted tag
+                let post_uri = format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}");
+                Notification::del_post_tag(&user_id, &label, &post_uri).await?;
             }
             // Handle other unexpected cases
             _ => {
@@ -272,117 +213,3 @@ pub async fn del(user_id: PubkyId, tag_id: String) -> Result<(), DynError> {
     }
     Ok(())
 }
-
-async fn del_sync_user(
-    tagger_id: PubkyId,
-    tagged_id: &str,
-    tag_label: &str,
-) -> Result<(), DynError> {
-    let indexing_results = tokio::join!(
-        // Update user counts in the tagged
-        UserCounts::update(tagged_id, "tags", JsonAction::Decrement(1), None),
-        // Update user counts in the tagger
-        UserCounts::update(&tagger_id, "tagged", JsonAction::Decrement(1), None),
-        async {
-            // Decrement label count to the user profile tag
-            TagUser::update_index_score(tagged_id, None, tag_label, ScoreAction::Decrement(1.0))
-                .await?;
-            // Decrease unique_tags
-            // NOTE: To update that field, we first need to decrement the value in the TagUser SORTED SET associated with that tag
-            UserCounts::update(
-                tagged_id,
-                "unique_tags",
-                JsonAction::Decrement(1),
-                Some(tag_label),
-            )
-            .await?;
-            Ok::<(), DynError>(())
-        },
-        async {
-            // Remove tagger to the user taggers list
-            TagUser(vec![tagger_id.to_string()])
-                .del_from_index(tagged_id, None, tag_label)
-                .await?;
-            Ok::<(), DynError>(())
-        }
-    );
-
-    handle_indexing_results!(
-        indexing_results.0,
-        indexing_results.1,
-        indexing_results.2,
-        indexing_results.3
-    );
-
-    Ok(())
-}
-
-async fn del_sync_post(
-    tagger_id: PubkyId,
-    post_id: &str,
-    author_id: &str,
-    tag_label: &str,
-) -> Result<(), DynError> {
-    // SAVE TO INDEXES
-    let post_key_slice: &[&str] = &[author_id, post_id];
-    let tag_post = TagPost(vec![tagger_id.to_string()]);
-
-    let indexing_results = tokio::join!(
-        // Update user counts for tagger
-        UserCounts::update(&tagger_id, "tagged", JsonAction::Decrement(1), None),
-        // Decrement in one the post tags
-        PostCounts::update_index_field(post_key_slice, "tags", JsonAction::Decrement(1), None),
-        async {
-            // Decrement label score in the post
-            TagPost::update_index_score(
-                author_id,
-                Some(post_id),
-                tag_label,
-                ScoreAction::Decrement(1.0),
-            )
-            .await?;
-            // Decrease unique_tag
-            // NOTE: To update that field, we first need to decrement the value in the SORTED SET associated with that tag
-            PostCounts::update_index_field(
-                post_key_slice,
-                "unique_tags",
-                JsonAction::Decrement(1),
-                Some(tag_label),
-            )
-            .await?;
-            Ok::<(), DynError>(())
-        },
-        // Decrease post from label total engagement
-        TagSearch::update_index_score(author_id, post_id, tag_label, ScoreAction::Decrement(1.0)),
-        async {
-            // Post replies cannot be included in the total engagement index once the tag have been deleted
-            if !post_relationships_is_reply(author_id, post_id).await? {
-                // Decrement in one post global engagement
-                PostStream::update_index_score(author_id, post_id, ScoreAction::Decrement(1.0))
-                    .await?;
-            }
-            Ok::<(), DynError>(())
-        },
-        async {
-            // Delete the tagger from the tag list
-            tag_post
-                .del_from_index(author_id, Some(post_id), tag_label)
-                .await?;
-            // NOTE: The tag search index, depends on the post taggers collection to delete
-            // Delete post from global label timeline
-            TagSearch::del_from_index(author_id, post_id, tag_label).await?;
-            Ok::<(), DynError>(())
-        }
-    );
-
-    handle_indexing_results!(
-        indexing_results.0,
-        indexing_results.1,
-        indexing_results.2,
-        indexing_results.3,
-        indexing_results.4,
-        indexing_results.5
-    );
-
-    Ok(())
-}
@@ -1,6 +1,7 @@
 use crate::db::graph::exec::{execute_graph_operation, OperationOutcome};
 use crate::events::error::EventProcessorError;
 use crate::handle_indexing_results;
+use crate::models::notification::Notification;
 use crate::models::user::UserSearch;
 use crate::models::{
     traits::Collection,
@@ -62,11 +63,14 @@ pub async fn del(user_id: PubkyId) -> Result<(), DynError> {
     // A deleted user is a user whose profile is empty and has username `"[DELETED]"`
     match execute_graph_operation(query).await? {
         OperationOutcome::CreatedOrDeleted => {
+            // The follows, tags and mentions of the user are not reported anymore
+            Notification::deleted_user(&user_id).await?;
             let indexing_results =
                 tokio::join!(UserDetails::delete(&user_id), UserCounts::delete(&user_id));
             handle_indexing_results!(indexing_results.0, indexing_results.1)
         }
         OperationOutcome::Updated => {
+            Notification::deleted_user(&user_id).await?;
             let deleted_user = PubkyAppUser {
                 name: "[DELETED]".to_string(),
                 bio: None,
@@ -80,7 +84,5 @@ pub async fn del(user_id: PubkyId) -> Result<(), DynError> {
         OperationOutcome::MissingDependency => return Err(EventProcessorError::SkipIndexing.into()),
     }
 
-    // TODO notifications for deleted user
-
     Ok(())
 }
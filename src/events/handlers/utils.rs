@@ -6,11 +6,7 @@ use crate::{models::post::PostRelationships, types::DynError};
 /// * `post_id` - The ID of the post to check
 ///
 pub async fn post_relationships_is_reply(author_id: &str, post_id: &str) -> Result<bool, DynError> {
-    match PostRelationships::get_by_id(author_id, post_id).await? {
-        Some(relationship) => Ok(relationship.replied.is_some()),
-        // If the post does not exist, it is treated as a reply to avoid incorrect assumptions
-        None => Ok(true),
-    }
+    PostRelationships::is_reply(author_id, post_id).await
 }
 
 /// A macro to handle the results of `tokio::join!` by checking for errors and propagating them.
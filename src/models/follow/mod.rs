@@ -1,9 +1,11 @@
 mod followers;
 mod following;
 mod friends;
+mod network;
 mod traits;
 
 pub use followers::Followers;
 pub use following::Following;
 pub use friends::Friends;
+pub use network::Network;
 pub use traits::UserFollows;
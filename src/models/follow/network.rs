@@ -0,0 +1,158 @@
+use crate::db::connectors::neo4j::get_neo4j_graph;
+use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::types::DynError;
+use crate::{queries, RedisOps};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use utoipa::ToSchema;
+
+pub const NETWORK_KEY_PARTS: [&str; 1] = ["Network"];
+const CACHE_SORTED_SET_PREFIX: &str = "Cache:Sorted";
+// TTL, 10MIN. The follows of the users reached can take that long to change the network
+const NETWORK_CACHE_TTL: i64 = 10 * 60;
+
+/// Follow distance explored from the user when the depth is not provided
+pub const DEFAULT_NETWORK_DEPTH: u8 = 2;
+/// Same cap as the friends of a user
+const MAX_NETWORK_USERS: usize = 10000;
+
+/// Users within a follow distance of a user, the closest first. The users at the same distance are ranked by how
+/// many of the users one follow closer follow them, then by their followers
+#[derive(Serialize, Deserialize, ToSchema, Default, Debug)]
+pub struct Network(pub Vec<String>);
+
+impl RedisOps for Network {}
+
+impl Network {
+    /// Retrieves the users reached following up to `depth` follows from the user, computing and caching
+    /// them on a cache miss
+    ///
+    /// # Arguments
+    /// * `user_id` - The user the follows start from
+    /// * `depth` - Follow distance (1-3) explored from the user. Defaults to `DEFAULT_NETWORK_DEPTH`
+    pub async fn get_by_id(user_id: &str, depth: Option<u8>) -> Result<Option<Self>, DynError> {
+        let users = Self::get_ranked(user_id, depth).await?;
+        if users.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(Self(
+            users.into_iter().map(|(user_id, _)| user_id).collect(),
+        )))
+    }
+
+    /// Retrieves up to `size` users of the network, taking them in turns from each follow distance so the
+    /// deeper users are represented even when the closest ones are enough to fill it
+    ///
+    /// # Arguments
+    /// * `user_id` - The user the follows start from
+    /// * `depth` - Follow distance (1-3) explored from the user. Defaults to `DEFAULT_NETWORK_DEPTH`
+    /// * `size` - The maximum number of users
+    pub async fn get_sample(
+        user_id: &str,
+        depth: Option<u8>,
+        size: usize,
+    ) -> Result<Vec<String>, DynError> {
+        let users = Self::get_ranked(user_id, depth).await?;
+        if users.len() <= size {
+            return Ok(users.into_iter().map(|(user_id, _)| user_id).collect());
+        }
+
+        // The users are already ranked inside each distance
+        let mut levels: Vec<VecDeque<String>> = Vec::new();
+        for (user_id, score) in users {
+            let distance = (score.floor() as usize).max(1);
+            if levels.len() < distance {
+                levels.resize_with(distance, VecDeque::new);
+            }
+            levels[distance - 1].push_back(user_id);
+        }
+        let mut sample = Vec::with_capacity(size);
+        while sample.len() < size {
+            for level in levels.iter_mut() {
+                if let Some(user_id) = level.pop_front() {
+                    sample.push(user_id);
+                }
+            }
+        }
+        sample.truncate(size);
+        Ok(sample)
+    }
+
+    pub fn clamp_depth(depth: Option<u8>) -> u8 {
+        depth.unwrap_or(DEFAULT_NETWORK_DEPTH).clamp(1, 3)
+    }
+
+    /// The users of the network with their rank score, from the cache or from the graph on a cache miss
+    async fn get_ranked(user_id: &str, depth: Option<u8>) -> Result<Vec<(String, f64)>, DynError> {
+        let depth = Self::clamp_depth(depth);
+        let depth_str = depth.to_string();
+        let key_parts = [&NETWORK_KEY_PARTS[..], &[user_id, &depth_str]].concat();
+
+        if let Some(users) = Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            None,
+            None,
+            Some(MAX_NETWORK_USERS),
+            SortOrder::Ascending,
+            Some(CACHE_SORTED_SET_PREFIX),
+        )
+        .await?
+        {
+            return Ok(users);
+        }
+
+        let users = Self::compute(user_id, depth).await?;
+        if users.is_empty() {
+            return Ok(users);
+        }
+        let sorted_set: Vec<(f64, &str)> = users
+            .iter()
+            .map(|(user_id, score)| (*score, user_id.as_str()))
+            .collect();
+        Self::put_index_sorted_set(
+            &key_parts,
+            &sorted_set,
+            Some(CACHE_SORTED_SET_PREFIX),
+            Some(NETWORK_CACHE_TTL),
+        )
+        .await?;
+        Ok(users)
+    }
+
+    /// The users of the network from the graph, exploring it one follow distance at a time. Each user is
+    /// scored by its distance plus a fraction that shrinks with its rank inside the distance
+    async fn compute(user_id: &str, depth: u8) -> Result<Vec<(String, f64)>, DynError> {
+        let mut reached = vec![user_id.to_string()];
+        let mut frontier = vec![user_id.to_string()];
+        let mut users = Vec::new();
+
+        for distance in 1..=depth {
+            let limit = MAX_NETWORK_USERS - users.len();
+            if frontier.is_empty() || limit == 0 {
+                break;
+            }
+            let mut result;
+            {
+                let graph = get_neo4j_graph()?;
+                let query = queries::get::network_level_users(&frontier, &reached, limit);
+
+                let graph = graph.lock().await;
+                result = graph.execute(query).await?;
+            }
+
+            let mut level = Vec::new();
+            while let Some(row) = result.next().await? {
+                let user_id: String = row.get("user_id")?;
+                level.push(user_id);
+            }
+            let level_size = level.len() as f64;
+            for (rank, user_id) in level.iter().enumerate() {
+                users.push((user_id.clone(), distance as f64 + rank as f64 / level_size));
+            }
+            reached.extend(level.iter().cloned());
+            frontier = level;
+        }
+        Ok(users)
+    }
+}
@@ -6,4 +6,5 @@ pub mod notification;
 pub mod post;
 pub mod tag;
 pub mod traits;
+pub mod trust;
 pub mod user;
@@ -5,6 +5,19 @@ use chrono::Utc;
 use neo4rs::Row;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use webhook::Webhook;
+
+pub mod webhook;
+
+/// References the notifications are looked up by, e.g. `Sorted:Notification:Refs:user:<user_id>`. Each
+/// member is the id of the notified user and the notification, `<user_id>:<notification_body_json>`
+const NOTIFICATION_REFS_KEY_PARTS: [&str; 2] = ["Notification", "Refs"];
+/// Notifications removed at once when what they report is undone or deleted
+const NOTIFICATION_REFS_PAGE: usize = 1000;
+/// The participants of the threads deeper than this many replies are not notified
+const MAX_THREAD_DEPTH: u8 = 20;
+/// Participants notified about each reply in a thread
+const MAX_THREAD_PARTICIPANTS: usize = 100;
 
 #[derive(Serialize, Deserialize, ToSchema, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -54,6 +67,12 @@ pub enum NotificationBody {
         parent_post_uri: String,
         reply_uri: String,
     },
+    ThreadReply {
+        replied_by: String,
+        root_post_uri: String,
+        parent_post_uri: String,
+        reply_uri: String,
+    },
     Repost {
         reposted_by: String,
         embed_uri: String,
@@ -88,6 +107,100 @@ impl Default for NotificationBody {
     }
 }
 
+impl NotificationBody {
+    /// Whether the notification points at the content behind `uri`. `PostDeleted` and `PostEdited`
+    /// are not considered, since they report the change itself.
+    pub fn references_uri(&self, uri: &str) -> bool {
+        match self {
+            NotificationBody::TagPost { post_uri, .. }
+            | NotificationBody::Mention { post_uri, .. } => post_uri == uri,
+            NotificationBody::Reply {
+                parent_post_uri,
+                reply_uri,
+                ..
+            } => parent_post_uri == uri || reply_uri == uri,
+            NotificationBody::ThreadReply {
+                root_post_uri,
+                parent_post_uri,
+                reply_uri,
+                ..
+            } => root_post_uri == uri || parent_post_uri == uri || reply_uri == uri,
+            NotificationBody::Repost {
+                embed_uri,
+                repost_uri,
+                ..
+            } => embed_uri == uri || repost_uri == uri,
+            _ => false,
+        }
+    }
+
+    /// The references the notification of `user_id` is removed by when what it reports is undone or deleted:
+    /// the follow or tag that created it, the posts it points at and the user that follows, tags or mentions
+    fn refs(&self, user_id: &str) -> Vec<String> {
+        let mut refs = match self {
+            NotificationBody::Follow { followed_by }
+            | NotificationBody::NewFriend { followed_by } => vec![
+                format!("follow:{followed_by}:{user_id}"),
+                format!("user:{followed_by}"),
+            ],
+            NotificationBody::TagProfile {
+                tagged_by,
+                tag_label,
+            } => vec![
+                format!("profile_tag:{tagged_by}:{user_id}:{tag_label}"),
+                format!("user:{tagged_by}"),
+            ],
+            NotificationBody::TagPost {
+                tagged_by,
+                tag_label,
+                post_uri,
+            } => vec![
+                format!("post_tag:{tagged_by}:{post_uri}:{tag_label}"),
+                format!("uri:{post_uri}"),
+            ],
+            NotificationBody::Mention {
+                mentioned_by,
+                post_uri,
+            } => vec![format!("user:{mentioned_by}"), format!("uri:{post_uri}")],
+            NotificationBody::Reply {
+                parent_post_uri,
+                reply_uri,
+                ..
+            } => vec![format!("uri:{parent_post_uri}"), format!("uri:{reply_uri}")],
+            NotificationBody::ThreadReply {
+                root_post_uri,
+                parent_post_uri,
+                reply_uri,
+                ..
+            } => vec![
+                format!("uri:{root_post_uri}"),
+                format!("uri:{parent_post_uri}"),
+                format!("uri:{reply_uri}"),
+            ],
+            NotificationBody::Repost {
+                embed_uri,
+                repost_uri,
+                ..
+            } => vec![format!("uri:{embed_uri}"), format!("uri:{repost_uri}")],
+            _ => vec![],
+        };
+        refs.sort();
+        refs.dedup();
+        refs
+    }
+
+    /// Whether the notification reports a follow, tag or mention made by `user_id`
+    pub fn references_user(&self, user_id: &str) -> bool {
+        match self {
+            NotificationBody::Follow { followed_by }
+            | NotificationBody::NewFriend { followed_by } => followed_by == user_id,
+            NotificationBody::TagProfile { tagged_by, .. } => tagged_by == user_id,
+            NotificationBody::Mention { mentioned_by, .. } => mentioned_by == user_id,
+            _ => false,
+        }
+    }
+}
+
 impl RedisOps for Notification {}
 
 impl Notification {
@@ -99,6 +212,7 @@ impl Notification {
     }
 
     /// Stores the `NotificationBody` in the sorted set for the user using the timestamp as the score.
+    /// The notification is referenced by what it reports, see `NotificationBody::refs`
     async fn put_to_index(&self, user_id: &str) -> Result<(), DynError> {
         let notification_body_json = serde_json::to_string(&self.body)?;
         let score = self.timestamp as f64;
@@ -109,7 +223,76 @@ impl Notification {
             None,
             None,
         )
-        .await
+        .await?;
+
+        let ref_member = format!("{user_id}:{notification_body_json}");
+        for reference in self.body.refs(user_id) {
+            Notification::put_index_sorted_set(
+                &[&NOTIFICATION_REFS_KEY_PARTS[..], &[reference.as_str()]].concat(),
+                &[(score, ref_member.as_str())],
+                None,
+                None,
+            )
+            .await?;
+        }
+
+        // Push the notification to the external endpoints subscribed to it
+        Webhook::dispatch(user_id, self).await;
+
+        Ok(())
+    }
+
+    /// Removes every notification with the `reference`, from the sorted sets of their users and from all
+    /// their references
+    async fn del_from_index_by_ref(reference: &str) -> Result<(), DynError> {
+        let key_parts = [&NOTIFICATION_REFS_KEY_PARTS[..], &[reference]].concat();
+        loop {
+            let ref_members = Notification::try_from_index_sorted_set(
+                &key_parts,
+                None,
+                None,
+                None,
+                Some(NOTIFICATION_REFS_PAGE),
+                SortOrder::Descending,
+                None,
+            )
+            .await?
+            .unwrap_or_default();
+
+            for (ref_member, _) in &ref_members {
+                let Some((user_id, notification_body_str)) = ref_member.split_once(':') else {
+                    continue;
+                };
+                Notification::remove_from_index_sorted_set(
+                    None,
+                    &["Notification", user_id],
+                    &[notification_body_str],
+                )
+                .await?;
+                if let Ok(body) = serde_json::from_str::<NotificationBody>(notification_body_str) {
+                    let refs = body.refs(user_id);
+                    let refs_key_parts: Vec<Vec<&str>> = refs
+                        .iter()
+                        .map(|other| [&NOTIFICATION_REFS_KEY_PARTS[..], &[other.as_str()]].concat())
+                        .collect();
+                    Notification::remove_from_index_sorted_sets(
+                        None,
+                        &refs_key_parts,
+                        &[ref_member.as_str()],
+                    )
+                    .await?;
+                }
+            }
+
+            let removed: Vec<&str> = ref_members
+                .iter()
+                .map(|(member, _)| member.as_str())
+                .collect();
+            Notification::remove_from_index_sorted_set(None, &key_parts, &removed).await?;
+            if ref_members.len() < NOTIFICATION_REFS_PAGE {
+                return Ok(());
+            }
+        }
     }
 
     /// Lists notifications from the sorted set for the user, based on skip and limit, or timestamp range.
@@ -183,6 +366,11 @@ impl Notification {
         Ok(())
     }
 
+    /// Removes the `Follow` and `NewFriend` notifications of a follow that no longer exists
+    pub async fn del_follow(user_id: &str, followee_id: &str) -> Result<(), DynError> {
+        Self::del_from_index_by_ref(&format!("follow:{user_id}:{followee_id}")).await
+    }
+
     pub async fn new_post_tag(
         user_id: &str,
         author_id: &str,
@@ -201,6 +389,11 @@ impl Notification {
         notification.put_to_index(author_id).await
     }
 
+    /// Removes the `TagPost` notification of a deleted post tag
+    pub async fn del_post_tag(user_id: &str, label: &str, post_uri: &str) -> Result<(), DynError> {
+        Self::del_from_index_by_ref(&format!("post_tag:{user_id}:{post_uri}:{label}")).await
+    }
+
     pub async fn new_user_tag(
         tagger_user_id: &str,
         tagged_user_id: &str,
@@ -217,6 +410,18 @@ impl Notification {
         notification.put_to_index(tagged_user_id).await
     }
 
+    /// Removes the `TagProfile` notification of a deleted user tag
+    pub async fn del_user_tag(
+        tagger_user_id: &str,
+        tagged_user_id: &str,
+        label: &str,
+    ) -> Result<(), DynError> {
+        Self::del_from_index_by_ref(&format!(
+            "profile_tag:{tagger_user_id}:{tagged_user_id}:{label}"
+        ))
+        .await
+    }
+
     pub async fn new_post_reply(
         user_id: &str,
         parent_uri: &str,
@@ -235,6 +440,63 @@ impl Notification {
         notification.put_to_index(parent_post_author).await
     }
 
+    /// Notifies the participants of a thread (the root author and every user that replied
+    /// somewhere in it) about a new reply. The replier, the direct parent author (who already
+    /// gets a `Reply` notification) and the users mentioned in the reply are skipped. Only the
+    /// threads up to `MAX_THREAD_DEPTH` replies deep and `MAX_THREAD_PARTICIPANTS` are notified.
+    pub async fn new_thread_reply(
+        user_id: &str,
+        parent_uri: &str,
+        reply_uri: &str,
+        parent_post_author: &str,
+        parent_post_id: &str,
+        mentioned: &[String],
+    ) -> Result<(), DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::get_thread_participants(
+                parent_post_author,
+                parent_post_id,
+                MAX_THREAD_DEPTH,
+                MAX_THREAD_PARTICIPANTS,
+            );
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let row = match result.next().await? {
+            Some(row) => row,
+            None => return Ok(()),
+        };
+
+        let root_author_id: String = row.get("root_author_id")?;
+        let root_post_id: String = row.get("root_post_id")?;
+        let participant_ids: Vec<String> = row.get("participant_ids").unwrap_or_default();
+
+        let root_post_uri = format!("pubky://{root_author_id}/pub/pubky.app/posts/{root_post_id}");
+
+        for participant_id in participant_ids {
+            if participant_id == user_id
+                || participant_id == parent_post_author
+                || mentioned.contains(&participant_id)
+            {
+                continue;
+            }
+            let body = NotificationBody::ThreadReply {
+                replied_by: user_id.to_string(),
+                root_post_uri: root_post_uri.clone(),
+                parent_post_uri: parent_uri.to_string(),
+                reply_uri: reply_uri.to_string(),
+            };
+            let notification = Notification::new(body);
+            notification.put_to_index(&participant_id).await?;
+        }
+
+        Ok(())
+    }
+
     pub async fn new_mention(
         user_id: &str,
         mentioned_id: &str,
@@ -300,6 +562,19 @@ impl Notification {
         notification.put_to_index(linked_post_author).await
     }
 
+    /// Removes the `Reply`, `ThreadReply`, `Repost`, `Mention` and `TagPost` notifications that point
+    /// at a deleted post
+    pub async fn deleted_post(author_id: &str, post_id: &str) -> Result<(), DynError> {
+        let deleted_uri = format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}");
+        Self::del_from_index_by_ref(&format!("uri:{deleted_uri}")).await
+    }
+
+    /// Removes the `Follow`, `NewFriend`, `TagProfile` and `Mention` notifications a deleted user left in
+    /// the sets of other users
+    pub async fn deleted_user(user_id: &str) -> Result<(), DynError> {
+        Self::del_from_index_by_ref(&format!("user:{user_id}")).await
+    }
+
     // Delete and Edit post notifications to users who interacted
 
     // A post you replied/reposted/tagged/bookmarked was edited or deleted
@@ -0,0 +1,388 @@
+use super::Notification;
+use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::types::DynError;
+use crate::RedisOps;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use log::{debug, error};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{HashMap, HashSet};
+use std::sync::RwLock;
+use std::time::Duration;
+
+pub const WEBHOOKS_GLOBAL_KEY_PARTS: [&str; 2] = ["Webhooks", "Global"];
+pub const WEBHOOKS_USER_KEY_PARTS: [&str; 2] = ["Webhooks", "User"];
+pub const WEBHOOK_DELIVERIES_KEY_PARTS: [&str; 2] = ["Webhooks", "Deliveries"];
+/// Every registered webhook, global or not
+pub const WEBHOOKS_ALL_KEY_PARTS: [&str; 2] = ["Webhooks", "All"];
+
+/// Header carrying the hex encoded HMAC-SHA256 of the request body, signed with the webhook secret
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Nexus-Signature";
+pub const WEBHOOK_ID_HEADER: &str = "X-Nexus-Webhook-Id";
+pub const WEBHOOK_ATTEMPT_HEADER: &str = "X-Nexus-Delivery-Attempt";
+
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Attempts kept in the delivery log of each webhook, the oldest are dropped
+const WEBHOOK_DELIVERIES_LIMIT: usize = 1000;
+/// Webhook IDs read at once from an index
+const WEBHOOK_IDS_PAGE: usize = 1000;
+
+static WEBHOOK_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .timeout(WEBHOOK_REQUEST_TIMEOUT)
+        .build()
+        .expect("Failed to build the webhook HTTP client")
+});
+
+/// Secrets of the registered webhooks by webhook ID. They are kept in the memory of the process that
+/// registers and delivers the webhooks, never in Redis
+static WEBHOOK_SECRETS: Lazy<RwLock<HashMap<String, String>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_backoff() -> u64 {
+    1000
+}
+
+/// An external endpoint that receives the notifications of one user, or of every user if `user_id` is not set
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    /// Secret used to sign the payloads. It is not stored with the webhook
+    #[serde(default, skip_serializing)]
+    pub secret: String,
+    /// Target user. Global webhook if `None`
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Notification types to deliver, e.g. `["reply", "mention"]`. All the types if empty
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Retries after the first failed attempt
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Initial delay between attempts in milliseconds, doubled on each retry
+    #[serde(default = "default_backoff")]
+    pub backoff: u64,
+}
+
+/// Signed JSON body POSTed to the webhook endpoints
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WebhookPayload {
+    pub webhook_id: String,
+    pub user_id: String,
+    pub notification: Notification,
+}
+
+/// Delivery log entry, one per attempt
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct WebhookDelivery {
+    pub user_id: String,
+    pub notification_timestamp: i64,
+    pub attempt: u32,
+    pub status_code: Option<u16>,
+    pub delivered: bool,
+    pub error: Option<String>,
+    pub timestamp: i64,
+}
+
+impl RedisOps for Webhook {}
+
+impl RedisOps for WebhookDelivery {}
+
+impl Webhook {
+    /// Stores the webhook and registers it in the global or the user webhooks index. A previous
+    /// registration with the same ID is replaced
+    pub async fn put_to_index(&self) -> Result<(), DynError> {
+        Self::delete(&self.id).await?;
+        WEBHOOK_SECRETS
+            .write()
+            .map_err(|_| "Webhook secrets lock poisoned")?
+            .insert(self.id.clone(), self.secret.clone());
+
+        self.put_index_json(&[&self.id], None, None).await?;
+        let score = Utc::now().timestamp_millis() as f64;
+        Self::put_index_sorted_set(
+            &WEBHOOKS_ALL_KEY_PARTS,
+            &[(score, self.id.as_str())],
+            None,
+            None,
+        )
+        .await?;
+        match &self.user_id {
+            Some(user_id) => {
+                Self::put_index_sorted_set(
+                    &[&WEBHOOKS_USER_KEY_PARTS[..], &[user_id.as_str()]].concat(),
+                    &[(score, self.id.as_str())],
+                    None,
+                    None,
+                )
+                .await
+            }
+            None => {
+                Self::put_index_sorted_set(
+                    &WEBHOOKS_GLOBAL_KEY_PARTS,
+                    &[(score, self.id.as_str())],
+                    None,
+                    None,
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn get_by_id(id: &str) -> Result<Option<Self>, DynError> {
+        Self::try_from_index_json(&[id], None).await
+    }
+
+    /// Unregisters the webhook. The delivery log is kept
+    pub async fn delete(id: &str) -> Result<(), DynError> {
+        WEBHOOK_SECRETS
+            .write()
+            .map_err(|_| "Webhook secrets lock poisoned")?
+            .remove(id);
+        Self::remove_from_index_sorted_set(None, &WEBHOOKS_ALL_KEY_PARTS, &[id]).await?;
+        let webhook = match Self::get_by_id(id).await? {
+            Some(webhook) => webhook,
+            None => return Ok(()),
+        };
+        match &webhook.user_id {
+            Some(user_id) => {
+                Self::remove_from_index_sorted_set(
+                    None,
+                    &[&WEBHOOKS_USER_KEY_PARTS[..], &[user_id.as_str()]].concat(),
+                    &[id],
+                )
+                .await?
+            }
+            None => {
+                Self::remove_from_index_sorted_set(None, &WEBHOOKS_GLOBAL_KEY_PARTS, &[id]).await?
+            }
+        }
+        Self::remove_from_index_multiple_json(&[&[id][..]]).await
+    }
+
+    /// Registers the webhooks listed in a JSON file. Used by operators to configure the watcher.
+    /// The registered webhooks that are not in the file anymore are unregistered
+    pub async fn register_from_file(path: &str) -> Result<(), DynError> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let webhooks: Vec<Webhook> = serde_json::from_str(&content)?;
+
+        let listed: HashSet<&str> = webhooks.iter().map(|webhook| webhook.id.as_str()).collect();
+        for key_parts in [&WEBHOOKS_ALL_KEY_PARTS, &WEBHOOKS_GLOBAL_KEY_PARTS] {
+            let registered = Self::get_ids(key_parts).await?;
+            for id in registered.iter().filter(|id| !listed.contains(id.as_str())) {
+                Self::delete(id).await?;
+            }
+        }
+
+        for webhook in webhooks {
+            webhook.put_to_index().await?;
+        }
+        Ok(())
+    }
+
+    /// The IDs of the webhooks of an index
+    async fn get_ids(key_parts: &[&str]) -> Result<Vec<String>, DynError> {
+        let mut ids = Vec::new();
+        let mut skip = 0;
+        loop {
+            let members = Self::try_from_index_sorted_set(
+                key_parts,
+                None,
+                None,
+                Some(skip),
+                Some(WEBHOOK_IDS_PAGE),
+                SortOrder::Ascending,
+                None,
+            )
+            .await?
+            .unwrap_or_default();
+            let page_len = members.len();
+            ids.extend(members.into_iter().map(|(id, _)| id));
+            if page_len < WEBHOOK_IDS_PAGE {
+                return Ok(ids);
+            }
+            skip += page_len;
+        }
+    }
+
+    /// Retrieves the global webhooks and the ones registered for the user, with their secret. The webhooks
+    /// registered by another process have no secret here and are left out
+    pub async fn get_for_user(user_id: &str) -> Result<Vec<Self>, DynError> {
+        let user_key_parts = [&WEBHOOKS_USER_KEY_PARTS[..], &[user_id]].concat();
+        let mut ids = Self::get_ids(&WEBHOOKS_GLOBAL_KEY_PARTS).await?;
+        ids.extend(Self::get_ids(&user_key_parts).await?);
+
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let key_parts_list: Vec<[&str; 1]> = ids.iter().map(|id| [id.as_str()]).collect();
+        let key_parts_list: Vec<&[&str]> = key_parts_list.iter().map(|k| &k[..]).collect();
+        let webhooks = Self::try_from_index_multiple_json(&key_parts_list).await?;
+
+        let secrets = WEBHOOK_SECRETS
+            .read()
+            .map_err(|_| "Webhook secrets lock poisoned")?;
+        Ok(webhooks
+            .into_iter()
+            .flatten()
+            .filter_map(|mut webhook| {
+                webhook.secret = secrets.get(&webhook.id)?.clone();
+                Some(webhook)
+            })
+            .collect())
+    }
+
+    /// Whether the webhook is subscribed to the type of the notification
+    pub fn accepts(&self, notification: &Notification) -> bool {
+        if self.types.is_empty() {
+            return true;
+        }
+        serde_json::to_value(&notification.body)
+            .ok()
+            .and_then(|body| {
+                body["type"]
+                    .as_str()
+                    .map(|t| self.types.iter().any(|s| s == t))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Hex encoded HMAC-SHA256 of the payload
+    pub fn sign(&self, payload: &str) -> Result<String, DynError> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())?;
+        mac.update(payload.as_bytes());
+        let signature = mac.finalize().into_bytes();
+        Ok(signature.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Sends the notification to every webhook of the user in the background. Failures are only
+    /// logged, a webhook must never block the indexing of an event
+    pub async fn dispatch(user_id: &str, notification: &Notification) {
+        let webhooks = match Self::get_for_user(user_id).await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                error!("Could not retrieve the webhooks of {}: {:?}", user_id, e);
+                return;
+            }
+        };
+
+        for webhook in webhooks {
+            if !webhook.accepts(notification) {
+                continue;
+            }
+            let payload = WebhookPayload {
+                webhook_id: webhook.id.clone(),
+                user_id: user_id.to_string(),
+                notification: Notification {
+                    timestamp: notification.timestamp,
+                    body: notification.body.clone(),
+                },
+            };
+            tokio::spawn(async move {
+                if let Err(e) = webhook.deliver(payload).await {
+                    error!("Webhook {} delivery failed: {:?}", webhook.id, e);
+                }
+            });
+        }
+    }
+
+    /// POSTs the payload, retrying with exponential backoff. Every attempt is added to the delivery log
+    async fn deliver(&self, payload: WebhookPayload) -> Result<(), DynError> {
+        let body = serde_json::to_string(&payload)?;
+        let signature = self.sign(&body)?;
+
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                let delay = self.backoff.saturating_mul(1u64 << (attempt - 1).min(16));
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+
+            let response = WEBHOOK_CLIENT
+                .post(&self.url)
+                .header("Content-Type", "application/json")
+                .header(WEBHOOK_SIGNATURE_HEADER, format!("sha256={signature}"))
+                .header(WEBHOOK_ID_HEADER, &self.id)
+                .header(WEBHOOK_ATTEMPT_HEADER, (attempt + 1).to_string())
+                .body(body.clone())
+                .send()
+                .await;
+
+            let (status_code, error) = match response {
+                Ok(response) if response.status().is_success() => {
+                    (Some(response.status().as_u16()), None)
+                }
+                Ok(response) => (
+                    Some(response.status().as_u16()),
+                    Some(format!("Unexpected status {}", response.status())),
+                ),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            let delivered = error.is_none();
+
+            WebhookDelivery {
+                user_id: payload.user_id.clone(),
+                notification_timestamp: payload.notification.timestamp,
+                attempt: attempt + 1,
+                status_code,
+                delivered,
+                error,
+                timestamp: Utc::now().timestamp_millis(),
+            }
+            .put_to_index(&self.id)
+            .await?;
+
+            if delivered {
+                debug!("Webhook {} delivered on attempt {}", self.id, attempt + 1);
+                return Ok(());
+            }
+        }
+
+        Err(format!("Gave up after {} attempts", self.max_retries + 1).into())
+    }
+}
+
+impl WebhookDelivery {
+    /// Adds the attempt to the delivery log, keeping the `WEBHOOK_DELIVERIES_LIMIT` latest ones
+    async fn put_to_index(&self, webhook_id: &str) -> Result<(), DynError> {
+        let delivery_json = serde_json::to_string(self)?;
+        Self::put_capped_index_sorted_sets(
+            &[[&WEBHOOK_DELIVERIES_KEY_PARTS[..], &[webhook_id]].concat()],
+            &[(self.timestamp as f64, delivery_json.as_str())],
+            WEBHOOK_DELIVERIES_LIMIT,
+            None,
+        )
+        .await
+    }
+
+    /// Lists the delivery log of a webhook, most recent attempts first
+    pub async fn get_by_webhook(
+        webhook_id: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Vec<Self>, DynError> {
+        let deliveries = Self::try_from_index_sorted_set(
+            &[&WEBHOOK_DELIVERIES_KEY_PARTS[..], &[webhook_id]].concat(),
+            None,
+            None,
+            skip,
+            limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
+
+        Ok(deliveries
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(delivery, _)| serde_json::from_str(&delivery).ok())
+            .collect())
+    }
+}
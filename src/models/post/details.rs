@@ -166,6 +166,7 @@ impl PostDetails {
             None => {
                 PostStream::remove_from_timeline_sorted_set(author_id, post_id).await?;
                 PostStream::remove_from_per_user_sorted_set(author_id, post_id).await?;
+                PostStream::remove_from_trending_sorted_sets(author_id, post_id).await?;
             }
             Some([parent_author_id, parent_post_id]) => {
                 PostStream::remove_from_post_reply_sorted_set(
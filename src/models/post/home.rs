@@ -0,0 +1,390 @@
+use super::{POST_PER_USER_KEY_PARTS, POST_TIMELINE_KEY_PARTS};
+use crate::db::kv::index::sets;
+use crate::db::kv::index::sorted_sets::{self, SortOrder, SORTED_PREFIX};
+use crate::models::follow::{Followers, Following, Friends, UserFollows};
+use crate::types::DynError;
+use crate::{Config, RedisOps};
+use log::{debug, info};
+use once_cell::sync::OnceCell;
+use std::collections::{HashMap, HashSet};
+
+pub const HOME_FOLLOWING_KEY_PARTS: [&str; 2] = ["Posts", "HomeFollowing"];
+pub const HOME_FRIENDS_KEY_PARTS: [&str; 2] = ["Posts", "HomeFriends"];
+const HOME_TIMELINE_PREFIX: &str = "Home:Timeline";
+/// Authors with too many followers to fan out their posts, `Home:Timeline:Popular`
+const POPULAR_AUTHORS_KEY_PARTS: [&str; 1] = ["Popular"];
+/// Same cap as the friends of a user
+const MAX_FOLLOWS: usize = 10000;
+/// Posts read at once while scanning a home timeline
+const HOME_SCAN_PAGE: usize = 1000;
+
+/// Precomputed Following and Friends timelines of each user. The root posts are fanned out to the home
+/// timelines of the followers and friends of their author when they are indexed, so reading a timeline does
+/// not merge the posts of every followed user. The posts of the popular authors are merged on read instead.
+/// Switched on with `HOME_TIMELINES`
+pub struct HomeTimeline;
+
+/// Settings of the home timelines, read from the config once
+static HOME_TIMELINE_SETTINGS: OnceCell<HomeTimelineSettings> = OnceCell::new();
+
+struct HomeTimelineSettings {
+    enabled: bool,
+    fanout_limit: usize,
+    size: usize,
+}
+
+impl From<&Config> for HomeTimelineSettings {
+    fn from(config: &Config) -> Self {
+        Self {
+            enabled: config.home_timelines,
+            fanout_limit: config.home_timeline_fanout_limit,
+            size: config.home_timeline_size,
+        }
+    }
+}
+
+impl HomeTimeline {
+    /// Stores the home timeline settings of the config, when the stack is set up
+    pub fn setup(config: &Config) {
+        match HOME_TIMELINE_SETTINGS.set(HomeTimelineSettings::from(config)) {
+            Err(_) => debug!("Home timeline settings were already set"),
+            Ok(()) => info!("Home timeline settings successfully set"),
+        }
+    }
+
+    /// The settings stored on setup, or the ones of the environment when the stack was not set up
+    fn settings() -> &'static HomeTimelineSettings {
+        HOME_TIMELINE_SETTINGS.get_or_init(|| HomeTimelineSettings::from(&Config::from_env()))
+    }
+
+    pub fn is_enabled() -> bool {
+        Self::settings().enabled
+    }
+
+    /// Posts of the Following or Friends home timeline of the user, newest first. `None` when the home timeline
+    /// has no posts in the range, e.g. it is not built yet or the range is older than its latest posts
+    pub async fn get_posts(
+        user_id: &str,
+        friends: bool,
+        start: Option<f64>,
+        end: Option<f64>,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Option<Vec<String>>, DynError> {
+        let key = Self::key(user_id, friends);
+        let popular_ids = Self::get_popular_ids(user_id, friends).await?;
+        if popular_ids.is_empty() {
+            let Some(post_keys) = Self::get_range(&key, start, end, skip, limit).await? else {
+                return Ok(None);
+            };
+            let post_keys = post_keys.into_iter().map(|(key, _)| key).collect();
+            return Self::filter_deleted(&key, post_keys).await.map(Some);
+        }
+
+        // The first `skip + limit` posts of each sorted set are enough to merge the page
+        let depth = limit.map(|limit| skip.unwrap_or(0).saturating_add(limit));
+        let Some(home_post_keys) = Self::get_range(&key, start, end, None, depth).await? else {
+            return Ok(None);
+        };
+
+        // The popular authors might have older posts fanned out already
+        let mut post_keys: HashMap<String, f64> = home_post_keys.into_iter().collect();
+        for author_id in &popular_ids {
+            let author_key = [&POST_PER_USER_KEY_PARTS[..], &[author_id.as_str()]]
+                .concat()
+                .join(":");
+            if let Some(post_ids) = Self::get_range(&author_key, start, end, None, depth).await? {
+                post_keys.extend(
+                    post_ids
+                        .into_iter()
+                        .map(|(post_id, score)| (format!("{}:{}", author_id, post_id), score)),
+                );
+            }
+        }
+
+        let mut post_keys: Vec<(String, f64)> = post_keys.into_iter().collect();
+        post_keys.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let post_keys = post_keys
+            .into_iter()
+            .skip(skip.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|(post_key, _)| post_key)
+            .collect();
+        Self::filter_deleted(&key, post_keys).await.map(Some)
+    }
+
+    /// Leaves out the deleted posts of a page of the home timeline and removes them from it. `remove_post` only
+    /// reaches the home timelines of the current followers of the author, e.g. not the ones of former followers
+    async fn filter_deleted(key: &str, post_keys: Vec<String>) -> Result<Vec<String>, DynError> {
+        let members: Vec<&str> = post_keys.iter().map(String::as_str).collect();
+        let scores =
+            sorted_sets::get_scores(SORTED_PREFIX, &POST_TIMELINE_KEY_PARTS.join(":"), &members)
+                .await?;
+        let (post_keys, deleted): (Vec<_>, Vec<_>) = post_keys
+            .into_iter()
+            .zip(scores)
+            .partition(|(_, score)| score.is_some());
+        if !deleted.is_empty() {
+            let deleted: Vec<&str> = deleted.iter().map(|(key, _)| key.as_str()).collect();
+            sorted_sets::del(SORTED_PREFIX, key, &deleted).await?;
+        }
+        Ok(post_keys
+            .into_iter()
+            .map(|(post_key, _)| post_key)
+            .collect())
+    }
+
+    /// Adds a root post to the home timelines of the followers and friends of its author. The authors with more
+    /// followers than `HOME_TIMELINE_FANOUT_LIMIT` are marked as popular and their posts are not fanned out
+    pub async fn fan_out(author_id: &str, post_id: &str, indexed_at: i64) -> Result<(), DynError> {
+        let settings = Self::settings();
+        let fanout_limit = settings.fanout_limit;
+        let followers = Followers::get_by_id(author_id, None, Some(fanout_limit + 1))
+            .await?
+            .unwrap_or_default()
+            .0;
+        if followers.len() > fanout_limit {
+            return sets::put(
+                HOME_TIMELINE_PREFIX,
+                &POPULAR_AUTHORS_KEY_PARTS.join(":"),
+                &[author_id],
+                None,
+            )
+            .await;
+        }
+        let friends: HashSet<String> = Friends::get_by_id(author_id, None, None)
+            .await?
+            .unwrap_or_default()
+            .0
+            .into_iter()
+            .collect();
+
+        let mut keys = Vec::with_capacity(followers.len() + friends.len());
+        for follower_id in &followers {
+            keys.push(Self::key(follower_id, false));
+            if friends.contains(follower_id) {
+                keys.push(Self::key(follower_id, true));
+            }
+        }
+        let post_key = format!("{}:{}", author_id, post_id);
+        sorted_sets::put_capped(
+            SORTED_PREFIX,
+            &keys,
+            &[(indexed_at as f64, post_key.as_str())],
+            settings.size,
+        )
+        .await
+    }
+
+    /// Removes a root post from the home timelines of the followers of its author. The posts left behind, e.g. in
+    /// the home timelines of former followers, are removed when they are read
+    pub async fn remove_post(author_id: &str, post_id: &str) -> Result<(), DynError> {
+        let followers = Followers::get_by_id(author_id, None, Some(MAX_FOLLOWS))
+            .await?
+            .unwrap_or_default()
+            .0;
+        let keys: Vec<String> = followers
+            .iter()
+            .flat_map(|follower_id| [Self::key(follower_id, false), Self::key(follower_id, true)])
+            .collect();
+        let post_key = format!("{}:{}", author_id, post_id);
+        sorted_sets::del_multiple(SORTED_PREFIX, &keys, &[post_key.as_str()]).await
+    }
+
+    /// Backfills the Following timeline of the follower with the latest posts of the followee. When they became
+    /// friends, the Friends timeline of each one gets the latest posts of the other
+    pub async fn follow(
+        follower_id: &str,
+        followee_id: &str,
+        new_friends: bool,
+    ) -> Result<(), DynError> {
+        Self::add_author_posts(follower_id, followee_id, false).await?;
+        if new_friends {
+            Self::add_author_posts(follower_id, followee_id, true).await?;
+            Self::add_author_posts(followee_id, follower_id, true).await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the posts of the followee from the Following timeline of the follower. When they were friends,
+    /// the posts of each one are removed from the Friends timeline of the other
+    pub async fn unfollow(
+        follower_id: &str,
+        followee_id: &str,
+        were_friends: bool,
+    ) -> Result<(), DynError> {
+        Self::remove_author_posts(follower_id, followee_id, false).await?;
+        if were_friends {
+            Self::remove_author_posts(follower_id, followee_id, true).await?;
+            Self::remove_author_posts(followee_id, follower_id, true).await?;
+        }
+        Self::demote_popular(followee_id).await
+    }
+
+    /// Unmarks a popular author once it has no more followers than `HOME_TIMELINE_FANOUT_LIMIT`. Its latest
+    /// posts are fanned out to the home timelines of its followers, since they are not merged on read anymore
+    async fn demote_popular(author_id: &str) -> Result<(), DynError> {
+        let popular_key = POPULAR_AUTHORS_KEY_PARTS.join(":");
+        let (_, is_popular) =
+            sets::check_member(HOME_TIMELINE_PREFIX, &popular_key, author_id).await?;
+        if !is_popular {
+            return Ok(());
+        }
+        let followers_count = Followers::get_set_size(&[author_id]).await?.unwrap_or(0);
+        if followers_count > Self::settings().fanout_limit {
+            return Ok(());
+        }
+
+        sets::del(HOME_TIMELINE_PREFIX, &popular_key, &[author_id]).await?;
+        let followers = Followers::get_by_id(author_id, None, Some(MAX_FOLLOWS))
+            .await?
+            .unwrap_or_default()
+            .0;
+        let friends: HashSet<String> = Friends::get_by_id(author_id, None, None)
+            .await?
+            .unwrap_or_default()
+            .0
+            .into_iter()
+            .collect();
+        for follower_id in &followers {
+            Self::add_author_posts(follower_id, author_id, false).await?;
+            if friends.contains(follower_id) {
+                Self::add_author_posts(follower_id, author_id, true).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the home timelines of the user from the latest posts of the users they follow.
+    /// The posts of the users have to be indexed first
+    pub async fn reindex(user_id: &str) -> Result<(), DynError> {
+        let following = Following::get_by_id(user_id, None, Some(MAX_FOLLOWS))
+            .await?
+            .unwrap_or_default()
+            .0;
+        let friends: HashSet<String> = Friends::get_by_id(user_id, None, None)
+            .await?
+            .unwrap_or_default()
+            .0
+            .into_iter()
+            .collect();
+        for followee_id in &following {
+            Self::add_author_posts(user_id, followee_id, false).await?;
+            if friends.contains(followee_id) {
+                Self::add_author_posts(user_id, followee_id, true).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The popular authors followed by the user, or the popular friends for the Friends timeline
+    async fn get_popular_ids(user_id: &str, friends: bool) -> Result<Vec<String>, DynError> {
+        let mut keys = vec![
+            format!(
+                "{}:{}",
+                HOME_TIMELINE_PREFIX,
+                POPULAR_AUTHORS_KEY_PARTS.join(":")
+            ),
+            format!("{}:{}", Following::prefix().await, user_id),
+        ];
+        if friends {
+            keys.push(format!("{}:{}", Followers::prefix().await, user_id));
+        }
+        sets::intersect(&keys).await
+    }
+
+    async fn add_author_posts(
+        user_id: &str,
+        author_id: &str,
+        friends: bool,
+    ) -> Result<(), DynError> {
+        let size = Self::settings().size;
+        let post_keys = Self::get_author_post_keys(author_id, Some(size)).await?;
+        let elements: Vec<(f64, &str)> = post_keys
+            .iter()
+            .map(|(post_key, score)| (*score, post_key.as_str()))
+            .collect();
+        sorted_sets::put_capped(
+            SORTED_PREFIX,
+            &[Self::key(user_id, friends)],
+            &elements,
+            size,
+        )
+        .await
+    }
+
+    /// Removes every post of the author from the home timeline of the user. The home timeline is scanned
+    /// instead of the posts of the author, which can be many more than the home timeline keeps
+    async fn remove_author_posts(
+        user_id: &str,
+        author_id: &str,
+        friends: bool,
+    ) -> Result<(), DynError> {
+        let key = Self::key(user_id, friends);
+        let author_prefix = format!("{}:", author_id);
+        let mut post_keys = Vec::new();
+        let mut skip = 0;
+        loop {
+            let page = Self::get_range(&key, None, None, Some(skip), Some(HOME_SCAN_PAGE))
+                .await?
+                .unwrap_or_default();
+            let page_len = page.len();
+            post_keys.extend(
+                page.into_iter()
+                    .map(|(post_key, _)| post_key)
+                    .filter(|post_key| post_key.starts_with(&author_prefix)),
+            );
+            if page_len < HOME_SCAN_PAGE {
+                break;
+            }
+            skip += page_len;
+        }
+        let post_keys: Vec<&str> = post_keys.iter().map(String::as_str).collect();
+        sorted_sets::del(SORTED_PREFIX, &key, &post_keys).await
+    }
+
+    /// The latest root posts of the author with their `indexed_at` score
+    async fn get_author_post_keys(
+        author_id: &str,
+        limit: Option<usize>,
+    ) -> Result<Vec<(String, f64)>, DynError> {
+        let key = [&POST_PER_USER_KEY_PARTS[..], &[author_id]]
+            .concat()
+            .join(":");
+        let post_ids = Self::get_range(&key, None, None, None, limit)
+            .await?
+            .unwrap_or_default();
+        Ok(post_ids
+            .into_iter()
+            .map(|(post_id, score)| (format!("{}:{}", author_id, post_id), score))
+            .collect())
+    }
+
+    /// Posts of a sorted set from `start` down to `end`, newest first
+    async fn get_range(
+        key: &str,
+        start: Option<f64>,
+        end: Option<f64>,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Option<Vec<(String, f64)>>, DynError> {
+        sorted_sets::get_range(
+            SORTED_PREFIX,
+            key,
+            end,
+            start,
+            skip,
+            limit,
+            SortOrder::Descending,
+        )
+        .await
+    }
+
+    fn key(user_id: &str, friends: bool) -> String {
+        let key_parts = match friends {
+            true => HOME_FRIENDS_KEY_PARTS,
+            false => HOME_FOLLOWING_KEY_PARTS,
+        };
+        [&key_parts[..], &[user_id]].concat().join(":")
+    }
+}
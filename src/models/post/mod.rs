@@ -1,6 +1,8 @@
 mod bookmark;
 mod counts;
 mod details;
+mod home;
+mod recommended;
 mod relationships;
 mod stream;
 mod view;
@@ -8,9 +10,14 @@ mod view;
 pub use bookmark::Bookmark;
 pub use counts::PostCounts;
 pub use details::PostDetails;
+pub use home::{HomeTimeline, HOME_FOLLOWING_KEY_PARTS, HOME_FRIENDS_KEY_PARTS};
+pub use recommended::{RecommendedPosts, POST_RECOMMENDED_KEY_PARTS};
 pub use relationships::PostRelationships;
 pub use stream::{
-    PostStream, StreamSource, POST_PER_USER_KEY_PARTS, POST_REPLIES_PER_POST_KEY_PARTS,
-    POST_REPLIES_PER_USER_KEY_PARTS, POST_TIMELINE_KEY_PARTS, POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+    PostStream, StreamSource, TagFilter, BOOKMARKS_USER_KEY_PARTS, POST_KIND_PER_USER_KEY_PARTS,
+    POST_KIND_TIMELINE_KEY_PARTS, POST_MENTIONS_PER_USER_KEY_PARTS, POST_PER_USER_KEY_PARTS,
+    POST_REPLIES_PER_POST_KEY_PARTS, POST_REPLIES_PER_USER_KEY_PARTS,
+    POST_TAGGED_PER_USER_KEY_PARTS, POST_TIMELINE_KEY_PARTS, POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+    POST_TRENDING_KEY_PARTS, POST_TRENDING_PER_USER_KEY_PARTS, TRENDING_DECAY_SECONDS,
 };
 pub use view::PostView;
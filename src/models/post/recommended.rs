@@ -0,0 +1,190 @@
+use super::{
+    PostStream, StreamSource, TagFilter, BOOKMARKS_USER_KEY_PARTS, POST_TAGGED_PER_USER_KEY_PARTS,
+};
+use crate::db::connectors::neo4j::get_neo4j_graph;
+use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::models::tag::label::canonical_label;
+use crate::models::user::Muted;
+use crate::types::{DynError, StreamSorting};
+use crate::{queries, RedisOps};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+pub const POST_RECOMMENDED_KEY_PARTS: [&str; 2] = ["Posts", "Recommended"];
+const CACHE_SORTED_SET_PREFIX: &str = "Cache:Sorted";
+// TTL, 10MIN. The feed keeps its order while the viewer pages through it
+const RECOMMENDED_CACHE_TTL: i64 = 10 * 60;
+
+/// Posts drawn from each source of candidates
+const CANDIDATES_PER_SOURCE: usize = 200;
+/// Labels the viewer tags with the most, the posts with any of them are candidates
+const INTEREST_LABELS: usize = 5;
+/// Each source adds `1 / (RANK_OFFSET + rank)` to the score of its posts, so the posts found by several
+/// sources rank above the top post of a single source
+const RANK_OFFSET: f64 = 60.0;
+/// Same cap as the friends of a user
+const MAX_MUTED: usize = 10000;
+
+/// "For you" feed of a viewer. It blends the latest posts of the network of the viewer, the latest posts
+/// with the labels the viewer tags with the most and the trending posts. The posts of the viewer and of the
+/// users it muted are left out, as well as the posts it already bookmarked or tagged.
+/// The ranked candidates are cached per viewer
+#[derive(Serialize, Deserialize)]
+pub struct RecommendedPosts;
+
+impl RedisOps for RecommendedPosts {}
+
+impl RecommendedPosts {
+    /// Recommended post keys for the viewer, the best first
+    pub async fn get_posts(
+        viewer_id: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Vec<String>, DynError> {
+        let key_parts = [&POST_RECOMMENDED_KEY_PARTS[..], &[viewer_id]].concat();
+
+        // The candidates are reused until they expire
+        if Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            None,
+            None,
+            Some(1),
+            SortOrder::Descending,
+            Some(CACHE_SORTED_SET_PREFIX),
+        )
+        .await?
+        .is_none()
+        {
+            let candidates = Self::rank_candidates(viewer_id).await?;
+            if candidates.is_empty() {
+                return Ok(vec![]);
+            }
+            let elements: Vec<(f64, &str)> = candidates
+                .iter()
+                .map(|(post_key, score)| (*score, post_key.as_str()))
+                .collect();
+            Self::put_index_sorted_set(
+                &key_parts,
+                &elements,
+                Some(CACHE_SORTED_SET_PREFIX),
+                Some(RECOMMENDED_CACHE_TTL),
+            )
+            .await?;
+        }
+
+        let post_keys = Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            None,
+            skip,
+            limit,
+            SortOrder::Descending,
+            Some(CACHE_SORTED_SET_PREFIX),
+        )
+        .await?;
+        Ok(post_keys
+            .map(|post_keys| post_keys.into_iter().map(|(key, _)| key).collect())
+            .unwrap_or_default())
+    }
+
+    /// Blends the candidates of each source by their rank in it
+    async fn rank_candidates(viewer_id: &str) -> Result<Vec<(String, f64)>, DynError> {
+        let network = PostStream::get_posts_by_source(
+            StreamSource::Network {
+                observer_id: viewer_id.to_string(),
+                depth: None,
+            },
+            StreamSorting::Timeline,
+            None,
+            None,
+            None,
+            Some(CANDIDATES_PER_SOURCE),
+        )
+        .await?;
+        let labels = Self::get_interest_labels(viewer_id).await?;
+        let interests = match labels.is_empty() {
+            true => vec![],
+            false => {
+                PostStream::get_posts_keys_by_tags(
+                    &TagFilter::from(labels),
+                    None,
+                    StreamSorting::Timeline,
+                    None,
+                    None,
+                    None,
+                    Some(CANDIDATES_PER_SOURCE),
+                )
+                .await?
+            }
+        };
+        let trending = PostStream::get_global_posts_keys(
+            StreamSorting::Trending,
+            None,
+            None,
+            None,
+            None,
+            Some(CANDIDATES_PER_SOURCE),
+        )
+        .await?;
+
+        let mut scores: HashMap<String, f64> = HashMap::new();
+        for post_keys in [network, interests, trending] {
+            for (rank, post_key) in post_keys.into_iter().enumerate() {
+                *scores.entry(post_key).or_default() += 1.0 / (RANK_OFFSET + rank as f64);
+            }
+        }
+
+        // The posts of the viewer and of the muted users are left out
+        let muted: HashSet<String> = Muted::get_by_id(viewer_id, None, Some(MAX_MUTED))
+            .await?
+            .unwrap_or_default()
+            .0
+            .into_iter()
+            .collect();
+        scores.retain(|post_key, _| match post_key.split_once(':') {
+            Some((author_id, _)) => author_id != viewer_id && !muted.contains(author_id),
+            None => false,
+        });
+        if scores.is_empty() {
+            return Ok(vec![]);
+        }
+
+        // The posts already bookmarked or tagged by the viewer were seen
+        let post_keys: Vec<String> = scores.keys().cloned().collect();
+        let members: Vec<&str> = post_keys.iter().map(|post_key| post_key.as_str()).collect();
+        for seen_key_parts in [&BOOKMARKS_USER_KEY_PARTS, &POST_TAGGED_PER_USER_KEY_PARTS] {
+            let key_parts = [&seen_key_parts[..], &[viewer_id]].concat();
+            let seen = Self::get_index_sorted_set_scores(None, &key_parts, &members).await?;
+            for (post_key, score) in members.iter().zip(seen) {
+                if score.is_some() {
+                    scores.remove(*post_key);
+                }
+            }
+        }
+
+        Ok(scores.into_iter().collect())
+    }
+
+    /// The labels the viewer tags with the most, as canonical labels
+    async fn get_interest_labels(viewer_id: &str) -> Result<Vec<String>, DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::user_top_labels(viewer_id, INTEREST_LABELS);
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut labels = Vec::new();
+        while let Some(row) = result.next().await? {
+            let label: String = row.get("label")?;
+            labels.push(canonical_label(&label).await?);
+        }
+        // Same order as the tag filters, to share their cached combinations
+        labels.sort();
+        labels.dedup();
+        Ok(labels)
+    }
+}
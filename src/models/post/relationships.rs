@@ -36,6 +36,15 @@ impl PostRelationships {
         }
     }
 
+    /// Checks if a post is a reply based on its relationships. A post that does not exist is treated as a
+    /// reply to avoid incorrect assumptions
+    pub async fn is_reply(author_id: &str, post_id: &str) -> Result<bool, DynError> {
+        match Self::get_by_id(author_id, post_id).await? {
+            Some(relationships) => Ok(relationships.replied.is_some()),
+            None => Ok(true),
+        }
+    }
+
     pub async fn get_from_index(
         author_id: &str,
         post_id: &str,
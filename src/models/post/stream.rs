@@ -1,26 +1,76 @@
-use super::{Bookmark, PostCounts, PostDetails, PostView};
-use crate::types::{DynError, Pagination, StreamSorting};
+use super::{
+    Bookmark, HomeTimeline, PostCounts, PostDetails, PostRelationships, PostView, RecommendedPosts,
+};
+use crate::models::tag::label::canonical_label;
+use crate::models::tag::list::UserList;
+use crate::models::tag::post::POST_TAGS_KEY_PARTS;
+use crate::models::trust::TrustScores;
+use crate::types::{
+    parse_string_to_bool, parse_string_to_u8, DynError, Pagination, StreamSorting, Timeframe,
+};
 use crate::{
     db::kv::index::sorted_sets::SortOrder,
     get_neo4j_graph,
     models::{
-        follow::{Followers, Following, Friends, UserFollows},
-        tag::search::TagSearch,
+        follow::{Followers, Following, Friends, Network, UserFollows},
+        tag::search::{TagSearch, TAG_GLOBAL_POST_TIMELINE, TAG_GLOBAL_POST_TRENDING},
     },
     queries, RedisOps, ScoreAction,
 };
+use chrono::Utc;
 use pubky_app_specs::PubkyAppPostKind;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tokio::task::spawn;
 use tokio::time::{timeout, Duration};
 use utoipa::ToSchema;
 
 pub const POST_TIMELINE_KEY_PARTS: [&str; 3] = ["Posts", "Global", "Timeline"];
 pub const POST_TOTAL_ENGAGEMENT_KEY_PARTS: [&str; 3] = ["Posts", "Global", "TotalEngagement"];
+pub const POST_TRENDING_KEY_PARTS: [&str; 3] = ["Posts", "Global", "Trending"];
+pub const POST_TRENDING_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "AuthorTrending"];
 pub const POST_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "AuthorParents"];
 pub const POST_REPLIES_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "AuthorReplies"];
 pub const POST_REPLIES_PER_POST_KEY_PARTS: [&str; 2] = ["Posts", "PostReplies"];
-const BOOKMARKS_USER_KEY_PARTS: [&str; 2] = ["Bookmarks", "User"];
+pub const POST_MENTIONS_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "Mentions"];
+pub const POST_TAGGED_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "Tagged"];
+pub const POST_KIND_TIMELINE_KEY_PARTS: [&str; 3] = ["Posts", "Kind", "Timeline"];
+pub const POST_KIND_PER_USER_KEY_PARTS: [&str; 2] = ["Posts", "AuthorKind"];
+pub const BOOKMARKS_USER_KEY_PARTS: [&str; 2] = ["Bookmarks", "User"];
+
+/// Prefix of the combinations of tags cached to serve the multi-tag streams
+const TAGS_CACHE_PREFIX: &str = "Cache:Sorted";
+/// The combinations of tags are shared by all the streams with the same tag filter and sorting.
+/// TTL, 1MIN. Newly tagged posts can take that long to show up
+const TAGS_CACHE_TTL: i64 = 60;
+/// Registry of the cached combinations of tags, only the most recently stored ones are kept
+const TAGS_CACHE_REGISTRY_KEY_PARTS: [&str; 3] = ["Posts", "Tags", "Cached"];
+const MAX_CACHED_TAG_COMBINATIONS: usize = 1000;
+/// The posts tagged with the excluded labels are left out while paging, up to this many posts are scanned
+const MAX_EXCLUDED_TAGS_SCAN: usize = 5000;
+/// Users of a source whose posts make up its streams
+const MAX_SOURCE_USERS: usize = 200;
+/// Latest posts of each user of a source that are filtered by the tags
+const SOURCE_TAGS_POSTS_PER_USER: usize = 100;
+/// Latest posts of a kind of an author that are ranked by engagement
+const MAX_AUTHOR_KIND_POSTS_RANKED: usize = 1000;
+
+/// Kinds of post with a global and per author timeline index, e.g. `Posts:Kind:Timeline:image`
+const POST_KINDS: [PubkyAppPostKind; 6] = [
+    PubkyAppPostKind::Short,
+    PubkyAppPostKind::Long,
+    PubkyAppPostKind::Image,
+    PubkyAppPostKind::Video,
+    PubkyAppPostKind::Link,
+    PubkyAppPostKind::File,
+];
+
+/// Timeframes with a total engagement index per window, e.g. `Posts:Global:TotalEngagement:Today:2024-10-31`
+const ENGAGEMENT_WINDOWS: [Timeframe; 2] = [Timeframe::Today, Timeframe::ThisMonth];
+
+/// Every `TRENDING_DECAY_SECONDS` a post needs ten times more engagement to keep its trending
+/// position against a newer post
+pub const TRENDING_DECAY_SECONDS: f64 = 45000.0;
 
 #[derive(ToSchema, Deserialize, Debug, Clone, PartialEq, Default)]
 #[serde(tag = "source", rename_all = "snake_case")]
@@ -38,6 +88,13 @@ pub enum StreamSource {
     Friends {
         observer_id: String,
     },
+    /// Posts of the users within `depth` follows of the observer
+    Network {
+        observer_id: String,
+        /// Follow distance (1-3) from the observer, 2 by default
+        #[serde(default, deserialize_with = "parse_string_to_u8")]
+        depth: Option<u8>,
+    },
     Bookmarks {
         observer_id: String,
     },
@@ -47,6 +104,29 @@ pub enum StreamSource {
     AuthorReplies {
         author_id: String,
     },
+    /// Posts of the users the observer tagged with `label`
+    TagList {
+        observer_id: String,
+        label: String,
+        /// Also include the users tagged with `label` by the friends of the observer
+        #[serde(default, deserialize_with = "parse_string_to_bool")]
+        include_friends: Option<bool>,
+    },
+    /// Posts of other users mentioning `user_id`
+    Mentions {
+        user_id: String,
+    },
+    /// Posts recommended to the viewer from its network, its interests and the trending posts
+    Recommended {
+        /// Set from the `viewer_id` of the stream
+        #[serde(skip_deserializing)]
+        viewer_id: String,
+    },
+    /// Posts tagged by `tagger_id`, only the ones tagged with `label` if provided
+    Tagged {
+        tagger_id: String,
+        label: Option<String>,
+    },
     #[default]
     All,
 }
@@ -57,7 +137,9 @@ impl StreamSource {
             StreamSource::Followers { observer_id }
             | StreamSource::Following { observer_id }
             | StreamSource::Friends { observer_id }
-            | StreamSource::Bookmarks { observer_id } => Some(observer_id),
+            | StreamSource::Network { observer_id, .. }
+            | StreamSource::Bookmarks { observer_id }
+            | StreamSource::TagList { observer_id, .. } => Some(observer_id),
             _ => None,
         }
     }
@@ -75,6 +157,72 @@ impl StreamSource {
     }
 }
 
+/// Tags of the posts of a stream
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TagFilter {
+    /// The posts are tagged with at least one of these labels
+    pub any: Vec<String>,
+    /// The posts are tagged with every one of these labels
+    pub all: Vec<String>,
+    /// The posts are not tagged with any of these labels
+    pub none: Vec<String>,
+}
+
+impl From<Vec<String>> for TagFilter {
+    fn from(any: Vec<String>) -> Self {
+        Self {
+            any,
+            ..Default::default()
+        }
+    }
+}
+
+impl TagFilter {
+    pub fn is_empty(&self) -> bool {
+        self.any.is_empty() && self.all.is_empty() && self.none.is_empty()
+    }
+
+    /// The label of the filters of a single label, served straight from the label indexes
+    fn single_label(&self) -> Option<&str> {
+        match (
+            self.any.as_slice(),
+            self.all.as_slice(),
+            self.none.is_empty(),
+        ) {
+            ([label], [], true) | ([], [label], true) => Some(label.as_str()),
+            _ => None,
+        }
+    }
+
+    /// The labels are stored with their canonical label, sorted and without duplicates
+    async fn to_canonical(&self) -> Result<Self, DynError> {
+        async fn canonical_labels(labels: &[String]) -> Result<Vec<String>, DynError> {
+            let mut canonical_labels = Vec::with_capacity(labels.len());
+            for label in labels {
+                canonical_labels.push(canonical_label(label).await?);
+            }
+            canonical_labels.sort();
+            canonical_labels.dedup();
+            Ok(canonical_labels)
+        }
+        Ok(Self {
+            any: canonical_labels(&self.any).await?,
+            all: canonical_labels(&self.all).await?,
+            none: canonical_labels(&self.none).await?,
+        })
+    }
+
+    /// Whether the filter has labels the posts have to be tagged with
+    fn includes(&self) -> bool {
+        !self.any.is_empty() || !self.all.is_empty()
+    }
+
+    /// Identifies the labels to include in the key of their cached combination
+    fn cache_id(&self) -> String {
+        format!("any={}|all={}", self.any.join(","), self.all.join(","))
+    }
+}
+
 #[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
 pub struct PostStream(pub Vec<PostView>);
 
@@ -86,15 +234,89 @@ impl PostStream {
         pagination: Pagination,
         sorting: StreamSorting,
         viewer_id: Option<String>,
-        tags: Option<Vec<String>>,
+        tags: Option<TagFilter>,
         kind: Option<PubkyAppPostKind>,
+        timeframe: Option<Timeframe>,
     ) -> Result<Option<Self>, DynError> {
+        // Only the engagement inside the timeframe window is counted, the rest of sortings ignore it
+        let window = timeframe.filter(|timeframe| {
+            sorting == StreamSorting::TotalEngagement && *timeframe != Timeframe::AllTime
+        });
+
+        // The tags are stored with their canonical label
+        let tags = match tags.filter(|tags| !tags.is_empty()) {
+            Some(tags) => Some(tags.to_canonical().await?),
+            None => None,
+        };
+
+        // The lists are defined by the canonical label
+        let source = match source {
+            StreamSource::TagList {
+                observer_id,
+                label,
+                include_friends,
+            } => StreamSource::TagList {
+                observer_id,
+                label: canonical_label(&label).await?,
+                include_friends,
+            },
+            StreamSource::Tagged {
+                tagger_id,
+                label: Some(label),
+            } => StreamSource::Tagged {
+                tagger_id,
+                label: Some(canonical_label(&label).await?),
+            },
+            // The networks are cached per depth
+            StreamSource::Network { observer_id, depth } => StreamSource::Network {
+                observer_id,
+                depth: Some(Network::clamp_depth(depth)),
+            },
+            source => source,
+        };
+
+        // The recommended posts are ranked by their own blend, whatever the sorting
+        if let StreamSource::Recommended {
+            viewer_id: recommended_id,
+        } = &source
+        {
+            let post_keys =
+                RecommendedPosts::get_posts(recommended_id, pagination.skip, pagination.limit)
+                    .await?;
+            if post_keys.is_empty() {
+                return Ok(None);
+            }
+            return Self::from_listed_post_ids(viewer_id, &post_keys).await;
+        }
+
+        // The trust weighted streams rank the posts by the trust of the viewer on their authors. The trust
+        // in a network is explored as deep as the network
+        let trust = match (&sorting, &viewer_id) {
+            (StreamSorting::TrustWeighted, Some(viewer_id)) => {
+                let depth = match &source {
+                    StreamSource::Network { depth, .. } => *depth,
+                    _ => None,
+                };
+                match TrustScores::get_by_id(viewer_id, depth, None, None).await? {
+                    Some(trust) => Some(trust),
+                    None => return Ok(None),
+                }
+            }
+            (StreamSorting::TrustWeighted, None) => {
+                return Err("viewer_id should be provided for trust weighted streams".into())
+            }
+            _ => None,
+        };
+
         // Decide whether to use index or fallback to graph query
-        let use_index = Self::can_use_index(&sorting, &source, &tags, &kind);
+        let use_index = Self::can_use_index(&sorting, &source, &tags, &kind, &window);
 
         let post_keys = match use_index {
-            true => Self::get_from_index(source, sorting, &tags, pagination).await?,
-            false => Self::get_from_graph(source, sorting, &tags, pagination, kind).await?,
+            true => Self::get_from_index(source, sorting, &tags, pagination, kind, window).await?,
+            false => {
+                Self::get_from_graph(source, sorting, &tags, pagination, kind, window, trust)
+                    .await?
+            }
         };
 
         if post_keys.is_empty() {
@@ -108,25 +330,71 @@ impl PostStream {
     fn can_use_index(
         sorting: &StreamSorting,
         source: &StreamSource,
-        tags: &Option<Vec<String>>,
+        tags: &Option<TagFilter>,
         kind: &Option<PubkyAppPostKind>,
+        window: &Option<Timeframe>,
     ) -> bool {
-        if kind.is_some() {
+        // There is no index of the trust of each viewer on the posts
+        if *sorting == StreamSorting::TrustWeighted {
             return false;
         }
+        // We have a sorted set per window only for the global engagement of the calendar windows in UTC
+        if let Some(window) = window {
+            return ENGAGEMENT_WINDOWS.contains(window)
+                && kind.is_none()
+                && matches!((source, tags), (StreamSource::All, None));
+        }
+        // We have a timeline sorted set per kind, global and per author. The global engagement and the
+        // label indexes are combined with them
+        if kind.is_some() {
+            return matches!(
+                (sorting, source, tags),
+                (
+                    StreamSorting::Timeline | StreamSorting::TotalEngagement,
+                    StreamSource::All,
+                    _
+                ) | (
+                    StreamSorting::Timeline | StreamSorting::TotalEngagement,
+                    StreamSource::Author { .. },
+                    None
+                )
+            );
+        }
         match (sorting, source, tags) {
             // We have a sorted set for posts by a specific author
             (StreamSorting::Timeline, StreamSource::Author { .. }, None) => true,
             // We have a sorted set for global for any sorting
             (_, StreamSource::All, None) => true,
-            // We have a sorted set for posts by tags for any sorting for a single tag
-            (_, StreamSource::All, Some(tags)) if tags.len() == 1 => true,
-            // We can use sorted set for posts by source only for timeline
-            (StreamSorting::Timeline, StreamSource::Following { .. }, None) => true,
-            (StreamSorting::Timeline, StreamSource::Followers { .. }, None) => true,
-            (StreamSorting::Timeline, StreamSource::Friends { .. }, None) => true,
+            // We have a sorted set for posts by tags for any sorting, the combinations of tags are computed from them
+            (_, StreamSource::All, Some(_)) => true,
+            // The posts of the users of a source are filtered by the combination of their tags, with the same
+            // sortings as the streams by source
+            (
+                StreamSorting::Timeline | StreamSorting::Trending,
+                StreamSource::Following { .. }
+                | StreamSource::Followers { .. }
+                | StreamSource::Friends { .. }
+                | StreamSource::Network { .. }
+                | StreamSource::TagList { .. }
+                | StreamSource::Author { .. },
+                Some(_),
+            ) => true,
+            // We can use sorted set for posts by source only for timeline and trending
+            (
+                StreamSorting::Timeline | StreamSorting::Trending,
+                StreamSource::Following { .. }
+                | StreamSource::Followers { .. }
+                | StreamSource::Friends { .. }
+                | StreamSource::Network { .. }
+                | StreamSource::TagList { .. },
+                None,
+            ) => true,
             // We have a sorted set for bookmarks only for timeline
             (StreamSorting::Timeline, StreamSource::Bookmarks { .. }, None) => true,
+            // We have a sorted set for mentions only for timeline
+            (StreamSorting::Timeline, StreamSource::Mentions { .. }, None) => true,
+            // We have a sorted set for the posts tagged by a user only for timeline, sorted by tag time
+            (StreamSorting::Timeline, StreamSource::Tagged { .. }, None) => true,
             // We can use sorted set of post replies
             (_, StreamSource::PostReplies { .. }, _) => true,
             // We can use sorted set of author replies
@@ -140,9 +408,15 @@ impl PostStream {
     async fn get_from_index(
         source: StreamSource,
         sorting: StreamSorting,
-        tags: &Option<Vec<String>>,
+        tags: &Option<TagFilter>,
         pagination: Pagination,
+        kind: Option<PubkyAppPostKind>,
+        window: Option<Timeframe>,
     ) -> Result<Vec<String>, DynError> {
+        if let Some(kind) = kind {
+            return Self::get_posts_keys_by_kind(source, &kind, sorting, tags, pagination).await;
+        }
+
         let start = pagination.start;
         let end = pagination.end;
         let skip = pagination.skip;
@@ -151,16 +425,29 @@ impl PostStream {
         match (source, tags) {
             // Global post streams
             (StreamSource::All, None) => {
-                Self::get_global_posts_keys(sorting, start, end, skip, limit).await
+                Self::get_global_posts_keys(sorting, window, start, end, skip, limit).await
             }
             // Streams by tags
-            (StreamSource::All, Some(tags)) if tags.len() == 1 => {
-                Self::get_posts_keys_by_tag(&tags[0], sorting, start, end, skip, limit).await
-            }
+            (StreamSource::All, Some(tags)) => match tags.single_label() {
+                Some(label) => {
+                    Self::get_posts_keys_by_tag(label, sorting, start, end, skip, limit).await
+                }
+                None => {
+                    Self::get_posts_keys_by_tags(tags, None, sorting, start, end, skip, limit).await
+                }
+            },
             // Bookmark streams
             (StreamSource::Bookmarks { observer_id }, None) => {
                 Self::get_bookmarked_posts(&observer_id, start, end, skip, limit).await
             }
+            // Mention streams
+            (StreamSource::Mentions { user_id }, None) => {
+                Self::get_mentioned_posts(&user_id, start, end, skip, limit).await
+            }
+            // Streams of the posts tagged by a user
+            (StreamSource::Tagged { tagger_id, label }, None) => {
+                Self::get_tagged_posts(&tagger_id, label.as_deref(), start, end, skip, limit).await
+            }
             // Stream of replies to specific a post
             (StreamSource::PostReplies { author_id, post_id }, None) => {
                 Self::get_post_replies(&author_id, &post_id, start, end, limit).await
@@ -173,9 +460,15 @@ impl PostStream {
             (StreamSource::AuthorReplies { author_id }, None) => {
                 Self::get_author_posts(&author_id, start, end, skip, limit, true).await
             }
-            // Streams by simple source/reach: Following, Followers, Friends
-            (source, None) => Self::get_posts_by_source(source, start, end, skip, limit).await,
-            _ => Ok(vec![]),
+            // Streams by simple source/reach: Following, Followers, Friends, Network, TagList
+            (source, None) => {
+                Self::get_posts_by_source(source, sorting, start, end, skip, limit).await
+            }
+            // Streams by source/reach or author, filtered by tags
+            (source, Some(tags)) => {
+                Self::get_posts_by_source_and_tags(source, tags, sorting, start, end, skip, limit)
+                    .await
+            }
         }
     }
 
@@ -183,14 +476,24 @@ impl PostStream {
     async fn get_from_graph(
         source: StreamSource,
         sorting: StreamSorting,
-        tags: &Option<Vec<String>>,
+        tags: &Option<TagFilter>,
         pagination: Pagination,
         kind: Option<PubkyAppPostKind>,
+        window: Option<Timeframe>,
+        trust: Option<TrustScores>,
     ) -> Result<Vec<String>, DynError> {
         let mut result;
         {
             let graph = get_neo4j_graph()?;
-            let query = queries::get::post_stream(source, sorting, tags, pagination, kind);
+            let query = queries::get::post_stream(
+                source,
+                sorting,
+                tags,
+                pagination,
+                kind,
+                window,
+                trust.as_ref(),
+            );
 
             let graph = graph.lock().await;
 
@@ -215,37 +518,43 @@ impl PostStream {
 
     pub async fn get_global_posts_keys(
         sorting: StreamSorting,
+        window: Option<Timeframe>,
         start: Option<f64>,
         end: Option<f64>,
         skip: Option<usize>,
         limit: Option<usize>,
     ) -> Result<Vec<String>, DynError> {
-        let sorted_set = match sorting {
-            StreamSorting::TotalEngagement => {
-                Self::try_from_index_sorted_set(
-                    &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
-                    start,
-                    end,
-                    skip,
-                    limit,
-                    SortOrder::Descending,
-                    None,
-                )
-                .await?
+        // Posts without engagement inside the window are left out
+        let (window, end) = match window {
+            Some(timeframe) if sorting == StreamSorting::TotalEngagement => (
+                timeframe
+                    .to_current_window_id()
+                    .map(|window_id| (timeframe.to_string(), window_id)),
+                end.or(Some(1.0)),
+            ),
+            _ => (None, end),
+        };
+        let key_parts = match (sorting, &window) {
+            (StreamSorting::TotalEngagement, Some((timeframe, window_id))) => {
+                Self::engagement_window_key_parts(timeframe, window_id)
             }
-            StreamSorting::Timeline => {
-                Self::try_from_index_sorted_set(
-                    &POST_TIMELINE_KEY_PARTS,
-                    start,
-                    end,
-                    skip,
-                    limit,
-                    SortOrder::Descending,
-                    None,
-                )
-                .await?
+            (StreamSorting::TotalEngagement, None) => POST_TOTAL_ENGAGEMENT_KEY_PARTS.to_vec(),
+            (StreamSorting::Timeline, _) => POST_TIMELINE_KEY_PARTS.to_vec(),
+            (StreamSorting::Trending, _) => POST_TRENDING_KEY_PARTS.to_vec(),
+            (StreamSorting::TrustWeighted, _) => {
+                return Err("The trust weighted streams are not indexed".into())
             }
         };
+        let sorted_set = Self::try_from_index_sorted_set(
+            &key_parts,
+            start,
+            end,
+            skip,
+            limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
         match sorted_set {
             Some(post_keys) => Ok(post_keys.into_iter().map(|(key, _)| key).collect()),
             None => Ok(vec![]),
@@ -321,11 +630,448 @@ impl PostStream {
 
     pub async fn get_posts_by_source(
         source: StreamSource,
+        sorting: StreamSorting,
+        start: Option<f64>,
+        end: Option<f64>,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Vec<String>, DynError> {
+        // The Following and Friends timelines are precomputed on write when the home timelines are enabled
+        if sorting == StreamSorting::Timeline && HomeTimeline::is_enabled() {
+            let home_timeline = match &source {
+                StreamSource::Following { observer_id } => Some((observer_id, false)),
+                StreamSource::Friends { observer_id } => Some((observer_id, true)),
+                _ => None,
+            };
+            if let Some((user_id, friends)) = home_timeline {
+                if let Some(post_keys) =
+                    HomeTimeline::get_posts(user_id, friends, start, end, skip, limit).await?
+                {
+                    return Ok(post_keys);
+                }
+            }
+        }
+
+        let user_ids = Self::get_source_user_ids(source).await?;
+
+        if !user_ids.is_empty() {
+            let post_keys = Self::get_posts_for_user_ids(
+                &user_ids.iter().map(AsRef::as_ref).collect::<Vec<_>>(),
+                sorting,
+                start,
+                end,
+                skip,
+                limit,
+            )
+            .await?;
+            Ok(post_keys)
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Posts with any, all or none of the tags of the filter and of `kind` if provided, from the combination
+    /// of the label and kind indexes. Without labels or kind to include, from the global index of the sorting
+    pub async fn get_posts_keys_by_tags(
+        tags: &TagFilter,
+        kind: Option<&str>,
+        sorting: StreamSorting,
+        start: Option<f64>,
+        end: Option<f64>,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Vec<String>, DynError> {
+        let (prefix, key_parts) = match Self::combine_tags(tags, kind, &sorting).await? {
+            Some(key_parts) => (Some(TAGS_CACHE_PREFIX), key_parts),
+            None => {
+                let key_parts = match sorting {
+                    StreamSorting::TotalEngagement => POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+                    StreamSorting::Trending => POST_TRENDING_KEY_PARTS,
+                    _ => POST_TIMELINE_KEY_PARTS,
+                };
+                (
+                    None,
+                    key_parts.iter().map(|part| part.to_string()).collect(),
+                )
+            }
+        };
+        let key_parts: Vec<&str> = key_parts.iter().map(|part| part.as_str()).collect();
+
+        if !tags.none.is_empty() {
+            let pagination = Pagination {
+                start,
+                end,
+                skip,
+                limit,
+            };
+            return Self::get_posts_keys_excluding_tags(prefix, &key_parts, &tags.none, pagination)
+                .await;
+        }
+
+        let post_keys = Self::try_from_index_sorted_set(
+            &key_parts,
+            start,
+            end,
+            skip,
+            limit,
+            SortOrder::Descending,
+            prefix,
+        )
+        .await?;
+
+        match post_keys {
+            Some(post_keys) => Ok(post_keys.into_iter().map(|(key, _)| key).collect()),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Posts of the users of the source or of the author with any, all or none of the tags of the filter.
+    /// The latest posts of each user in the index of the sorting are the candidates, the ones missing from
+    /// the combination of the label indexes or tagged with the excluded labels are left out
+    pub async fn get_posts_by_source_and_tags(
+        source: StreamSource,
+        tags: &TagFilter,
+        sorting: StreamSorting,
         start: Option<f64>,
         end: Option<f64>,
         skip: Option<usize>,
         limit: Option<usize>,
     ) -> Result<Vec<String>, DynError> {
+        let user_ids = match source {
+            StreamSource::Author { author_id } => vec![author_id],
+            source => Self::get_source_user_ids(source).await?,
+        };
+
+        let user_ids: Vec<&str> = user_ids
+            .iter()
+            .take(MAX_SOURCE_USERS)
+            .map(AsRef::as_ref)
+            .collect();
+        let user_key_parts = match sorting {
+            StreamSorting::Trending => POST_TRENDING_PER_USER_KEY_PARTS,
+            _ => POST_PER_USER_KEY_PARTS,
+        };
+        let key_parts_list: Vec<Vec<&str>> = user_ids
+            .iter()
+            .map(|user_id| [&user_key_parts[..], &[*user_id]].concat())
+            .collect();
+        let user_post_ids = Self::try_from_multiple_index_sorted_sets(
+            &key_parts_list,
+            start,
+            end,
+            SOURCE_TAGS_POSTS_PER_USER,
+        )
+        .await?;
+        let mut post_keys: Vec<(String, f64)> = user_ids
+            .iter()
+            .zip(user_post_ids)
+            .flat_map(|(user_id, post_ids)| {
+                post_ids
+                    .into_iter()
+                    .map(move |(post_id, score)| (format!("{}:{}", user_id, post_id), score))
+            })
+            .collect();
+
+        if let Some(key_parts) = Self::combine_tags(tags, None, &sorting).await? {
+            let key_parts: Vec<&str> = key_parts.iter().map(|part| part.as_str()).collect();
+            let members: Vec<&str> = post_keys.iter().map(|(key, _)| key.as_str()).collect();
+            let scores =
+                Self::get_index_sorted_set_scores(Some(TAGS_CACHE_PREFIX), &key_parts, &members)
+                    .await?;
+            post_keys = post_keys
+                .into_iter()
+                .zip(scores)
+                .filter_map(|(post_key, score)| score.map(|_| post_key))
+                .collect();
+        }
+        let mut post_keys = Self::exclude_tagged_posts(post_keys, &tags.none).await?;
+        post_keys.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        Ok(post_keys
+            .into_iter()
+            .skip(skip.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|(post_key, _)| post_key)
+            .collect())
+    }
+
+    /// Pages through a sorted set of posts leaving out the posts tagged with any of the `labels`. The sorted set
+    /// is read in batches until the page is complete or `MAX_EXCLUDED_TAGS_SCAN` posts are scanned
+    async fn get_posts_keys_excluding_tags(
+        prefix: Option<&str>,
+        key_parts: &[&str],
+        labels: &[String],
+        pagination: Pagination,
+    ) -> Result<Vec<String>, DynError> {
+        let skip = pagination.skip.unwrap_or(0);
+        let limit = pagination.limit.unwrap_or(10);
+        let batch_size = (skip + limit).clamp(100, 1000);
+
+        let mut post_keys = Vec::new();
+        let mut scanned = 0;
+        while post_keys.len() < skip + limit && scanned < MAX_EXCLUDED_TAGS_SCAN {
+            let batch = Self::try_from_index_sorted_set(
+                key_parts,
+                pagination.start,
+                pagination.end,
+                Some(scanned),
+                Some(batch_size),
+                SortOrder::Descending,
+                prefix,
+            )
+            .await?
+            .unwrap_or_default();
+            let batch_len = batch.len();
+            scanned += batch_len;
+            post_keys.extend(Self::exclude_tagged_posts(batch, labels).await?);
+            if batch_len < batch_size {
+                break;
+            }
+        }
+
+        Ok(post_keys
+            .into_iter()
+            .skip(skip)
+            .take(limit)
+            .map(|(post_key, _)| post_key)
+            .collect())
+    }
+
+    /// Leaves out the posts tagged with any of the `labels`
+    async fn exclude_tagged_posts(
+        mut post_keys: Vec<(String, f64)>,
+        labels: &[String],
+    ) -> Result<Vec<(String, f64)>, DynError> {
+        for label in labels {
+            if post_keys.is_empty() {
+                break;
+            }
+            let key_parts = [&TAG_GLOBAL_POST_TIMELINE[..], &[label.as_str()]].concat();
+            let members: Vec<&str> = post_keys.iter().map(|(key, _)| key.as_str()).collect();
+            let scores = Self::get_index_sorted_set_scores(None, &key_parts, &members).await?;
+            post_keys = post_keys
+                .into_iter()
+                .zip(scores)
+                .filter_map(|(post_key, score)| score.is_none().then_some(post_key))
+                .collect();
+        }
+        Ok(post_keys)
+    }
+
+    /// Posts of `kind` from the global and author timelines of the kind. The global streams with other
+    /// sortings or tags combine them with the global and label indexes, the author streams are scored
+    /// by the global index of the sorting
+    pub async fn get_posts_keys_by_kind(
+        source: StreamSource,
+        kind: &PubkyAppPostKind,
+        sorting: StreamSorting,
+        tags: &Option<TagFilter>,
+        pagination: Pagination,
+    ) -> Result<Vec<String>, DynError> {
+        let kind = kind.to_string();
+        let Pagination {
+            start,
+            end,
+            skip,
+            limit,
+        } = pagination;
+
+        match (source, sorting, tags) {
+            (StreamSource::Author { author_id }, sorting, _) => {
+                let key_parts = [
+                    &POST_KIND_PER_USER_KEY_PARTS[..],
+                    &[author_id.as_str(), kind.as_str()],
+                ]
+                .concat();
+                // The timeline is paginated by the index, the engagement ranks the latest posts of the kind
+                let post_ids = match sorting {
+                    StreamSorting::Timeline => {
+                        Self::try_from_index_sorted_set(
+                            &key_parts,
+                            start,
+                            end,
+                            skip,
+                            limit,
+                            SortOrder::Descending,
+                            None,
+                        )
+                        .await?
+                    }
+                    _ => {
+                        Self::try_from_index_sorted_set(
+                            &key_parts,
+                            None,
+                            None,
+                            None,
+                            Some(MAX_AUTHOR_KIND_POSTS_RANKED),
+                            SortOrder::Descending,
+                            None,
+                        )
+                        .await?
+                    }
+                };
+                let post_keys: Vec<String> = post_ids
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(post_id, _)| format!("{}:{}", author_id, post_id))
+                    .collect();
+                match sorting {
+                    StreamSorting::Timeline => Ok(post_keys),
+                    _ => {
+                        Self::rank_post_keys(
+                            None,
+                            &POST_TOTAL_ENGAGEMENT_KEY_PARTS,
+                            post_keys,
+                            pagination,
+                        )
+                        .await
+                    }
+                }
+            }
+            (_, StreamSorting::Timeline, None) => {
+                let key_parts = [&POST_KIND_TIMELINE_KEY_PARTS[..], &[kind.as_str()]].concat();
+                let post_keys = Self::try_from_index_sorted_set(
+                    &key_parts,
+                    start,
+                    end,
+                    skip,
+                    limit,
+                    SortOrder::Descending,
+                    None,
+                )
+                .await?;
+                match post_keys {
+                    Some(post_keys) => Ok(post_keys.into_iter().map(|(key, _)| key).collect()),
+                    None => Ok(vec![]),
+                }
+            }
+            (_, sorting, tags) => {
+                let tags = tags.clone().unwrap_or_default();
+                Self::get_posts_keys_by_tags(&tags, Some(&kind), sorting, start, end, skip, limit)
+                    .await
+            }
+        }
+    }
+
+    /// Sorts the posts by their score in a sorted set and paginates them. The posts missing from the sorted set
+    /// are left out
+    async fn rank_post_keys(
+        prefix: Option<&str>,
+        key_parts: &[&str],
+        post_keys: Vec<String>,
+        pagination: Pagination,
+    ) -> Result<Vec<String>, DynError> {
+        if post_keys.is_empty() {
+            return Ok(vec![]);
+        }
+        let members: Vec<&str> = post_keys.iter().map(|key| key.as_str()).collect();
+        let scores = Self::get_index_sorted_set_scores(prefix, key_parts, &members).await?;
+
+        let (start, end) = (pagination.start, pagination.end);
+        let mut scored_post_keys: Vec<(f64, String)> = scores
+            .into_iter()
+            .zip(post_keys)
+            .filter_map(|(score, post_key)| score.map(|score| (score, post_key)))
+            .filter(|(score, _)| {
+                !start.is_some_and(|start| *score > start) && !end.is_some_and(|end| *score < end)
+            })
+            .collect();
+        scored_post_keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        Ok(scored_post_keys
+            .into_iter()
+            .skip(pagination.skip.unwrap_or(0))
+            .take(pagination.limit.unwrap_or(usize::MAX))
+            .map(|(_, post_key)| post_key)
+            .collect())
+    }
+
+    /// Combines the label indexes of the posts into a cached sorted set for the labels to include and returns its
+    /// key parts, `None` when there are no labels or kind to include. The label timelines and the timeline of `kind`
+    /// decide the posts of the combination and the global index of the sorting scores them. The excluded labels are
+    /// left out of the combination, they are applied to the posts read from it
+    async fn combine_tags(
+        tags: &TagFilter,
+        kind: Option<&str>,
+        sorting: &StreamSorting,
+    ) -> Result<Option<Vec<String>>, DynError> {
+        if !tags.includes() && kind.is_none() {
+            return Ok(None);
+        }
+        let sorting_id = match sorting {
+            StreamSorting::TotalEngagement => "TotalEngagement",
+            StreamSorting::Trending => "Trending",
+            _ => "Timeline",
+        };
+        let mut cache_id = tags.cache_id();
+        if let Some(kind) = kind {
+            cache_id.push_str(&format!("|kind={kind}"));
+        }
+        let key_parts = vec![
+            "Posts".to_string(),
+            "Tags".to_string(),
+            sorting_id.to_string(),
+            cache_id,
+        ];
+        let combined_key_parts: Vec<&str> = key_parts.iter().map(|part| part.as_str()).collect();
+
+        // The combination is reused until it expires
+        if Self::try_from_index_sorted_set(
+            &combined_key_parts,
+            None,
+            None,
+            None,
+            Some(1),
+            SortOrder::Descending,
+            Some(TAGS_CACHE_PREFIX),
+        )
+        .await?
+        .is_some()
+        {
+            return Ok(Some(key_parts));
+        }
+
+        fn label_key_parts(labels: &[String]) -> Vec<Vec<&str>> {
+            labels
+                .iter()
+                .map(|label| [&TAG_GLOBAL_POST_TIMELINE[..], &[label.as_str()]].concat())
+                .collect()
+        }
+        let mut intersection = label_key_parts(&tags.all);
+        if let Some(kind) = kind {
+            intersection.push([&POST_KIND_TIMELINE_KEY_PARTS[..], &[kind]].concat());
+        }
+        // The label timelines are already scored by the creation time of the posts
+        let scores: Option<&[&str]> = match sorting {
+            StreamSorting::TotalEngagement => Some(&POST_TOTAL_ENGAGEMENT_KEY_PARTS[..]),
+            StreamSorting::Trending => Some(&POST_TRENDING_KEY_PARTS[..]),
+            _ => None,
+        };
+
+        Self::combine_index_sorted_sets(
+            &combined_key_parts,
+            Some(TAGS_CACHE_PREFIX),
+            &label_key_parts(&tags.any),
+            &intersection,
+            scores,
+            &[],
+            TAGS_CACHE_TTL,
+        )
+        .await?;
+        // Every distinct filter stores its own combination, the oldest ones are dropped past the cap
+        Self::register_cached_index_sorted_set(
+            TAGS_CACHE_PREFIX,
+            &TAGS_CACHE_REGISTRY_KEY_PARTS,
+            &combined_key_parts,
+            Utc::now().timestamp_millis() as f64,
+            MAX_CACHED_TAG_COMBINATIONS,
+        )
+        .await?;
+        Ok(Some(key_parts))
+    }
+
+    /// Users whose posts make up a stream by source/reach
+    async fn get_source_user_ids(source: StreamSource) -> Result<Vec<String>, DynError> {
         let user_ids = match source {
             StreamSource::Following { observer_id } => {
                 Following::get_by_id(&observer_id, None, None)
@@ -345,22 +1091,21 @@ impl PostStream {
                     .unwrap_or_default()
                     .0
             }
+            // The users kept when the users are capped are taken from every follow distance
+            StreamSource::Network { observer_id, depth } => {
+                Network::get_sample(&observer_id, depth, MAX_SOURCE_USERS).await?
+            }
+            StreamSource::TagList {
+                observer_id,
+                label,
+                include_friends,
+            } => {
+                UserList::get_members(&observer_id, &label, include_friends.unwrap_or(false))
+                    .await?
+            }
             _ => vec![],
         };
-
-        if !user_ids.is_empty() {
-            let post_keys = Self::get_posts_for_user_ids(
-                &user_ids.iter().map(AsRef::as_ref).collect::<Vec<_>>(),
-                start,
-                end,
-                skip,
-                limit,
-            )
-            .await?;
-            Ok(post_keys)
-        } else {
-            Ok(vec![])
-        }
+        Ok(user_ids)
     }
 
     pub async fn get_bookmarked_posts(
@@ -389,6 +1134,57 @@ impl PostStream {
         }
     }
 
+    pub async fn get_mentioned_posts(
+        user_id: &str,
+        start: Option<f64>,
+        end: Option<f64>,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Vec<String>, DynError> {
+        let key_parts = [&POST_MENTIONS_PER_USER_KEY_PARTS[..], &[user_id]].concat();
+        let post_keys = Self::try_from_index_sorted_set(
+            &key_parts,
+            start,
+            end,
+            skip,
+            limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
+
+        match post_keys {
+            Some(post_keys) => Ok(post_keys.into_iter().map(|(key, _)| key).collect()),
+            None => Ok(vec![]),
+        }
+    }
+
+    pub async fn get_tagged_posts(
+        tagger_id: &str,
+        label: Option<&str>,
+        start: Option<f64>,
+        end: Option<f64>,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Vec<String>, DynError> {
+        let key_parts = Self::tagged_key_parts(tagger_id, label);
+        let post_keys = Self::try_from_index_sorted_set(
+            &key_parts,
+            start,
+            end,
+            skip,
+            limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
+
+        match post_keys {
+            Some(post_keys) => Ok(post_keys.into_iter().map(|(key, _)| key).collect()),
+            None => Ok(vec![]),
+        }
+    }
+
     pub async fn get_post_replies(
         author_id: &str,
         post_id: &str,
@@ -418,6 +1214,7 @@ impl PostStream {
     // TODO rethink, we could also fallback to graph
     async fn get_posts_for_user_ids(
         user_ids: &[&str],
+        sorting: StreamSorting,
         start: Option<f64>,
         end: Option<f64>,
         skip: Option<usize>,
@@ -425,13 +1222,18 @@ impl PostStream {
     ) -> Result<Vec<String>, DynError> {
         let mut post_keys = Vec::new();
 
-        // Limit the number of user IDs to process to the first 200
-        let max_user_ids = 200;
-        let truncated_user_ids: Vec<&str> = user_ids.iter().take(max_user_ids).cloned().collect();
+        // Limit the number of user IDs to process to the first ones
+        let truncated_user_ids: Vec<&str> =
+            user_ids.iter().take(MAX_SOURCE_USERS).cloned().collect();
+
+        let user_key_parts = match sorting {
+            StreamSorting::Trending => POST_TRENDING_PER_USER_KEY_PARTS,
+            _ => POST_PER_USER_KEY_PARTS,
+        };
 
         // Retrieve posts for each user and collect them
         for user_id in &truncated_user_ids {
-            let key_parts = [&POST_PER_USER_KEY_PARTS[..], &[user_id]].concat();
+            let key_parts = [&user_key_parts[..], &[user_id]].concat();
             if let Some(post_ids) = Self::try_from_index_sorted_set(
                 &key_parts,
                 start,
@@ -483,7 +1285,8 @@ impl PostStream {
             let viewer_id = viewer_id.clone();
             let post_id = post_id.to_string();
             let handle = spawn(async move {
-                PostView::get_by_id(&author_id, &post_id, viewer_id.as_deref(), None, None).await
+                PostView::get_by_id(&author_id, &post_id, viewer_id.as_deref(), None, None, None)
+                    .await
             });
             handles.push(handle);
         }
@@ -509,7 +1312,10 @@ impl PostStream {
             None,
             None,
         )
-        .await
+        .await?;
+        let kind = details.kind.to_string();
+        let key_parts = [&POST_KIND_TIMELINE_KEY_PARTS[..], &[kind.as_str()]].concat();
+        Self::put_index_sorted_set(&key_parts, &[(score, element.as_str())], None, None).await
     }
 
     /// Adds the post to a Redis sorted set using the `indexed_at` timestamp as the score.
@@ -519,13 +1325,27 @@ impl PostStream {
     ) -> Result<(), DynError> {
         let element = format!("{}:{}", author_id, post_id);
         Self::remove_from_index_sorted_set(None, &POST_TIMELINE_KEY_PARTS, &[element.as_str()])
-            .await
+            .await?;
+        // The details of the post might be gone already, it is removed from every kind
+        for kind in POST_KINDS {
+            let kind = kind.to_string();
+            let key_parts = [&POST_KIND_TIMELINE_KEY_PARTS[..], &[kind.as_str()]].concat();
+            Self::remove_from_index_sorted_set(None, &key_parts, &[element.as_str()]).await?;
+        }
+        Ok(())
     }
 
     /// Adds the post to a Redis sorted set using the `indexed_at` timestamp as the score.
     pub async fn add_to_per_user_sorted_set(details: &PostDetails) -> Result<(), DynError> {
         let key_parts = [&POST_PER_USER_KEY_PARTS[..], &[details.author.as_str()]].concat();
         let score = details.indexed_at as f64;
+        Self::put_index_sorted_set(&key_parts, &[(score, details.id.as_str())], None, None).await?;
+        let kind = details.kind.to_string();
+        let key_parts = [
+            &POST_KIND_PER_USER_KEY_PARTS[..],
+            &[details.author.as_str(), kind.as_str()],
+        ]
+        .concat();
         Self::put_index_sorted_set(&key_parts, &[(score, details.id.as_str())], None, None).await
     }
 
@@ -535,7 +1355,54 @@ impl PostStream {
         post_id: &str,
     ) -> Result<(), DynError> {
         let key_parts = [&POST_PER_USER_KEY_PARTS[..], &[author_id]].concat();
-        Self::remove_from_index_sorted_set(None, &key_parts, &[post_id]).await
+        Self::remove_from_index_sorted_set(None, &key_parts, &[post_id]).await?;
+        for kind in POST_KINDS {
+            let kind = kind.to_string();
+            let key_parts = [
+                &POST_KIND_PER_USER_KEY_PARTS[..],
+                &[author_id, kind.as_str()],
+            ]
+            .concat();
+            Self::remove_from_index_sorted_set(None, &key_parts, &[post_id]).await?;
+        }
+        Ok(())
+    }
+
+    /// Moves an edited root post to the kind sorted sets of its current kind. The post keeps the score it has
+    /// in the global timeline
+    pub async fn update_kind_sorted_sets(details: &PostDetails) -> Result<(), DynError> {
+        let post_key = format!("{}:{}", details.author, details.id);
+        let score = Self::get_index_sorted_set_scores(None, &POST_TIMELINE_KEY_PARTS, &[&post_key])
+            .await?
+            .into_iter()
+            .next()
+            .flatten();
+        let Some(score) = score else {
+            return Ok(());
+        };
+        let kind = details.kind.to_string();
+        for other_kind in POST_KINDS {
+            let other_kind = other_kind.to_string();
+            if other_kind == kind {
+                continue;
+            }
+            let key_parts = [&POST_KIND_TIMELINE_KEY_PARTS[..], &[other_kind.as_str()]].concat();
+            Self::remove_from_index_sorted_set(None, &key_parts, &[&post_key]).await?;
+            let key_parts = [
+                &POST_KIND_PER_USER_KEY_PARTS[..],
+                &[details.author.as_str(), other_kind.as_str()],
+            ]
+            .concat();
+            Self::remove_from_index_sorted_set(None, &key_parts, &[&details.id]).await?;
+        }
+        let key_parts = [&POST_KIND_TIMELINE_KEY_PARTS[..], &[kind.as_str()]].concat();
+        Self::put_index_sorted_set(&key_parts, &[(score, post_key.as_str())], None, None).await?;
+        let key_parts = [
+            &POST_KIND_PER_USER_KEY_PARTS[..],
+            &[details.author.as_str(), kind.as_str()],
+        ]
+        .concat();
+        Self::put_index_sorted_set(&key_parts, &[(score, details.id.as_str())], None, None).await
     }
 
     /// Adds the post response to a Redis sorted set using the `indexed_at` timestamp as the score.
@@ -608,6 +1475,198 @@ impl PostStream {
         Self::remove_from_index_sorted_set(None, &key_parts, &[&post_key]).await
     }
 
+    /// Adds the post to the mentions sorted set of each mentioned user using the `indexed_at` timestamp as the score.
+    /// The author mentioning itself is left out
+    pub async fn add_to_mentions_sorted_sets(
+        mentioned_ids: &[String],
+        author_id: &str,
+        post_id: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let post_key = format!("{}:{}", author_id, post_id);
+        for mentioned_id in mentioned_ids.iter().filter(|id| *id != author_id) {
+            let key_parts = [
+                &POST_MENTIONS_PER_USER_KEY_PARTS[..],
+                &[mentioned_id.as_str()],
+            ]
+            .concat();
+            Self::put_index_sorted_set(
+                &key_parts,
+                &[(indexed_at as f64, post_key.as_str())],
+                None,
+                None,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the post from the mentions sorted set of each mentioned user
+    pub async fn remove_from_mentions_sorted_sets(
+        mentioned_ids: &[String],
+        author_id: &str,
+        post_id: &str,
+    ) -> Result<(), DynError> {
+        let post_key = format!("{}:{}", author_id, post_id);
+        for mentioned_id in mentioned_ids {
+            let key_parts = [
+                &POST_MENTIONS_PER_USER_KEY_PARTS[..],
+                &[mentioned_id.as_str()],
+            ]
+            .concat();
+            Self::remove_from_index_sorted_set(None, &key_parts, &[&post_key]).await?;
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the mentions of the post from its indexed details and relationships
+    pub async fn reindex_mentions(author_id: &str, post_id: &str) -> Result<(), DynError> {
+        let (details, relationships) = tokio::try_join!(
+            PostDetails::get_from_index(author_id, post_id),
+            PostRelationships::get_from_index(author_id, post_id),
+        )?;
+        if let (Some(details), Some(relationships)) = (details, relationships) {
+            Self::add_to_mentions_sorted_sets(
+                &relationships.mentioned,
+                author_id,
+                post_id,
+                details.indexed_at,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Adds the post to the tagged sorted sets of the tagger, for any label and for `label`, using the tag
+    /// `indexed_at` timestamp as the score
+    pub async fn add_to_tagged_sorted_sets(
+        tagger_id: &str,
+        author_id: &str,
+        post_id: &str,
+        label: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let post_key = format!("{}:{}", author_id, post_id);
+        let element = [(indexed_at as f64, post_key.as_str())];
+        Self::put_index_sorted_set(
+            &Self::tagged_key_parts(tagger_id, None),
+            &element,
+            None,
+            None,
+        )
+        .await?;
+        Self::put_index_sorted_set(
+            &Self::tagged_key_parts(tagger_id, Some(label)),
+            &element,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Removes the post from the tagged sorted set of `label`. The post stays in the tagged sorted set for
+    /// any label while the tagger keeps other labels on it, scored by the latest of them.
+    /// The tag has to be deleted from the graph first
+    pub async fn remove_from_tagged_sorted_sets(
+        tagger_id: &str,
+        author_id: &str,
+        post_id: &str,
+        label: &str,
+    ) -> Result<(), DynError> {
+        let post_key = format!("{}:{}", author_id, post_id);
+        Self::remove_from_index_sorted_set(
+            None,
+            &Self::tagged_key_parts(tagger_id, Some(label)),
+            &[&post_key],
+        )
+        .await?;
+
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::post_tagged_at(tagger_id, author_id, post_id);
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+        let tagged_at: Option<i64> = match result.next().await? {
+            Some(row) => row.get("tagged_at")?,
+            None => None,
+        };
+
+        let key_parts = Self::tagged_key_parts(tagger_id, None);
+        match tagged_at {
+            Some(tagged_at) => {
+                Self::put_index_sorted_set(
+                    &key_parts,
+                    &[(tagged_at as f64, post_key.as_str())],
+                    None,
+                    None,
+                )
+                .await
+            }
+            None => Self::remove_from_index_sorted_set(None, &key_parts, &[&post_key]).await,
+        }
+    }
+
+    /// Rebuilds the tagged sorted sets of the tagger from the graph
+    pub async fn reindex_tagged(tagger_id: &str) -> Result<(), DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::tagged_posts(tagger_id);
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut labels: HashMap<String, Vec<(f64, String)>> = HashMap::new();
+        let mut posts: HashMap<String, f64> = HashMap::new();
+        while let Some(row) = result.next().await? {
+            let label: String = row.get("label")?;
+            let post_key: String = row.get("post_key")?;
+            let tagged_at = row.get::<i64>("tagged_at")? as f64;
+            let latest = posts.entry(post_key.clone()).or_insert(tagged_at);
+            *latest = latest.max(tagged_at);
+            labels.entry(label).or_default().push((tagged_at, post_key));
+        }
+
+        if posts.is_empty() {
+            return Ok(());
+        }
+        let sorted_set: Vec<(f64, &str)> = posts
+            .iter()
+            .map(|(post_key, tagged_at)| (*tagged_at, post_key.as_str()))
+            .collect();
+        Self::put_index_sorted_set(
+            &Self::tagged_key_parts(tagger_id, None),
+            &sorted_set,
+            None,
+            None,
+        )
+        .await?;
+        for (label, post_keys) in &labels {
+            let sorted_set: Vec<(f64, &str)> = post_keys
+                .iter()
+                .map(|(tagged_at, post_key)| (*tagged_at, post_key.as_str()))
+                .collect();
+            Self::put_index_sorted_set(
+                &Self::tagged_key_parts(tagger_id, Some(label)),
+                &sorted_set,
+                None,
+                None,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    fn tagged_key_parts<'a>(tagger_id: &'a str, label: Option<&'a str>) -> Vec<&'a str> {
+        let mut key_parts = [&POST_TAGGED_PER_USER_KEY_PARTS[..], &[tagger_id]].concat();
+        key_parts.extend(label);
+        key_parts
+    }
+
     /// Adds the post to a Redis sorted set using the total engagement as the score.
     pub async fn add_to_engagement_sorted_set(
         counts: &PostCounts,
@@ -633,13 +1692,25 @@ impl PostStream {
     ) -> Result<(), DynError> {
         let post_key = format!("{}:{}", author_id, post_id);
         Self::remove_from_index_sorted_set(None, &POST_TOTAL_ENGAGEMENT_KEY_PARTS, &[&post_key])
-            .await
+            .await?;
+        // Past windows are not served anymore and expire on their own
+        for timeframe in ENGAGEMENT_WINDOWS {
+            if let Some(window_id) = timeframe.to_current_window_id() {
+                let timeframe = timeframe.to_string();
+                let key_parts = Self::engagement_window_key_parts(&timeframe, &window_id);
+                Self::remove_from_index_sorted_set(None, &key_parts, &[&post_key]).await?;
+            }
+        }
+        Ok(())
     }
 
+    /// Updates the total engagement of a post. The engagement created at `engaged_at` is also counted
+    /// in the windows in progress that contain it, e.g. the engagement of today
     pub async fn update_index_score(
         author_id: &str,
         post_id: &str,
         score_action: ScoreAction,
+        engaged_at: i64,
     ) -> Result<(), DynError> {
         let post_key_slice = &[author_id, post_id];
         Self::put_score_index_sorted_set(
@@ -647,6 +1718,141 @@ impl PostStream {
             post_key_slice,
             score_action,
         )
-        .await
+        .await?;
+
+        for timeframe in ENGAGEMENT_WINDOWS {
+            // Past windows are not served anymore, their engagement is not updated
+            let current_window_id = timeframe.to_current_window_id();
+            let Some(window_id) = timeframe
+                .to_window_id(engaged_at)
+                .filter(|window_id| Some(window_id) == current_window_id.as_ref())
+            else {
+                continue;
+            };
+            let timeframe_str = timeframe.to_string();
+            Self::put_expiring_score_index_sorted_set(
+                &Self::engagement_window_key_parts(&timeframe_str, &window_id),
+                post_key_slice,
+                score_action,
+                timeframe.to_window_ttl(),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the total engagement indexes of the windows in progress from the graph
+    pub async fn reindex_engagement_windows() -> Result<(), DynError> {
+        for timeframe in ENGAGEMENT_WINDOWS {
+            let (since, _) = timeframe.to_timestamp_range();
+            let window_id = match timeframe.to_current_window_id() {
+                Some(window_id) => window_id,
+                None => continue,
+            };
+
+            let mut result;
+            {
+                let graph = get_neo4j_graph()?;
+                let query = queries::get::global_posts_engagement_since(since);
+
+                let graph = graph.lock().await;
+                result = graph.execute(query).await?;
+            }
+
+            if let Some(row) = result.next().await? {
+                let sorted_set: Vec<(f64, &str)> = row.get("sorted_set").unwrap_or_default();
+                let timeframe_str = timeframe.to_string();
+                Self::put_index_sorted_set(
+                    &Self::engagement_window_key_parts(&timeframe_str, &window_id),
+                    &sorted_set,
+                    None,
+                    Some(timeframe.to_window_ttl()),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn engagement_window_key_parts<'a>(timeframe: &'a str, window_id: &'a str) -> Vec<&'a str> {
+        [
+            &POST_TOTAL_ENGAGEMENT_KEY_PARTS[..],
+            &[timeframe, window_id],
+        ]
+        .concat()
+    }
+
+    /// Trending score of a post. The engagement counts logarithmically and the creation time linearly,
+    /// so the order does not change while the engagement stays the same and no recomputation is needed over time
+    pub fn trending_score(counts: &PostCounts, indexed_at: i64) -> f64 {
+        let engagement = (counts.tags + counts.replies + counts.reposts).max(1) as f64;
+        engagement.log10() + (indexed_at as f64 / 1000.0) / TRENDING_DECAY_SECONDS
+    }
+
+    /// Recomputes the trending score of a post after its engagement changed. Replies are not trending
+    pub async fn update_root_trending_score(
+        author_id: &str,
+        post_id: &str,
+    ) -> Result<(), DynError> {
+        if !PostRelationships::is_reply(author_id, post_id).await? {
+            Self::update_trending_score(author_id, post_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Recomputes the trending score of a root post in the global, author and post tags trending sorted sets.
+    /// Replies are not part of the trending streams, the caller has to skip them
+    pub async fn update_trending_score(author_id: &str, post_id: &str) -> Result<(), DynError> {
+        let (counts, details) = tokio::join!(
+            PostCounts::get_from_index(author_id, post_id),
+            PostDetails::get_from_index(author_id, post_id)
+        );
+        let (Some(counts), Some(details)) = (counts?, details?) else {
+            return Ok(());
+        };
+        let score = Self::trending_score(&counts, details.indexed_at);
+        let post_key = format!("{}:{}", author_id, post_id);
+
+        Self::put_index_sorted_set(
+            &POST_TRENDING_KEY_PARTS,
+            &[(score, post_key.as_str())],
+            None,
+            None,
+        )
+        .await?;
+
+        let key_parts = [&POST_TRENDING_PER_USER_KEY_PARTS[..], &[author_id]].concat();
+        Self::put_index_sorted_set(&key_parts, &[(score, post_id)], None, None).await?;
+
+        // Only the labels still in use by some tagger
+        let post_tags_key_parts = [&POST_TAGS_KEY_PARTS[..], &[author_id, post_id]].concat();
+        let labels = Self::try_from_index_sorted_set(
+            &post_tags_key_parts,
+            None,
+            Some(1.0),
+            None,
+            None,
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+
+        for (label, _) in labels {
+            let key_parts = [&TAG_GLOBAL_POST_TRENDING[..], &[label.as_str()]].concat();
+            Self::put_index_sorted_set(&key_parts, &[(score, post_key.as_str())], None, None)
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn remove_from_trending_sorted_sets(
+        author_id: &str,
+        post_id: &str,
+    ) -> Result<(), DynError> {
+        let post_key = format!("{}:{}", author_id, post_id);
+        Self::remove_from_index_sorted_set(None, &POST_TRENDING_KEY_PARTS, &[&post_key]).await?;
+        let key_parts = [&POST_TRENDING_PER_USER_KEY_PARTS[..], &[author_id]].concat();
+        Self::remove_from_index_sorted_set(None, &key_parts, &[post_id]).await
     }
 }
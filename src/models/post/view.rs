@@ -19,12 +19,14 @@ pub struct PostView {
 
 impl PostView {
     /// Retrieves a user ID, checking the cache first and then the graph database.
+    /// With a `viewer_id` and a `depth` the tags are the ones in the Web of Trust of the viewer
     pub async fn get_by_id(
         author_id: &str,
         post_id: &str,
         viewer_id: Option<&str>,
         limit_tags: Option<usize>,
         limit_taggers: Option<usize>,
+        depth: Option<u8>,
     ) -> Result<Option<Self>, DynError> {
         // Perform all operations concurrently
         let (details, counts, bookmark, relationships, tags) = tokio::try_join!(
@@ -39,8 +41,8 @@ impl PostView {
                 limit_tags,
                 limit_taggers,
                 viewer_id,
-                None
-            ), // Avoid by default WoT tags in a Post
+                depth
+            ),
         )?;
 
         let details = match details {
@@ -6,11 +6,17 @@ type TagFieldsTuple<'a> = (Vec<(f64, &'a str)>, (Vec<&'a str>, Vec<Vec<&'a str>>
 #[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Default)]
 pub struct TagDetails {
     pub label: String,
+    /// The label as most taggers write it, e.g. `Bitcoin` for the `bitcoin` label
+    #[serde(default)]
+    pub original_label: String,
     pub taggers: Vec<String>,
     pub taggers_count: usize,
     #[serde(default)]
     // Describes if the viewer is part of the taggers list
     pub relationship: bool,
+    /// Sum of the trust of the viewer on the taggers, only present on trust weighted tags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust: Option<f64>,
 }
 
 impl TagDetails {
@@ -32,10 +38,12 @@ impl TagDetails {
             .filter_map(|((label, _), taggers)| {
                 // TIP: MAP will not process None types and it will be automatically passed through unchanged
                 taggers.map(|(taggers, taggers_count, relationship)| TagDetails {
+                    original_label: label.clone(),
                     label,
                     taggers,
                     taggers_count,
                     relationship,
+                    trust: None,
                 })
             })
             .collect()
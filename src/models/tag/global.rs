@@ -1,4 +1,5 @@
 use super::{
+    label::canonical_label,
     stream::{TagStreamReach, HOT_TAGS_CACHE_PREFIX, POST_HOT_TAGS},
     Taggers as TaggersType,
 };
@@ -99,6 +100,7 @@ impl Taggers {
         limit: usize,
         timeframe: Timeframe,
     ) -> Result<Option<TaggersType>, DynError> {
+        let label = canonical_label(&label).await?;
         match user_id {
             None => Self::get_from_global_timeline(&label, skip, limit, timeframe).await,
             Some(id) => {
@@ -114,7 +116,9 @@ impl Taggers {
         }
     }
 
-    /// Retrieves paginated taggers from the global timeline based on a specified timeframe
+    /// Retrieves paginated taggers from the global timeline based on a specified timeframe. The taggers
+    /// cached along the global hot tags are used when they cover the page, the rest of labels are
+    /// queried in the graph
     ///
     /// # Arguments
     /// * `label` - The tag label for which to retrieve taggers
@@ -128,15 +132,17 @@ impl Taggers {
         timeframe: Timeframe,
     ) -> Result<Option<TaggersType>, DynError> {
         let timeframe_str = timeframe.to_string();
-        let taggers_by_timeframe = Self::get_from_index(&timeframe_str).await?;
-
-        if let Some(taggers_hash_map) = taggers_by_timeframe {
+        if let Some(taggers_hash_map) = Self::get_from_index(&timeframe_str).await? {
             if let Some(taggers) = taggers_hash_map.get(label) {
-                return Ok(Some(Self::get_taggers_by_pagination(taggers, skip, limit)));
+                if skip + limit <= taggers.len() {
+                    return Ok(Some(Self::get_taggers_by_pagination(taggers, skip, limit)));
+                }
             }
         }
 
-        Ok(None)
+        let query = queries::get::get_global_tag_taggers(label, &timeframe, skip, limit);
+        let taggers = retrieve_from_graph::<TaggersType>(query, "tagger_ids").await?;
+        Ok(taggers.filter(|taggers| !taggers.is_empty()))
     }
 
     /// Returns a paginated subset of taggers from the given list
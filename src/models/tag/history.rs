@@ -0,0 +1,339 @@
+use crate::db::connectors::neo4j::get_neo4j_graph;
+use crate::types::DynError;
+use crate::{queries, RedisOps, ScoreAction};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Deref;
+use utoipa::ToSchema;
+
+use super::label::canonical_label;
+use super::TaggedType;
+
+pub const TAG_HISTORY_KEY_PARTS: [&str; 2] = ["Tags", "History"];
+const HOUR_MILLIS: i64 = 60 * 60 * 1000;
+const DAY_MILLIS: i64 = 24 * HOUR_MILLIS;
+const WEEK_MILLIS: i64 = 7 * DAY_MILLIS;
+/// The Unix epoch is a Thursday, the weeks start on Monday 1970-01-05
+const WEEK_OFFSET_MILLIS: i64 = 4 * DAY_MILLIS;
+/// Seconds the taggers of a bucket are kept to count the unique taggers when a tag is deleted.
+/// Deleting an older tag only updates the tag count
+const BUCKET_TAGGERS_TTL: i64 = 30 * 24 * 60 * 60;
+
+/// Size of the buckets of the tag history
+#[derive(Deserialize, ToSchema, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TagHistoryGranularity {
+    Hour,
+    #[default]
+    Day,
+    /// Weeks starting on Monday, UTC
+    Week,
+}
+
+impl Display for TagHistoryGranularity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagHistoryGranularity::Hour => write!(f, "Hour"),
+            TagHistoryGranularity::Day => write!(f, "Day"),
+            TagHistoryGranularity::Week => write!(f, "Week"),
+        }
+    }
+}
+
+impl TagHistoryGranularity {
+    const ALL: [Self; 3] = [Self::Hour, Self::Day, Self::Week];
+
+    fn bucket_millis(&self) -> i64 {
+        match self {
+            TagHistoryGranularity::Hour => HOUR_MILLIS,
+            TagHistoryGranularity::Day => DAY_MILLIS,
+            TagHistoryGranularity::Week => WEEK_MILLIS,
+        }
+    }
+
+    /// Start of the bucket that contains the timestamp, in milliseconds
+    pub fn bucket_start(&self, timestamp: i64) -> i64 {
+        let offset = match self {
+            TagHistoryGranularity::Week => WEEK_OFFSET_MILLIS,
+            _ => 0,
+        };
+        let size = self.bucket_millis();
+        (timestamp - offset).div_euclid(size) * size + offset
+    }
+}
+
+/// Usage of a label inside one bucket of time
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct TagHistoryPoint {
+    /// Start of the bucket, Unix timestamp in milliseconds
+    pub timestamp: i64,
+    pub tag_count: u64,
+    pub unique_taggers: u64,
+}
+
+/// Usage history of a label, the oldest bucket first
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+pub struct TagHistory(pub Vec<TagHistoryPoint>);
+
+impl RedisOps for TagHistory {}
+
+impl Deref for TagHistory {
+    type Target = Vec<TagHistoryPoint>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl TagHistory {
+    /// Retrieves the usage of the label in the `buckets` buckets up to `end`, the empty buckets included
+    ///
+    /// # Arguments
+    /// * `label` - The tag label
+    /// * `tagged_type` - Whether the tags are on posts or on users
+    /// * `granularity` - The size of the buckets
+    /// * `end` - Timestamp in milliseconds inside the last bucket. Defaults to now
+    /// * `buckets` - The number of buckets to retrieve
+    pub async fn get_by_label(
+        label: &str,
+        tagged_type: TaggedType,
+        granularity: TagHistoryGranularity,
+        end: Option<i64>,
+        buckets: usize,
+    ) -> Result<Self, DynError> {
+        let label = &canonical_label(label).await?;
+        let last_bucket = granularity.bucket_start(end.unwrap_or(Utc::now().timestamp_millis()));
+        let timestamps: Vec<i64> = (0..buckets as i64)
+            .rev()
+            .map(|index| {
+                granularity.bucket_start(last_bucket - index * granularity.bucket_millis())
+            })
+            .collect();
+        let members: Vec<String> = timestamps.iter().map(i64::to_string).collect();
+        let members: Vec<&str> = members.iter().map(String::as_str).collect();
+
+        let (tagged_type, granularity) = (tagged_type.to_string(), granularity.to_string());
+        let count_key = Self::build_key_parts(&tagged_type, &granularity, "Count", label);
+        let taggers_key = Self::build_key_parts(&tagged_type, &granularity, "Taggers", label);
+        let (counts, taggers) = tokio::try_join!(
+            Self::get_index_sorted_set_scores(None, &count_key, &members),
+            Self::get_index_sorted_set_scores(None, &taggers_key, &members),
+        )?;
+
+        Ok(Self(
+            timestamps
+                .into_iter()
+                .zip(counts.into_iter().zip(taggers))
+                .map(|(timestamp, (count, taggers))| TagHistoryPoint {
+                    timestamp,
+                    tag_count: count.unwrap_or(0.0).max(0.0) as u64,
+                    unique_taggers: taggers.unwrap_or(0.0).max(0.0) as u64,
+                })
+                .collect(),
+        ))
+    }
+
+    /// Counts a new tag in the buckets of every granularity
+    pub async fn put_to_index(
+        tagged_type: TaggedType,
+        label: &str,
+        tagger_id: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let tagged_type = tagged_type.to_string();
+        for granularity in TagHistoryGranularity::ALL {
+            let bucket = granularity.bucket_start(indexed_at).to_string();
+            let granularity = granularity.to_string();
+            let count_key = Self::build_key_parts(&tagged_type, &granularity, "Count", label);
+            Self::put_score_index_sorted_set(&count_key, &[&bucket], ScoreAction::Increment(1.0))
+                .await?;
+
+            let bucket_taggers_key =
+                Self::build_bucket_taggers_key_parts(&tagged_type, &granularity, label, &bucket);
+            Self::put_expiring_score_index_sorted_set(
+                &bucket_taggers_key,
+                &[tagger_id],
+                ScoreAction::Increment(1.0),
+                BUCKET_TAGGERS_TTL,
+            )
+            .await?;
+            // First tag of the tagger with the label in the bucket
+            if Self::check_sorted_set_member(None, &bucket_taggers_key, &[tagger_id]).await?
+                == Some(1)
+            {
+                let taggers_key =
+                    Self::build_key_parts(&tagged_type, &granularity, "Taggers", label);
+                Self::put_score_index_sorted_set(
+                    &taggers_key,
+                    &[&bucket],
+                    ScoreAction::Increment(1.0),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Discounts a deleted tag from the buckets of every granularity
+    pub async fn del_from_index(
+        tagged_type: TaggedType,
+        label: &str,
+        tagger_id: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let tagged_type = tagged_type.to_string();
+        for granularity in TagHistoryGranularity::ALL {
+            let bucket = granularity.bucket_start(indexed_at).to_string();
+            let granularity = granularity.to_string();
+            let count_key = Self::build_key_parts(&tagged_type, &granularity, "Count", label);
+            Self::decrement_or_remove(&count_key, &bucket).await?;
+
+            // The taggers of the old buckets already expired
+            let bucket_taggers_key =
+                Self::build_bucket_taggers_key_parts(&tagged_type, &granularity, label, &bucket);
+            if Self::check_sorted_set_member(None, &bucket_taggers_key, &[tagger_id])
+                .await?
+                .is_none()
+            {
+                continue;
+            }
+            // Last tag of the tagger with the label in the bucket
+            if Self::decrement_or_remove(&bucket_taggers_key, tagger_id).await? {
+                let taggers_key =
+                    Self::build_key_parts(&tagged_type, &granularity, "Taggers", label);
+                Self::decrement_or_remove(&taggers_key, &bucket).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the history of every label from the tags in the graph
+    pub async fn reindex() -> Result<(), DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::global_tags_history();
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        // Tag count and tag count of each tagger per label bucket
+        type Buckets = HashMap<(String, String, String, i64), (f64, HashMap<String, f64>)>;
+        let mut buckets: Buckets = HashMap::new();
+        while let Some(row) = result.next().await? {
+            let label: String = row.get("label")?;
+            let tagged_type: String = row.get("tagged_type")?;
+            let tagger_id: String = row.get("tagger_id")?;
+            let indexed_at: i64 = row.get("indexed_at")?;
+            for granularity in TagHistoryGranularity::ALL {
+                let key = (
+                    tagged_type.clone(),
+                    granularity.to_string(),
+                    label.clone(),
+                    granularity.bucket_start(indexed_at),
+                );
+                let (count, taggers) = buckets.entry(key).or_default();
+                *count += 1.0;
+                *taggers.entry(tagger_id.clone()).or_default() += 1.0;
+            }
+        }
+
+        let taggers_since = Utc::now().timestamp_millis() - BUCKET_TAGGERS_TTL * 1000;
+        for ((tagged_type, granularity, label, bucket), (count, taggers)) in buckets {
+            let bucket_str = bucket.to_string();
+            let count_key = Self::build_key_parts(&tagged_type, &granularity, "Count", &label);
+            Self::put_index_sorted_set(&count_key, &[(count, &bucket_str)], None, None).await?;
+            let taggers_key = Self::build_key_parts(&tagged_type, &granularity, "Taggers", &label);
+            let unique_taggers = taggers.len() as f64;
+            Self::put_index_sorted_set(&taggers_key, &[(unique_taggers, &bucket_str)], None, None)
+                .await?;
+
+            if bucket >= taggers_since {
+                let bucket_taggers_key = Self::build_bucket_taggers_key_parts(
+                    &tagged_type,
+                    &granularity,
+                    &label,
+                    &bucket_str,
+                );
+                let taggers: Vec<(f64, &str)> = taggers
+                    .iter()
+                    .map(|(tagger_id, count)| (*count, tagger_id.as_str()))
+                    .collect();
+                Self::put_index_sorted_set(
+                    &bucket_taggers_key,
+                    &taggers,
+                    None,
+                    Some(BUCKET_TAGGERS_TTL),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrements the score of the member, removing it when it reaches zero. Returns whether it was removed
+    async fn decrement_or_remove(key_parts: &[&str], member: &str) -> Result<bool, DynError> {
+        Self::put_score_index_sorted_set(key_parts, &[member], ScoreAction::Decrement(1.0)).await?;
+        let score = Self::check_sorted_set_member(None, key_parts, &[member]).await?;
+        match score {
+            Some(score) if score > 0 => Ok(false),
+            _ => {
+                Self::remove_from_index_sorted_set(None, key_parts, &[member]).await?;
+                Ok(true)
+            }
+        }
+    }
+
+    fn build_key_parts<'a>(
+        tagged_type: &'a str,
+        granularity: &'a str,
+        series: &'a str,
+        label: &'a str,
+    ) -> Vec<&'a str> {
+        [
+            &TAG_HISTORY_KEY_PARTS[..],
+            &[tagged_type, granularity, series, label],
+        ]
+        .concat()
+    }
+
+    fn build_bucket_taggers_key_parts<'a>(
+        tagged_type: &'a str,
+        granularity: &'a str,
+        label: &'a str,
+        bucket: &'a str,
+    ) -> Vec<&'a str> {
+        [
+            &TAG_HISTORY_KEY_PARTS[..],
+            &[tagged_type, granularity, "BucketTaggers", label, bucket],
+        ]
+        .concat()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_start() {
+        // 2024-10-31T15:42:10Z, a Thursday
+        let timestamp = 1730389330000;
+        assert_eq!(
+            TagHistoryGranularity::Hour.bucket_start(timestamp),
+            1730386800000
+        );
+        assert_eq!(
+            TagHistoryGranularity::Day.bucket_start(timestamp),
+            1730332800000
+        );
+        // Monday 2024-10-28T00:00:00Z
+        assert_eq!(
+            TagHistoryGranularity::Week.bucket_start(timestamp),
+            1730073600000
+        );
+    }
+}
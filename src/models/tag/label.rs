@@ -0,0 +1,268 @@
+use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::models::tag::post::TagPost;
+use crate::models::tag::user::TagUser;
+use crate::models::tag::TagDetails;
+use crate::types::DynError;
+use crate::{get_neo4j_graph, queries, RedisOps, ScoreAction};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use unicode_normalization::UnicodeNormalization;
+
+pub const TAG_DISPLAY_KEY_PARTS: [&str; 2] = ["Tags", "Display"];
+/// Aliases followed to resolve a label whose canonical label became an alias itself
+const MAX_ALIAS_HOPS: usize = 8;
+
+/// Characters that only change how a label is rendered: variation selectors, zero width characters
+/// (except the joiner, it composes emojis) and the byte order mark
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{FE0E}' | '\u{FE0F}' | '\u{200B}' | '\u{200C}' | '\u{2060}' | '\u{FEFF}'
+    )
+}
+
+/// Normalizes a tag label so the labels that look the same share the same indexes: Unicode NFKC,
+/// lowercase, without invisible characters and with a single space between words.
+/// E.g. `" Ｂitcoin️ "` becomes `"bitcoin"`
+pub fn normalize_label(label: &str) -> String {
+    let label = label
+        .nfkc()
+        .filter(|c| !is_invisible(*c))
+        .collect::<String>()
+        .to_lowercase();
+    label.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Operator managed mapping from a label to the canonical label it is merged into, e.g. `btc` -> `bitcoin`
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TagAlias {
+    pub alias: String,
+    pub canonical: String,
+}
+
+impl RedisOps for TagAlias {}
+
+impl TagAlias {
+    pub fn new(alias: &str, canonical: &str) -> Self {
+        Self {
+            alias: normalize_label(alias),
+            canonical: normalize_label(canonical),
+        }
+    }
+
+    pub async fn get_by_alias(alias: &str) -> Result<Option<Self>, DynError> {
+        Self::try_from_index_json(&[&normalize_label(alias)], None).await
+    }
+
+    /// Stores the alias and merges the existing tags with the alias label into the canonical label,
+    /// in the graph and in the indexes. An alias of an alias is stored with the canonical label it resolves
+    /// to. Nothing is merged when the alias is already stored with the same canonical label
+    pub async fn put(&self) -> Result<(), DynError> {
+        if self.alias.is_empty() || self.canonical.is_empty() || self.alias == self.canonical {
+            return Err(format!("Invalid tag alias {} -> {}", self.alias, self.canonical).into());
+        }
+        let canonical = canonical_label(&self.canonical).await?;
+        if canonical == self.alias {
+            return Err(format!(
+                "Tag alias {} -> {} would resolve to itself",
+                self.alias, self.canonical
+            )
+            .into());
+        }
+        if let Some(stored) = Self::get_by_alias(&self.alias).await? {
+            if stored.canonical == canonical {
+                return Ok(());
+            }
+        }
+
+        let alias = Self {
+            alias: self.alias.clone(),
+            canonical,
+        };
+        alias.put_index_json(&[&alias.alias], None, None).await?;
+        merge_label(&alias.alias, &alias.canonical).await
+    }
+
+    pub async fn delete(alias: &str) -> Result<(), DynError> {
+        let alias = normalize_label(alias);
+        Self::remove_from_index_multiple_json(&[&[alias.as_str()][..]]).await
+    }
+
+    /// Registers the aliases of a JSON file with the shape `{ "alias": "canonical" }`. Used by operators
+    /// to configure the watcher
+    pub async fn register_from_file(path: &str) -> Result<(), DynError> {
+        let content = tokio::fs::read_to_string(path).await?;
+        let aliases: HashMap<String, String> = serde_json::from_str(&content)?;
+        for (alias, canonical) in aliases {
+            Self::new(&alias, &canonical).put().await?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolves the label used in the indexes and the graph for a label received from a homeserver or
+/// an API request: the normalized label, or its canonical label if it is an alias. The canonical label of
+/// an alias that became an alias later is resolved too
+pub async fn canonical_label(label: &str) -> Result<String, DynError> {
+    let mut label = normalize_label(label);
+    for _ in 0..MAX_ALIAS_HOPS {
+        match TagAlias::try_from_index_json(&[&label], None).await? {
+            Some(alias) => {
+                debug!("Tag label {} resolved to {}", label, alias.canonical);
+                label = alias.canonical;
+            }
+            None => break,
+        }
+    }
+    Ok(label)
+}
+
+/// Merges the tags with the `from` label into the `to` label, in the graph and in the indexes. Used when
+/// an alias is registered and to normalize the stored labels. Each merged tag leaves the indexes of the
+/// `from` label and joins the ones of the `to` label, as if it was deleted and created again, without
+/// notifying the tagged users. The tags whose tagger already tagged the same post or user with the `to`
+/// label are deleted
+/// # Arguments
+/// - `from` - The label of the merged tags.
+/// - `to` - The label the tags are merged into.
+pub async fn merge_label(from: &str, to: &str) -> Result<(), DynError> {
+    let mut result;
+    {
+        let graph = get_neo4j_graph()?;
+        let query = queries::put::merge_tag_label(from, to);
+
+        let graph = graph.lock().await;
+        result = graph.execute(query).await?;
+    }
+
+    let mut merged_tags = Vec::new();
+    while let Some(row) = result.next().await? {
+        let tagger_id: String = row.get("tagger_id")?;
+        let user_id: Option<String> = row.get("user_id").unwrap_or(None);
+        let post_id: Option<String> = row.get("post_id").unwrap_or(None);
+        let author_id: Option<String> = row.get("author_id").unwrap_or(None);
+        let original_label: String = row.get("original_label")?;
+        let indexed_at: i64 = row.get("indexed_at").unwrap_or_default();
+        let duplicated: bool = row.get("duplicated")?;
+        merged_tags.push((
+            tagger_id,
+            user_id,
+            post_id,
+            author_id,
+            original_label,
+            indexed_at,
+            duplicated,
+        ));
+    }
+
+    for (tagger_id, user_id, post_id, author_id, original_label, indexed_at, duplicated) in
+        &merged_tags
+    {
+        match (user_id, post_id, author_id) {
+            (Some(tagged_id), None, None) => {
+                TagUser::del_from_indexes(tagger_id, tagged_id, from, original_label, *indexed_at)
+                    .await?;
+                if !duplicated {
+                    TagUser::put_to_indexes(tagger_id, tagged_id, to, original_label, *indexed_at)
+                        .await?;
+                }
+            }
+            (None, Some(post_id), Some(author_id)) => {
+                TagPost::del_from_indexes(
+                    tagger_id,
+                    author_id,
+                    post_id,
+                    from,
+                    original_label,
+                    *indexed_at,
+                )
+                .await?;
+                if !duplicated {
+                    TagPost::put_to_indexes(
+                        tagger_id,
+                        author_id,
+                        post_id,
+                        to,
+                        original_label,
+                        *indexed_at,
+                    )
+                    .await?;
+                }
+            }
+            _ => {
+                debug!("MERGE-Tag: Unexpected combination of tag details");
+            }
+        }
+    }
+    debug!(
+        "Merged {} tags with the label {} into {}",
+        merged_tags.len(),
+        from,
+        to
+    );
+    Ok(())
+}
+
+/// Spellings of a label as written by the taggers, scored by the number of tags that use them.
+/// The most used spelling is the one displayed
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TagDisplay;
+
+impl RedisOps for TagDisplay {}
+
+impl TagDisplay {
+    /// Counts a tag written as `original_label` in the spellings of `label`
+    pub async fn put_to_index(label: &str, original_label: &str) -> Result<(), DynError> {
+        let key_parts = [&TAG_DISPLAY_KEY_PARTS[..], &[label]].concat();
+        Self::put_score_index_sorted_set(
+            &key_parts,
+            &[original_label.trim()],
+            ScoreAction::Increment(1.0),
+        )
+        .await
+    }
+
+    /// Discounts a deleted tag written as `original_label`. The spelling is dropped with its last tag
+    pub async fn del_from_index(label: &str, original_label: &str) -> Result<(), DynError> {
+        let key_parts = [&TAG_DISPLAY_KEY_PARTS[..], &[label]].concat();
+        let original_label = original_label.trim();
+        Self::put_score_index_sorted_set(
+            &key_parts,
+            &[original_label],
+            ScoreAction::Decrement(1.0),
+        )
+        .await?;
+        let score = Self::check_sorted_set_member(None, &key_parts, &[original_label]).await?;
+        if score.is_some_and(|score| score <= 0) {
+            Self::remove_from_index_sorted_set(None, &key_parts, &[original_label]).await?;
+        }
+        Ok(())
+    }
+
+    /// Retrieves the most used spelling of the label, `None` if no tag was counted
+    pub async fn get_original_label(label: &str) -> Result<Option<String>, DynError> {
+        let key_parts = [&TAG_DISPLAY_KEY_PARTS[..], &[label]].concat();
+        let spellings = Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            Some(1.0),
+            None,
+            Some(1),
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
+        Ok(spellings.and_then(|spellings| spellings.into_iter().next().map(|(label, _)| label)))
+    }
+
+    /// Sets the displayed label of the tags. The tags without counted spellings display their label
+    pub async fn set_original_labels(tags: &mut [TagDetails]) -> Result<(), DynError> {
+        for tag in tags.iter_mut() {
+            tag.original_label = Self::get_original_label(&tag.label)
+                .await?
+                .unwrap_or_else(|| tag.label.clone());
+        }
+        Ok(())
+    }
+}
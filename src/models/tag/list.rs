@@ -0,0 +1,112 @@
+use crate::db::connectors::neo4j::get_neo4j_graph;
+use crate::models::follow::Friends;
+use crate::types::DynError;
+use crate::{queries, RedisOps};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Same cap as the users of the Following timeline
+const MAX_LIST_MEMBERS: usize = 200;
+
+/// Personal list of users defined by a tag, the users that a tagger has tagged with a label.
+/// Indexed as a set under `User:List:{tagger_id}:{label}`
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UserList(pub Vec<String>);
+
+impl AsRef<[String]> for UserList {
+    fn as_ref(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl RedisOps for UserList {}
+
+impl UserList {
+    /// Retrieves the users that `tagger_id` tagged with `label`, from the index or the graph
+    pub async fn get_by_id(tagger_id: &str, label: &str) -> Result<Option<Self>, DynError> {
+        if let Some(members) =
+            Self::try_from_index_set(&[tagger_id, label], None, Some(MAX_LIST_MEMBERS), None)
+                .await?
+        {
+            return Ok(Some(Self(members)));
+        }
+        let lists = Self::get_from_graph(tagger_id, Some(label)).await?;
+        match lists.into_iter().next() {
+            Some((_, list)) => {
+                list.put_to_index(tagger_id, label).await?;
+                Ok(Some(list))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Retrieves the members of the list of the observer, optionally merged with the lists with the
+    /// same label of its friends
+    pub async fn get_members(
+        observer_id: &str,
+        label: &str,
+        include_friends: bool,
+    ) -> Result<Vec<String>, DynError> {
+        let mut taggers = vec![observer_id.to_string()];
+        if include_friends {
+            if let Some(friends) = Friends::get_by_id(observer_id, None, None).await? {
+                taggers.extend(friends.0);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut members = Vec::new();
+        for tagger_id in &taggers {
+            if let Some(list) = Self::get_by_id(tagger_id, label).await? {
+                members.extend(list.0.into_iter().filter(|id| seen.insert(id.clone())));
+            }
+        }
+        Ok(members)
+    }
+
+    pub async fn add_member(tagger_id: &str, label: &str, tagged_id: &str) -> Result<(), DynError> {
+        Self::put_index_set(&[tagger_id, label], &[tagged_id], None, None).await
+    }
+
+    pub async fn del_member(tagger_id: &str, label: &str, tagged_id: &str) -> Result<(), DynError> {
+        Self(vec![tagged_id.to_string()])
+            .remove_from_index_set(&[tagger_id, label])
+            .await
+    }
+
+    /// Indexes all the lists of the tagger
+    pub async fn reindex(tagger_id: &str) -> Result<(), DynError> {
+        for (label, list) in Self::get_from_graph(tagger_id, None).await? {
+            list.put_to_index(tagger_id, &label).await?;
+        }
+        Ok(())
+    }
+
+    async fn put_to_index(&self, tagger_id: &str, label: &str) -> Result<(), DynError> {
+        let members: Vec<&str> = self.0.iter().map(|id| id.as_str()).collect();
+        Self::put_index_set(&[tagger_id, label], &members, None, None).await
+    }
+
+    /// Retrieves the lists of the tagger from the graph, only the list of `label` if provided
+    async fn get_from_graph(
+        tagger_id: &str,
+        label: Option<&str>,
+    ) -> Result<Vec<(String, Self)>, DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::user_lists(tagger_id, label);
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut lists = Vec::new();
+        while let Some(row) = result.next().await? {
+            let label: String = row.get("label")?;
+            let members: Vec<String> = row.get("members")?;
+            lists.push((label, Self(members)));
+        }
+        Ok(lists)
+    }
+}
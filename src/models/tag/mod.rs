@@ -1,6 +1,10 @@
 pub mod details;
 pub mod global;
+pub mod history;
+pub mod label;
+pub mod list;
 pub mod post;
+pub mod related;
 pub mod search;
 pub mod stream;
 pub mod traits;
@@ -1,4 +1,14 @@
-use crate::RedisOps;
+use crate::db::kv::index::json::JsonAction;
+use crate::models::post::{PostCounts, PostRelationships, PostStream};
+use crate::models::tag::history::TagHistory;
+use crate::models::tag::label::TagDisplay;
+use crate::models::tag::related::RelatedTags;
+use crate::models::tag::search::TagSearch;
+use crate::models::tag::stream::HotTags;
+use crate::models::tag::TaggedType;
+use crate::models::user::UserCounts;
+use crate::types::DynError;
+use crate::{RedisOps, ScoreAction};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -32,3 +42,214 @@ impl TagCollection for TagPost {
 }
 
 impl TaggersCollection for TagPost {}
+
+impl TagPost {
+    /// Adds a new post tag to the indexes and updates the related counts
+    /// # Arguments
+    /// - `tagger_id` - The user tagging the post.
+    /// - `author_id` - The author of the tagged post.
+    /// - `post_id` - The tagged post.
+    /// - `tag_label` - The canonical label of the tag.
+    /// - `original_label` - The label as written by the tagger.
+    /// - `indexed_at` - The time the tag was indexed.
+    pub async fn put_to_indexes(
+        tagger_id: &str,
+        author_id: &str,
+        post_id: &str,
+        tag_label: &str,
+        original_label: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let post_key_slice: &[&str] = &[author_id, post_id];
+
+        let indexing_results = tokio::join!(
+            // Update user counts for tagger
+            UserCounts::update(tagger_id, "tagged", JsonAction::Increment(1), None),
+            // Increment in one the post tags
+            PostCounts::update_index_field(post_key_slice, "tags", JsonAction::Increment(1), None),
+            async {
+                // Increase unique_tags if the tag does not exist already
+                // NOTE: To update that field, it cannot exist in TagPost SORTED SET the tag. Thats why it has to be executed
+                // before TagPost operation
+                PostCounts::update_index_field(
+                    post_key_slice,
+                    "unique_tags",
+                    JsonAction::Increment(1),
+                    Some(tag_label),
+                )
+                .await?;
+                // Increment the label count to post
+                Self::update_index_score(
+                    author_id,
+                    Some(post_id),
+                    tag_label,
+                    ScoreAction::Increment(1.0),
+                )
+                .await?;
+                // NOTE: The co-occurrences depend on the updated label count of the post
+                RelatedTags::put_to_index(TaggedType::Post, post_key_slice, tag_label).await?;
+                Ok::<(), DynError>(())
+            },
+            // Add user tag in post
+            Self::add_tagger_to_index(author_id, Some(post_id), tagger_id, tag_label),
+            // Add post to label total engagement
+            TagSearch::update_index_score(
+                author_id,
+                post_id,
+                tag_label,
+                ScoreAction::Increment(1.0)
+            ),
+            async {
+                // Post replies cannot be included in the total engagement index once they have been tagged
+                if !PostRelationships::is_reply(author_id, post_id).await? {
+                    // Increment in one post global engagement
+                    PostStream::update_index_score(
+                        author_id,
+                        post_id,
+                        ScoreAction::Increment(1.0),
+                        indexed_at,
+                    )
+                    .await?;
+                }
+                Ok::<(), DynError>(())
+            },
+            // Add post to global label timeline
+            TagSearch::put_to_index(author_id, post_id, tag_label),
+            // Count the tag in the label history
+            TagHistory::put_to_index(TaggedType::Post, tag_label, tagger_id, indexed_at),
+            // Add post to the posts tagged by the tagger
+            PostStream::add_to_tagged_sorted_sets(
+                tagger_id, author_id, post_id, tag_label, indexed_at
+            ),
+            // The hot tags by reach of the users that reach the tagger are outdated
+            HotTags::del_reach_cache_by_tagger(tagger_id),
+            // Count the spelling of the label written by the tagger
+            TagDisplay::put_to_index(tag_label, original_label)
+        );
+
+        for result in [
+            indexing_results.0,
+            indexing_results.1,
+            indexing_results.2,
+            indexing_results.3,
+            indexing_results.4,
+            indexing_results.5,
+            indexing_results.6,
+            indexing_results.7,
+            indexing_results.8,
+            indexing_results.9,
+            indexing_results.10,
+        ] {
+            result?;
+        }
+
+        // The trending score depends on the updated counts and post tags
+        PostStream::update_root_trending_score(author_id, post_id).await
+    }
+
+    /// Removes a deleted post tag from the indexes and updates the related counts
+    /// # Arguments
+    /// - `tagger_id` - The user that tagged the post.
+    /// - `author_id` - The author of the tagged post.
+    /// - `post_id` - The tagged post.
+    /// - `tag_label` - The canonical label of the tag.
+    /// - `original_label` - The label as written by the tagger.
+    /// - `indexed_at` - The time the tag was indexed.
+    pub async fn del_from_indexes(
+        tagger_id: &str,
+        author_id: &str,
+        post_id: &str,
+        tag_label: &str,
+        original_label: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let post_key_slice: &[&str] = &[author_id, post_id];
+        let tag_post = Self(vec![tagger_id.to_string()]);
+
+        let indexing_results = tokio::join!(
+            // Update user counts for tagger
+            UserCounts::update(tagger_id, "tagged", JsonAction::Decrement(1), None),
+            // Decrement in one the post tags
+            PostCounts::update_index_field(post_key_slice, "tags", JsonAction::Decrement(1), None),
+            async {
+                // Decrement label score in the post
+                Self::update_index_score(
+                    author_id,
+                    Some(post_id),
+                    tag_label,
+                    ScoreAction::Decrement(1.0),
+                )
+                .await?;
+                // NOTE: The co-occurrences depend on the updated label count of the post
+                RelatedTags::del_from_index(TaggedType::Post, post_key_slice, tag_label).await?;
+                // Decrease unique_tag
+                // NOTE: To update that field, we first need to decrement the value in the SORTED SET associated with that tag
+                PostCounts::update_index_field(
+                    post_key_slice,
+                    "unique_tags",
+                    JsonAction::Decrement(1),
+                    Some(tag_label),
+                )
+                .await?;
+                Ok::<(), DynError>(())
+            },
+            // Decrease post from label total engagement
+            TagSearch::update_index_score(
+                author_id,
+                post_id,
+                tag_label,
+                ScoreAction::Decrement(1.0)
+            ),
+            async {
+                // Post replies cannot be included in the total engagement index once the tag have been deleted
+                if !PostRelationships::is_reply(author_id, post_id).await? {
+                    // Decrement in one post global engagement
+                    PostStream::update_index_score(
+                        author_id,
+                        post_id,
+                        ScoreAction::Decrement(1.0),
+                        indexed_at,
+                    )
+                    .await?;
+                }
+                Ok::<(), DynError>(())
+            },
+            async {
+                // Delete the tagger from the tag list
+                tag_post
+                    .del_from_index(author_id, Some(post_id), tag_label)
+                    .await?;
+                // NOTE: The tag search index, depends on the post taggers collection to delete
+                // Delete post from global label timeline
+                TagSearch::del_from_index(author_id, post_id, tag_label).await?;
+                Ok::<(), DynError>(())
+            },
+            // Discount the tag from the label history
+            TagHistory::del_from_index(TaggedType::Post, tag_label, tagger_id, indexed_at),
+            // Remove post from the posts tagged by the tagger
+            PostStream::remove_from_tagged_sorted_sets(tagger_id, author_id, post_id, tag_label),
+            // The hot tags by reach of the users that reach the tagger are outdated
+            HotTags::del_reach_cache_by_tagger(tagger_id),
+            // Discount the spelling of the label written by the tagger
+            TagDisplay::del_from_index(tag_label, original_label)
+        );
+
+        for result in [
+            indexing_results.0,
+            indexing_results.1,
+            indexing_results.2,
+            indexing_results.3,
+            indexing_results.4,
+            indexing_results.5,
+            indexing_results.6,
+            indexing_results.7,
+            indexing_results.8,
+            indexing_results.9,
+        ] {
+            result?;
+        }
+
+        // The trending score depends on the updated counts and post tags
+        PostStream::update_root_trending_score(author_id, post_id).await
+    }
+}
@@ -0,0 +1,240 @@
+use crate::db::graph::exec::retrieve_from_graph;
+use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::types::DynError;
+use crate::{get_neo4j_graph, queries, RedisOps, ScoreAction};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+use utoipa::ToSchema;
+
+use super::label::canonical_label;
+use super::post::POST_TAGS_KEY_PARTS;
+use super::stream::TagStreamReach;
+use super::user::USER_TAGS_KEY_PARTS;
+use super::TaggedType;
+
+pub const TAG_RELATED_KEY_PARTS: [&str; 2] = ["Tags", "Related"];
+
+/// A label that co-occurs with another one on the same posts or users
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone)]
+pub struct RelatedTag {
+    pub label: String,
+    /// Number of posts or users tagged with both labels
+    pub strength: f64,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+pub struct RelatedTags(pub Vec<RelatedTag>);
+
+impl RedisOps for RelatedTags {}
+
+impl Deref for RelatedTags {
+    type Target = Vec<RelatedTag>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl RelatedTags {
+    /// Retrieves the labels that co-occur with `label`, the strongest first. Global co-occurrences are
+    /// served from the index, the ones restricted to the reach of a user are computed in the graph
+    ///
+    /// # Arguments
+    /// * `label` - The tag label to find related labels for
+    /// * `tagged_type` - Whether the labels co-occur on posts or on users
+    /// * `user_id` - An optional user ID. If provided, only the tags of the users in its reach are counted
+    /// * `reach` - The reach of the user. Defaults to `Following`
+    /// * `skip` - The number of related labels to skip for pagination
+    /// * `limit` - The maximum number of related labels to retrieve
+    pub async fn get_related(
+        label: &str,
+        tagged_type: TaggedType,
+        user_id: Option<String>,
+        reach: Option<TagStreamReach>,
+        skip: usize,
+        limit: usize,
+    ) -> Result<Option<Self>, DynError> {
+        let label = &canonical_label(label).await?;
+        match user_id {
+            Some(user_id) => {
+                let query = queries::get::get_related_tags_by_reach(
+                    label,
+                    &tagged_type,
+                    &user_id,
+                    reach.unwrap_or(TagStreamReach::Following),
+                    skip,
+                    limit,
+                );
+                retrieve_from_graph::<RelatedTags>(query, "related_tags").await
+            }
+            None => Self::get_from_index(label, &tagged_type, skip, limit).await,
+        }
+    }
+
+    async fn get_from_index(
+        label: &str,
+        tagged_type: &TaggedType,
+        skip: usize,
+        limit: usize,
+    ) -> Result<Option<Self>, DynError> {
+        let tagged_type = tagged_type.to_string();
+        // Labels that stopped co-occurring keep a zero score
+        let related = Self::try_from_index_sorted_set(
+            &Self::build_key_parts(&tagged_type, label),
+            None,
+            Some(1.0),
+            Some(skip),
+            Some(limit),
+            SortOrder::Descending,
+            None,
+        )
+        .await?;
+
+        Ok(related.map(|related| {
+            RelatedTags(
+                related
+                    .into_iter()
+                    .map(|(label, strength)| RelatedTag { label, strength })
+                    .collect(),
+            )
+        }))
+    }
+
+    /// Counts a new co-occurrence of `label` with the rest of labels of the tagged post or user.
+    /// It has to be called after the label score of the target was incremented
+    ///
+    /// # Arguments
+    /// * `tagged_type` - Whether the target is a post or a user
+    /// * `target_key_parts` - `[author_id, post_id]` for a post or `[user_id]` for a user
+    /// * `label` - The label added to the target
+    pub async fn put_to_index(
+        tagged_type: TaggedType,
+        target_key_parts: &[&str],
+        label: &str,
+    ) -> Result<(), DynError> {
+        Self::update_co_occurrences(
+            tagged_type,
+            target_key_parts,
+            label,
+            ScoreAction::Increment(1.0),
+        )
+        .await
+    }
+
+    /// Discounts the co-occurrences of `label` with the rest of labels of the tagged post or user.
+    /// It has to be called after the label score of the target was decremented
+    ///
+    /// # Arguments
+    /// * `tagged_type` - Whether the target is a post or a user
+    /// * `target_key_parts` - `[author_id, post_id]` for a post or `[user_id]` for a user
+    /// * `label` - The label removed from the target
+    pub async fn del_from_index(
+        tagged_type: TaggedType,
+        target_key_parts: &[&str],
+        label: &str,
+    ) -> Result<(), DynError> {
+        Self::update_co_occurrences(
+            tagged_type,
+            target_key_parts,
+            label,
+            ScoreAction::Decrement(1.0),
+        )
+        .await
+    }
+
+    async fn update_co_occurrences(
+        tagged_type: TaggedType,
+        target_key_parts: &[&str],
+        label: &str,
+        score_action: ScoreAction,
+    ) -> Result<(), DynError> {
+        let target_tags_key_parts = match tagged_type {
+            TaggedType::Post => [&POST_TAGS_KEY_PARTS[..], target_key_parts].concat(),
+            TaggedType::User => [&USER_TAGS_KEY_PARTS[..], target_key_parts].concat(),
+        };
+
+        // The co-occurrence only changes when the first tagger adds the label or the last one removes it
+        let label_score = Self::check_sorted_set_member(None, &target_tags_key_parts, &[label])
+            .await?
+            .unwrap_or(0);
+        let changed = match score_action {
+            ScoreAction::Increment(_) => label_score == 1,
+            ScoreAction::Decrement(_) => label_score == 0,
+        };
+        if !changed {
+            return Ok(());
+        }
+
+        let labels = Self::try_from_index_sorted_set(
+            &target_tags_key_parts,
+            None,
+            Some(1.0),
+            None,
+            None,
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .unwrap_or_default();
+
+        let tagged_type = tagged_type.to_string();
+        for (other_label, _) in labels.iter().filter(|(other, _)| other.as_str() != label) {
+            Self::put_score_index_sorted_set(
+                &Self::build_key_parts(&tagged_type, label),
+                &[other_label.as_str()],
+                score_action,
+            )
+            .await?;
+            Self::put_score_index_sorted_set(
+                &Self::build_key_parts(&tagged_type, other_label),
+                &[label],
+                score_action,
+            )
+            .await?;
+        }
+
+        // Labels that no longer co-occur leave the related tags
+        if let ScoreAction::Decrement(_) = score_action {
+            let mut key_parts_list = vec![Self::build_key_parts(&tagged_type, label)];
+            for (other_label, _) in labels.iter().filter(|(other, _)| other.as_str() != label) {
+                key_parts_list.push(Self::build_key_parts(&tagged_type, other_label));
+            }
+            Self::remove_non_positive_from_index_sorted_sets(&key_parts_list).await?;
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the co-occurrence indexes of the posts and users labels from the graph
+    pub async fn reindex() -> Result<(), DynError> {
+        for tagged_type in [TaggedType::Post, TaggedType::User] {
+            let mut result;
+            {
+                let graph = get_neo4j_graph()?;
+                let query = queries::get::global_related_tags(&tagged_type);
+
+                let graph = graph.lock().await;
+                result = graph.execute(query).await?;
+            }
+
+            let tagged_type = tagged_type.to_string();
+            while let Some(row) = result.next().await? {
+                let label: &str = row.get("label").unwrap_or("");
+                let sorted_set: Vec<(f64, &str)> = row.get("sorted_set").unwrap_or_default();
+                if !label.is_empty() && !sorted_set.is_empty() {
+                    Self::put_index_sorted_set(
+                        &Self::build_key_parts(&tagged_type, label),
+                        &sorted_set,
+                        None,
+                        None,
+                    )
+                    .await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn build_key_parts<'a>(tagged_type: &'a str, label: &'a str) -> Vec<&'a str> {
+        [&TAG_RELATED_KEY_PARTS[..], &[tagged_type, label]].concat()
+    }
+}
@@ -1,8 +1,12 @@
 use crate::db::connectors::neo4j::get_neo4j_graph;
 use crate::db::kv::index::sorted_sets::SortOrder;
 use crate::models::post::PostDetails;
+use crate::models::tag::label::canonical_label;
 use crate::models::tag::traits::TaggersCollection;
-use crate::queries::get::{global_tags_by_post, global_tags_by_post_engagement};
+use crate::queries::get::{
+    global_tags_by_post, global_tags_by_post_engagement, global_tags_by_user,
+    users_by_tag_in_reach, users_by_tag_unique_taggers,
+};
 use crate::types::DynError;
 use crate::types::{Pagination, StreamSorting};
 use crate::{RedisOps, ScoreAction};
@@ -14,6 +18,11 @@ use super::post::TagPost;
 
 pub const TAG_GLOBAL_POST_TIMELINE: [&str; 4] = ["Tags", "Global", "Post", "Timeline"];
 pub const TAG_GLOBAL_POST_ENGAGEMENT: [&str; 4] = ["Tags", "Global", "Post", "TotalEngagement"];
+pub const TAG_GLOBAL_POST_TRENDING: [&str; 4] = ["Tags", "Global", "Post", "Trending"];
+pub const TAG_GLOBAL_USER_TAG_COUNT: [&str; 4] = ["Tags", "Global", "User", "TagCount"];
+
+/// Follow distance of the taggers counted by the `wot` sorting when the depth is not provided
+const DEFAULT_USER_SEARCH_DEPTH: u8 = 2;
 
 /// Represents a single search result of post keys (`author_id:post_id`) by tags
 #[derive(Serialize, Deserialize, ToSchema, Default)]
@@ -72,6 +81,7 @@ impl TagSearch {
         sort_by: Option<StreamSorting>,
         pagination: Pagination,
     ) -> Result<Option<Vec<TagSearch>>, DynError> {
+        let label = &canonical_label(label).await?;
         let post_score_list = match sort_by {
             Some(StreamSorting::TotalEngagement) => {
                 Self::try_from_index_sorted_set(
@@ -85,6 +95,18 @@ impl TagSearch {
                 )
                 .await?
             }
+            Some(StreamSorting::Trending) => {
+                Self::try_from_index_sorted_set(
+                    &[&TAG_GLOBAL_POST_TRENDING[..], &[label]].concat(),
+                    pagination.start,
+                    pagination.end,
+                    pagination.skip,
+                    pagination.limit,
+                    SortOrder::Descending,
+                    None,
+                )
+                .await?
+            }
             // Default case always: SortBy::Timeline
             _ => {
                 Self::try_from_index_sorted_set(
@@ -155,10 +177,115 @@ impl TagSearch {
         let label_taggers = TagPost::get_from_index(post_label_key, None, None, None, None).await?;
         // Make sure that post does not have more taggers with that tag. Post:Taggers:user_id:post_id:label
         if label_taggers.is_none() {
-            let key_parts = [&TAG_GLOBAL_POST_TIMELINE[..], &[tag_label]].concat();
             let post_key = format!("{}:{}", author_id, post_id);
+            let key_parts = [&TAG_GLOBAL_POST_TIMELINE[..], &[tag_label]].concat();
+            Self::remove_from_index_sorted_set(None, &key_parts, &[&post_key]).await?;
+            let key_parts = [&TAG_GLOBAL_POST_TRENDING[..], &[tag_label]].concat();
             Self::remove_from_index_sorted_set(None, &key_parts, &[&post_key]).await?;
         }
         Ok(())
     }
 }
+
+/// Ranking of the users tagged with a label
+#[derive(Deserialize, ToSchema, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum UserTagSorting {
+    /// Number of times the user was tagged with the label
+    #[default]
+    TagCount,
+    /// Number of different users that tagged the user, with any label
+    UniqueTaggers,
+    /// Number of users in the viewer's follow network that tagged the user with the label
+    Wot,
+}
+
+/// Search result of the user IDs tagged with a label
+#[derive(Serialize, Deserialize, ToSchema, Default)]
+pub struct UserTagSearch(pub Vec<String>);
+
+impl RedisOps for UserTagSearch {}
+
+impl UserTagSearch {
+    /// Indexes the number of tags of each label on every user
+    pub async fn reindex() -> Result<(), DynError> {
+        TagSearch::add_to_global_sorted_set(global_tags_by_user(), TAG_GLOBAL_USER_TAG_COUNT).await
+    }
+
+    /// Retrieves the users tagged with the label, the highest ranked first
+    ///
+    /// # Arguments
+    /// * `label` - The label of the tags
+    /// * `sorting` - The ranking of the users
+    /// * `viewer_id` - The user whose follow network is counted. Required by the `wot` sorting
+    /// * `depth` - Follow distance (1-3) of the counted taggers. Defaults to `DEFAULT_USER_SEARCH_DEPTH`
+    /// * `skip` - The number of users to skip for pagination
+    /// * `limit` - The maximum number of users to retrieve
+    pub async fn get_by_label(
+        label: &str,
+        sorting: UserTagSorting,
+        viewer_id: Option<&str>,
+        depth: Option<u8>,
+        skip: usize,
+        limit: usize,
+    ) -> Result<Option<Self>, DynError> {
+        let label = &canonical_label(label).await?;
+        let query = match sorting {
+            UserTagSorting::TagCount => {
+                let user_ids = Self::try_from_index_sorted_set(
+                    &[&TAG_GLOBAL_USER_TAG_COUNT[..], &[label]].concat(),
+                    None,
+                    Some(1.0),
+                    Some(skip),
+                    Some(limit),
+                    SortOrder::Descending,
+                    None,
+                )
+                .await?;
+                return Ok(
+                    user_ids.map(|set| Self(set.into_iter().map(|(user_id, _)| user_id).collect()))
+                );
+            }
+            UserTagSorting::UniqueTaggers => users_by_tag_unique_taggers(label, skip, limit),
+            UserTagSorting::Wot => {
+                let viewer_id = viewer_id.ok_or("viewer_id should be provided for wot sorting")?;
+                let depth = depth.unwrap_or(DEFAULT_USER_SEARCH_DEPTH).clamp(1, 3);
+                users_by_tag_in_reach(label, viewer_id, depth, skip, limit)
+            }
+        };
+
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut user_ids = Vec::new();
+        while let Some(row) = result.next().await? {
+            user_ids.push(row.get("user_id")?);
+        }
+        match user_ids.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(Self(user_ids))),
+        }
+    }
+
+    /// Updates the number of tags of the label on the user. The user leaves the label search results
+    /// when the last tag is deleted
+    pub async fn update_index_score(
+        user_id: &str,
+        label: &str,
+        score_action: ScoreAction,
+    ) -> Result<(), DynError> {
+        let key_parts = [&TAG_GLOBAL_USER_TAG_COUNT[..], &[label]].concat();
+        Self::put_score_index_sorted_set(&key_parts, &[user_id], score_action).await?;
+        if let ScoreAction::Decrement(_) = score_action {
+            let score = Self::check_sorted_set_member(None, &key_parts, &[user_id]).await?;
+            if score.is_some_and(|score| score <= 0) {
+                Self::remove_from_index_sorted_set(None, &key_parts, &[user_id]).await?;
+            }
+        }
+        Ok(())
+    }
+}
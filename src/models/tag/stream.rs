@@ -1,9 +1,11 @@
 use crate::db::graph::exec::retrieve_from_graph;
 use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::models::follow::{Followers, Following};
 use crate::routes::v0::tag::HotTagsInput;
 use crate::types::{DynError, Timeframe};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
 use std::ops::Deref;
 use utoipa::ToSchema;
 
@@ -15,6 +17,25 @@ use super::TaggedType;
 
 pub const HOT_TAGS_CACHE_PREFIX: &str = "Cache";
 pub const POST_HOT_TAGS: [&str; 3] = ["Tags", "Post", "Hot"];
+pub const REACH_HOT_TAGS: [&str; 3] = ["Tags", "Reach", "Hot"];
+/// Set of the users with cached hot tags by reach, the ones to invalidate when their reach changes
+const REACH_HOT_TAGS_USERS: [&str; 4] = ["Tags", "Reach", "Hot", "Users"];
+/// The hot tags by reach are cached for the named timeframes, the custom ranges are computed on each request
+const REACH_HOT_TAGS_TIMEFRAMES: [Timeframe; 6] = [
+    Timeframe::Today,
+    Timeframe::ThisMonth,
+    Timeframe::AllTime,
+    Timeframe::Last24Hours,
+    Timeframe::Last7Days,
+    Timeframe::Last30Days,
+];
+const REACH_HOT_TAGS_REACHES: [TagStreamReach; 3] = [
+    TagStreamReach::Followers,
+    TagStreamReach::Following,
+    TagStreamReach::Friends,
+];
+// TTL, 1 DAY. The longest cache period of the cached timeframes
+const REACH_HOT_TAGS_USERS_TTL: i64 = 60 * 60 * 24;
 
 #[derive(Deserialize, Debug, ToSchema, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -24,6 +45,16 @@ pub enum TagStreamReach {
     Friends,
 }
 
+impl Display for TagStreamReach {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TagStreamReach::Followers => write!(f, "Followers"),
+            TagStreamReach::Following => write!(f, "Following"),
+            TagStreamReach::Friends => write!(f, "Friends"),
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, ToSchema, Debug, Clone)]
 pub struct HotTag {
     pub label: String,
@@ -83,7 +114,8 @@ impl HotTags {
 
     /// Retrieves hot tags based on the user's reach criteria
     /// Queries the graph database to fetch hot tags relevant to a given user,
-    /// filtered by their reach and additional criteria defined in `hot_tags_input`
+    /// filtered by their reach and additional criteria defined in `hot_tags_input`.
+    /// The first hot tags of the named timeframes are cached until the reach of the user changes
     ///
     /// # Arguments
     /// * `user_id` - The ID of the user whose reach is used for filtering hot tags
@@ -94,8 +126,148 @@ impl HotTags {
         reach: TagStreamReach,
         hot_tags_input: &HotTagsInput,
     ) -> Result<Option<HotTags>, DynError> {
-        let query = queries::get::get_hot_tags_by_reach(user_id.as_str(), reach, hot_tags_input);
-        retrieve_from_graph::<HotTags>(query, "hot_tags").await
+        if !Self::is_reach_cacheable(hot_tags_input) {
+            let query =
+                queries::get::get_hot_tags_by_reach(user_id.as_str(), reach, hot_tags_input);
+            return retrieve_from_graph::<HotTags>(query, "hot_tags").await;
+        }
+
+        let (reach_str, timeframe) = (reach.to_string(), hot_tags_input.timeframe.to_string());
+        let key_parts = Self::build_reach_hot_tags_key_parts(&user_id, &reach_str, &timeframe);
+        let hot_tags =
+            match HotTags::try_from_index_json(&key_parts, Some(HOT_TAGS_CACHE_PREFIX.to_string()))
+                .await?
+            {
+                Some(hot_tags) => hot_tags,
+                None => {
+                    let cached_input = HotTagsInput::new(
+                        hot_tags_input.timeframe.clone(),
+                        100,
+                        0,
+                        20,
+                        Some(TaggedType::Post),
+                    );
+                    let query =
+                        queries::get::get_hot_tags_by_reach(user_id.as_str(), reach, &cached_input);
+                    let hot_tags = retrieve_from_graph::<HotTags>(query, "hot_tags")
+                        .await?
+                        .unwrap_or_default();
+                    Self::put_reach_hot_tags_to_index(
+                        &user_id,
+                        &key_parts,
+                        &hot_tags,
+                        hot_tags_input,
+                    )
+                    .await?;
+                    hot_tags
+                }
+            };
+
+        Ok(Some(
+            hot_tags
+                .0
+                .into_iter()
+                .skip(hot_tags_input.skip)
+                .take(hot_tags_input.limit)
+                .map(|hot_tag| HotTag {
+                    taggers_id: Taggers(Taggers::get_taggers_by_pagination(
+                        &hot_tag.taggers_id,
+                        0,
+                        hot_tags_input.taggers_limit,
+                    )),
+                    ..hot_tag
+                })
+                .collect(),
+        ))
+    }
+
+    /// Only the first 100 hot tags with 20 taggers of the named timeframes are cached
+    fn is_reach_cacheable(hot_tags_input: &HotTagsInput) -> bool {
+        hot_tags_input.min_reputation.is_none()
+            && matches!(hot_tags_input.tagged_type, Some(TaggedType::Post))
+            && REACH_HOT_TAGS_TIMEFRAMES.contains(&hot_tags_input.timeframe)
+            && hot_tags_input.skip + hot_tags_input.limit <= 100
+            && hot_tags_input.taggers_limit <= 20
+    }
+
+    async fn put_reach_hot_tags_to_index(
+        user_id: &str,
+        key_parts: &[&str],
+        hot_tags: &HotTags,
+        hot_tags_input: &HotTagsInput,
+    ) -> Result<(), DynError> {
+        hot_tags
+            .put_index_json(
+                key_parts,
+                Some(HOT_TAGS_CACHE_PREFIX.to_string()),
+                Some(hot_tags_input.timeframe.to_cache_period()),
+            )
+            .await?;
+        HotTags::put_index_set(
+            &REACH_HOT_TAGS_USERS,
+            &[user_id],
+            Some(REACH_HOT_TAGS_USERS_TTL),
+            Some(HOT_TAGS_CACHE_PREFIX.to_string()),
+        )
+        .await
+    }
+
+    /// Drops the cached hot tags by reach of the users, e.g. after one of them follows or unfollows someone
+    ///
+    /// # Arguments
+    /// * `user_ids` - The users whose reach changed
+    pub async fn del_reach_cache(user_ids: &[&str]) -> Result<(), DynError> {
+        let timeframes: Vec<String> = REACH_HOT_TAGS_TIMEFRAMES
+            .iter()
+            .map(Timeframe::to_string)
+            .collect();
+        let reaches: Vec<String> = REACH_HOT_TAGS_REACHES
+            .iter()
+            .map(TagStreamReach::to_string)
+            .collect();
+
+        let mut key_parts_list = Vec::new();
+        for user_id in user_ids {
+            for reach in &reaches {
+                for timeframe in &timeframes {
+                    key_parts_list.push(Self::build_reach_hot_tags_key_parts(
+                        user_id, reach, timeframe,
+                    ));
+                }
+            }
+        }
+        let key_parts_list: Vec<&[&str]> = key_parts_list.iter().map(Vec::as_slice).collect();
+        HotTags::remove_from_prefixed_index_multiple_json(&key_parts_list, HOT_TAGS_CACHE_PREFIX)
+            .await
+    }
+
+    /// Drops the cached hot tags by reach of the users that have the tagger in their reach,
+    /// its followers and the users it follows
+    ///
+    /// # Arguments
+    /// * `tagger_id` - The user that tagged or removed a tag
+    pub async fn del_reach_cache_by_tagger(tagger_id: &str) -> Result<(), DynError> {
+        // Only the users with cached hot tags by reach are invalidated
+        let reach_keys = [
+            format!("{}:{}", Followers::prefix().await, tagger_id),
+            format!("{}:{}", Following::prefix().await, tagger_id),
+        ];
+        let mut user_ids = HashSet::new();
+        for reach_key in reach_keys {
+            user_ids.extend(
+                HotTags::intersect_index_sets(
+                    &REACH_HOT_TAGS_USERS,
+                    Some(HOT_TAGS_CACHE_PREFIX.to_string()),
+                    &[reach_key],
+                )
+                .await?,
+            );
+        }
+        let user_ids: Vec<&str> = user_ids.iter().map(String::as_str).collect();
+        if user_ids.is_empty() {
+            return Ok(());
+        }
+        Self::del_reach_cache(&user_ids).await
     }
 
     /// Retrieves global hot tags, checking the cache first before querying the database.
@@ -109,6 +281,14 @@ impl HotTags {
     async fn get_global_hot_tags(
         hot_tags_input: &HotTagsInput,
     ) -> Result<Option<HotTags>, DynError> {
+        // The cache only holds the hot tags of all the taggers in the named timeframes, each custom
+        // range would add its own entry
+        if hot_tags_input.min_reputation.is_some()
+            || matches!(hot_tags_input.timeframe, Timeframe::Custom { .. })
+        {
+            let query = queries::get::get_global_hot_tags(hot_tags_input);
+            return retrieve_from_graph::<HotTags>(query, "hot_tags").await;
+        }
         let cached_hot_tags = HotTags::get_from_global_cache(hot_tags_input).await?;
         // If it is cache miss, retry all the info related from the graph
         if cached_hot_tags.is_some() {
@@ -233,6 +413,14 @@ impl HotTags {
         [&POST_HOT_TAGS[..], &[timeframe]].concat()
     }
 
+    fn build_reach_hot_tags_key_parts<'a>(
+        user_id: &'a str,
+        reach: &'a str,
+        timeframe: &'a str,
+    ) -> Vec<&'a str> {
+        [&REACH_HOT_TAGS[..], &[user_id, reach, timeframe]].concat()
+    }
+
     /// Reindexes global hot tags
     /// Retrieves and updates global hot tags for different timeframes. It fetches the top 100 hot tags
     ///  with a taggers limit of 20 for both "all-time" and "this month" timeframes
@@ -12,8 +12,19 @@ use crate::{
     queries, RedisOps, ScoreAction,
 };
 
+use crate::models::tag::label::TagDisplay;
 use crate::models::tag::TagDetails;
 
+/// Target user id, target post id, post author id, label, label written by the tagger and creation time of a deleted tag
+pub type DeletedTagDetails = (
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    String,
+    String,
+    i64,
+);
+
 const CACHE_SORTED_SET_PREFIX: &str = "Cache:Sorted";
 pub const CACHE_SET_PREFIX: &str = "Cache";
 // TTL, 3HR
@@ -42,8 +53,8 @@ where
     ///
     /// # Behavior
     ///
-    /// - If `viewer_id` is provided and `depth` is within the range 1-3, it will retrieve the WoT tags
-    /// - If `viewer_id` is not provided or `depth` is out of range, the function retrieves global tags for the user
+    /// - If `viewer_id` is provided and `depth` is within the range 1-3, it will retrieve the WoT tags of the user or post
+    /// - If `viewer_id` is not provided or `depth` is out of range, the function retrieves global tags for the user or post
     /// - The function ensures results from the graph database are cached in the index for faster future retrievals.
     async fn get_by_id(
         user_id: &str,
@@ -55,10 +66,11 @@ where
         depth: Option<u8>,
     ) -> Result<Option<Vec<TagDetails>>, DynError> {
         // Query for the tags that are in its WoT
-        // Actually we just apply that search to User node
-        if viewer_id.is_some() && matches!(depth, Some(1..=3)) {
+        let tag_details = if let (Some(viewer), Some(depth @ 1..=3)) = (viewer_id, depth) {
+            // The WoT cache of a post is identified by its author and post ids
+            let wot_key = Self::create_wot_target_key(user_id, extra_param);
             match Self::get_from_index(
-                user_id,
+                &wot_key,
                 viewer_id,
                 viewer_id,
                 skip_tags,
@@ -68,40 +80,47 @@ where
             )
             .await?
             {
-                Some(tag_details) => return Ok(Some(tag_details)),
+                Some(tag_details) => Some(tag_details),
                 None => {
-                    let depth = depth.unwrap_or(1);
                     let graph_response =
-                        Self::get_from_graph(user_id, viewer_id, Some(depth)).await?;
-                    if let Some(tag_details) = graph_response {
-                        Self::put_to_index(user_id, viewer_id, &tag_details, true).await?;
-                        return Ok(Some(tag_details));
+                        Self::get_from_graph(user_id, extra_param, Some((viewer, depth))).await?;
+                    if let Some(tag_details) = &graph_response {
+                        Self::put_to_index(&wot_key, viewer_id, tag_details, true).await?;
                     }
-                    return Ok(None);
+                    graph_response
                 }
             }
-        }
-        // Get global tags for that user/post
-        match Self::get_from_index(
-            user_id,
-            extra_param,
-            viewer_id,
-            skip_tags,
-            limit_tags,
-            limit_taggers,
-            false,
-        )
-        .await?
-        {
-            Some(tag_details) => Ok(Some(tag_details)),
-            None => {
-                let graph_response = Self::get_from_graph(user_id, extra_param, None).await?;
-                if let Some(tag_details) = graph_response {
-                    Self::put_to_index(user_id, extra_param, &tag_details, false).await?;
-                    return Ok(Some(tag_details));
+        } else {
+            // Get global tags for that user/post
+            match Self::get_from_index(
+                user_id,
+                extra_param,
+                viewer_id,
+                skip_tags,
+                limit_tags,
+                limit_taggers,
+                false,
+            )
+            .await?
+            {
+                Some(tag_details) => Some(tag_details),
+                None => {
+                    let graph_response = Self::get_from_graph(user_id, extra_param, None).await?;
+                    if let Some(tag_details) = &graph_response {
+                        Self::put_to_index(user_id, extra_param, tag_details, false).await?;
+                    }
+                    graph_response
                 }
-                Ok(None)
             }
+        };
+
+        match tag_details {
+            Some(mut tag_details) => {
+                // The labels are displayed as the taggers write them
+                TagDisplay::set_original_labels(&mut tag_details).await?;
+                Ok(Some(tag_details))
+            }
+            None => Ok(None),
         }
     }
 
@@ -186,25 +205,28 @@ where
     /// Retrieves the tag collection from the graph database if it is not found in the index.
     /// # Arguments
     /// * user_id - The key of the user for whom to retrieve tags.
-    /// * extra_param - An optional parameter for specifying additional constraints (e.g., post_id, viewer_id (for WoT search) )
-    /// * `depth` - An optional depth value (1-3) for filtering tags within the viewer's Web of Trust.
+    /// * extra_param - An optional parameter for specifying additional constraints (e.g., post_id)
+    /// * `web_of_trust` - An optional viewer ID and depth (1-3) for filtering tags within the viewer's Web of Trust.
     /// # Returns
     /// A Result containing an optional vector of TagDetails, or an error.
     async fn get_from_graph(
         user_id: &str,
         extra_param: Option<&str>,
-        depth: Option<u8>,
+        web_of_trust: Option<(&str, u8)>,
     ) -> Result<Option<Vec<TagDetails>>, DynError> {
         let mut result;
         {
             // We cannot use LIMIT clause because we need all data related
-            let query = match depth {
-                Some(distance) => queries::get::get_viewer_trusted_network_tags(
-                    user_id,
-                    extra_param.unwrap_or_default(),
-                    distance,
-                ),
-                None => Self::read_graph_query(user_id, extra_param),
+            let query = match (web_of_trust, extra_param) {
+                (Some((viewer_id, distance)), Some(post_id)) => {
+                    queries::get::get_viewer_trusted_network_post_tags(
+                        user_id, post_id, viewer_id, distance,
+                    )
+                }
+                (Some((viewer_id, distance)), None) => {
+                    queries::get::get_viewer_trusted_network_tags(user_id, viewer_id, distance)
+                }
+                (None, _) => Self::read_graph_query(user_id, extra_param),
             };
             let graph = get_neo4j_graph()?;
 
@@ -322,7 +344,8 @@ where
     ///   If `Some`, the function creates a tag relationship associated with a specific post;
     ///   otherwise, it creates a tag relationship between users.
     /// - `tag_id` - A string slice representing the unique identifier of the tag being created.
-    /// - `label` - A string slice representing the label of the tag.
+    /// - `label` - A string slice representing the normalized label of the tag.
+    /// - `original_label` - A string slice representing the label as written by the tagger.
     /// - `indexed_at` - A 64-bit integer representing the timestamp (milliseconds)
     ///   when the tag was indexed.
     async fn put_to_graph(
@@ -331,6 +354,7 @@ where
         extra_param: Option<&str>,
         tag_id: &str,
         label: &str,
+        original_label: &str,
         indexed_at: i64,
     ) -> Result<OperationOutcome, DynError> {
         let query = match extra_param {
@@ -340,6 +364,7 @@ where
                 post_id,
                 tag_id,
                 label,
+                original_label,
                 indexed_at,
             ),
             None => queries::put::create_user_tag(
@@ -347,6 +372,7 @@ where
                 tagged_user_id,
                 tag_id,
                 label,
+                original_label,
                 indexed_at,
             ),
         };
@@ -382,11 +408,13 @@ where
     /// # Returns
     ///
     /// A `Result` containing:
-    /// * `Some((Option<String>, Option<String>, Option<String>, String))`: If the tag was found and deleted:
+    /// * `Some(DeletedTagDetails)`: If the tag was found and deleted:
     ///   - `Option<String>` for the `user_id` of the target (if the target is a user, otherwise `None`),
     ///   - `Option<String>` for the `post_id` of the target (if the target is a post, otherwise `None`),
     ///   - `Option<String>` for the `author_id` of the post (if applicable, otherwise `None`),
-    ///   - `String` for the tag label.
+    ///   - `String` for the tag label,
+    ///   - `String` for the label as written by the tagger,
+    ///   - `i64` for the creation time of the tag.
     /// * `None` if no matching tag relationship is found.
     ///
     /// # Errors
@@ -395,7 +423,7 @@ where
     async fn del_from_graph(
         user_id: &str,
         tag_id: &str,
-    ) -> Result<Option<(Option<String>, Option<String>, Option<String>, String)>, DynError> {
+    ) -> Result<Option<DeletedTagDetails>, DynError> {
         let mut result;
         {
             let graph = get_neo4j_graph()?;
@@ -410,7 +438,16 @@ where
             let author_id: Option<String> = row.get("author_id").unwrap_or(None);
             let post_id: Option<String> = row.get("post_id").unwrap_or(None);
             let label: String = row.get("label").expect("Query should return tag label");
-            return Ok(Some((user_id, post_id, author_id, label)));
+            let original_label: String = row.get("original_label").unwrap_or(label.clone());
+            let indexed_at: i64 = row.get("indexed_at").unwrap_or_default();
+            return Ok(Some((
+                user_id,
+                post_id,
+                author_id,
+                label,
+                original_label,
+                indexed_at,
+            )));
         }
         Ok(None)
     }
@@ -431,6 +468,19 @@ where
         }
     }
 
+    /// Constructs the key that identifies the tagged user or post in the WoT cache indexes
+    /// # Arguments
+    /// * user_id - The key of the user, the author in case of a post.
+    /// * extra_param - An optional post_id
+    /// # Returns
+    /// The user_id or the `author_id:post_id` key of a post
+    fn create_wot_target_key(user_id: &str, extra_param: Option<&str>) -> String {
+        match extra_param {
+            Some(post_id) => format!("{}:{}", user_id, post_id),
+            None => user_id.to_string(),
+        }
+    }
+
     /// Constructs the index for a sorted set in Redis based on the user key and an optional extra parameter.
     /// # Arguments
     /// * user_id - The key of the user.
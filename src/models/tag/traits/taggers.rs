@@ -1,3 +1,4 @@
+use crate::models::tag::label::canonical_label;
 use crate::routes::v0::types::TaggersInfo;
 use crate::types::{DynError, Pagination};
 use crate::RedisOps;
@@ -43,14 +44,19 @@ where
         // Set default params for pagination
         let skip = pagination.skip.unwrap_or(0);
         let limit = pagination.limit.unwrap_or(40);
+        let label = &canonical_label(label).await?;
         let mut prefix = None;
         let key_parts;
-        // Get WoT tags. If we do not first hit the graph using `TagUser::get_by_id` function
+        // Get WoT tags. If we do not first hit the graph using `TagUser::get_by_id` or `TagPost::get_by_id` function
         // for example using, user/{user_id}/tags?viewer_id={viewer_id}&depth={distance} endpoint
         // we get empty array because it was not cached the WoT tags
-        if viewer_id.is_some() && depth.is_some() && extra_param.is_none() {
+        if let (Some(viewer), Some(_)) = (viewer_id, depth) {
             prefix = Some(CACHE_SET_PREFIX.to_string());
-            key_parts = Self::create_label_index(user_id, viewer_id, label, true);
+            key_parts = match extra_param {
+                // The WoT cache of a post is identified by its author and post ids
+                Some(post_id) => vec![viewer, user_id, post_id, label],
+                None => Self::create_label_index(user_id, viewer_id, label, true),
+            };
         } else {
             key_parts = Self::create_label_index(user_id, extra_param, label, false);
         }
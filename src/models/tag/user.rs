@@ -1,4 +1,13 @@
-use crate::RedisOps;
+use crate::db::kv::index::json::JsonAction;
+use crate::models::tag::history::TagHistory;
+use crate::models::tag::label::TagDisplay;
+use crate::models::tag::list::UserList;
+use crate::models::tag::related::RelatedTags;
+use crate::models::tag::search::UserTagSearch;
+use crate::models::tag::TaggedType;
+use crate::models::user::UserCounts;
+use crate::types::DynError;
+use crate::{RedisOps, ScoreAction};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
@@ -30,3 +39,137 @@ impl TagCollection for TagUser {
 }
 
 impl TaggersCollection for TagUser {}
+
+impl TagUser {
+    /// Adds a new user tag to the indexes and updates the related counts
+    /// # Arguments
+    /// - `tagger_id` - The user tagging the user.
+    /// - `tagged_id` - The tagged user.
+    /// - `tag_label` - The canonical label of the tag.
+    /// - `original_label` - The label as written by the tagger.
+    /// - `indexed_at` - The time the tag was indexed.
+    pub async fn put_to_indexes(
+        tagger_id: &str,
+        tagged_id: &str,
+        tag_label: &str,
+        original_label: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let indexing_results = tokio::join!(
+            // Update user counts for the tagged user
+            UserCounts::update(tagged_id, "tags", JsonAction::Increment(1), None),
+            // Update user counts for the tagger user
+            UserCounts::update(tagger_id, "tagged", JsonAction::Increment(1), None),
+            async {
+                // Increase unique_tags if the tag does not exist already
+                // NOTE: To update that field, it cannot exist in TagUser SORTED SET the tag. Thats why it has to be executed
+                // before TagUser operation
+                UserCounts::update(
+                    tagged_id,
+                    "unique_tags",
+                    JsonAction::Increment(1),
+                    Some(tag_label),
+                )
+                .await?;
+                // Add label count to the user profile tag
+                Self::update_index_score(tagged_id, None, tag_label, ScoreAction::Increment(1.0))
+                    .await?;
+                // NOTE: The co-occurrences depend on the updated label count of the user
+                RelatedTags::put_to_index(TaggedType::User, &[tagged_id], tag_label).await?;
+                Ok::<(), DynError>(())
+            },
+            // Add tagger to the user taggers list
+            Self::add_tagger_to_index(tagged_id, None, tagger_id, tag_label),
+            // Add the tagged user to the tagger list of the label
+            UserList::add_member(tagger_id, tag_label, tagged_id),
+            // Add the tag to the user search by label
+            UserTagSearch::update_index_score(tagged_id, tag_label, ScoreAction::Increment(1.0)),
+            // Count the tag in the label history
+            TagHistory::put_to_index(TaggedType::User, tag_label, tagger_id, indexed_at),
+            // Count the spelling of the label written by the tagger
+            TagDisplay::put_to_index(tag_label, original_label)
+        );
+
+        for result in [
+            indexing_results.0,
+            indexing_results.1,
+            indexing_results.2,
+            indexing_results.3,
+            indexing_results.4,
+            indexing_results.5,
+            indexing_results.6,
+            indexing_results.7,
+        ] {
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Removes a deleted user tag from the indexes and updates the related counts
+    /// # Arguments
+    /// - `tagger_id` - The user that tagged the user.
+    /// - `tagged_id` - The tagged user.
+    /// - `tag_label` - The canonical label of the tag.
+    /// - `original_label` - The label as written by the tagger.
+    /// - `indexed_at` - The time the tag was indexed.
+    pub async fn del_from_indexes(
+        tagger_id: &str,
+        tagged_id: &str,
+        tag_label: &str,
+        original_label: &str,
+        indexed_at: i64,
+    ) -> Result<(), DynError> {
+        let indexing_results = tokio::join!(
+            // Update user counts in the tagged
+            UserCounts::update(tagged_id, "tags", JsonAction::Decrement(1), None),
+            // Update user counts in the tagger
+            UserCounts::update(tagger_id, "tagged", JsonAction::Decrement(1), None),
+            async {
+                // Decrement label count to the user profile tag
+                Self::update_index_score(tagged_id, None, tag_label, ScoreAction::Decrement(1.0))
+                    .await?;
+                // NOTE: The co-occurrences depend on the updated label count of the user
+                RelatedTags::del_from_index(TaggedType::User, &[tagged_id], tag_label).await?;
+                // Decrease unique_tags
+                // NOTE: To update that field, we first need to decrement the value in the TagUser SORTED SET associated with that tag
+                UserCounts::update(
+                    tagged_id,
+                    "unique_tags",
+                    JsonAction::Decrement(1),
+                    Some(tag_label),
+                )
+                .await?;
+                Ok::<(), DynError>(())
+            },
+            async {
+                // Remove tagger to the user taggers list
+                Self(vec![tagger_id.to_string()])
+                    .del_from_index(tagged_id, None, tag_label)
+                    .await?;
+                Ok::<(), DynError>(())
+            },
+            // Remove the tagged user from the tagger list of the label
+            UserList::del_member(tagger_id, tag_label, tagged_id),
+            // Remove the tag from the user search by label
+            UserTagSearch::update_index_score(tagged_id, tag_label, ScoreAction::Decrement(1.0)),
+            // Discount the tag from the label history
+            TagHistory::del_from_index(TaggedType::User, tag_label, tagger_id, indexed_at),
+            // Discount the spelling of the label written by the tagger
+            TagDisplay::del_from_index(tag_label, original_label)
+        );
+
+        for result in [
+            indexing_results.0,
+            indexing_results.1,
+            indexing_results.2,
+            indexing_results.3,
+            indexing_results.4,
+            indexing_results.5,
+            indexing_results.6,
+            indexing_results.7,
+        ] {
+            result?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use super::pagerank::pagerank;
+use super::personalized::TAG_EDGE_WEIGHT;
+use crate::db::connectors::neo4j::get_neo4j_graph;
+use crate::db::graph::exec::exec_single_row;
+use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::types::DynError;
+use crate::{queries, RedisOps};
+use log::warn;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+pub const USER_REPUTATION_KEY_PARTS: [&str; 2] = ["Users", "Reputation"];
+
+/// Global reputation of a user, the EigenTrust score over the follow and tag graph seeded from the
+/// trusted keys of the instance
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct UserReputation {
+    pub user_id: String,
+    /// Normalized so the average user has a reputation of `1.0`. Users unreachable from the
+    /// trusted keys, such as sybil clusters, have no reputation
+    pub score: f64,
+}
+
+impl RedisOps for UserReputation {}
+
+impl UserReputation {
+    /// Recomputes the reputation of all the users and replaces the `Users:Reputation` sorted set and
+    /// the `reputation` property of the users in the graph
+    ///
+    /// # Arguments
+    /// * `seeds` - The trusted user IDs the reputation flows from. Without seeds, every user is
+    ///   trusted the same and the reputation is the classic PageRank
+    ///
+    /// # Returns
+    /// The number of users with reputation
+    pub async fn refresh(seeds: &[String]) -> Result<usize, DynError> {
+        if seeds.is_empty() {
+            warn!("No reputation seeds configured, the reputation is not sybil resistant");
+        }
+        let reputation = Self::compute(seeds).await?;
+
+        let sorted_set: Vec<(f64, &str)> = reputation
+            .iter()
+            .map(|(user_id, score)| (*score, user_id.as_str()))
+            .collect();
+        Self::replace_index_sorted_set(&USER_REPUTATION_KEY_PARTS, &sorted_set, None).await?;
+
+        let (user_ids, scores): (Vec<String>, Vec<f64>) = reputation.into_iter().unzip();
+        let count = user_ids.len();
+        exec_single_row(queries::put::set_users_reputation(user_ids, scores)).await?;
+        Ok(count)
+    }
+
+    /// Computes the reputation of all the users from the graph without storing it. The users
+    /// without reputation are left out
+    ///
+    /// # Arguments
+    /// * `seeds` - The trusted user IDs the reputation flows from
+    pub async fn compute(seeds: &[String]) -> Result<HashMap<String, f64>, DynError> {
+        let edges = Self::get_edges().await?;
+        let seeds: Vec<&str> = seeds.iter().map(|seed| seed.as_str()).collect();
+        Ok(Self::normalize(pagerank(&seeds, &edges)))
+    }
+
+    /// Retrieves the reputation of a user, `None` if the user has no reputation
+    pub async fn get_by_id(user_id: &str) -> Result<Option<f64>, DynError> {
+        Ok(Self::get_scores(&[user_id]).await?.pop().flatten())
+    }
+
+    /// Retrieves the reputation of multiple users, in the same order as the user IDs
+    pub async fn get_scores(user_ids: &[&str]) -> Result<Vec<Option<f64>>, DynError> {
+        Self::get_index_sorted_set_scores(None, &USER_REPUTATION_KEY_PARTS, user_ids).await
+    }
+
+    /// Retrieves the users ranked by reputation, the most reputable first
+    pub async fn get_ranking(
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Option<Vec<String>>, DynError> {
+        Ok(Self::try_from_index_sorted_set(
+            &USER_REPUTATION_KEY_PARTS,
+            None,
+            None,
+            skip,
+            limit,
+            SortOrder::Descending,
+            None,
+        )
+        .await?
+        .map(|set| set.into_iter().map(|(user_id, _)| user_id).collect()))
+    }
+
+    /// Sorts the user IDs by reputation, the most reputable first. The users without reputation
+    /// keep their relative order at the end
+    pub async fn rank_user_ids(user_ids: &mut [String]) -> Result<(), DynError> {
+        let ids: Vec<&str> = user_ids.iter().map(|id| id.as_str()).collect();
+        let scores: HashMap<String, f64> = Self::get_scores(&ids)
+            .await?
+            .into_iter()
+            .zip(user_ids.iter())
+            .filter_map(|(score, user_id)| score.map(|score| (user_id.clone(), score)))
+            .collect();
+        let score_of = |id: &String| scores.get(id).copied().unwrap_or(0.0);
+        user_ids.sort_by(|a, b| score_of(b).total_cmp(&score_of(a)));
+        Ok(())
+    }
+
+    async fn get_edges() -> Result<Vec<(String, String, f64)>, DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::global_trust_edges(TAG_EDGE_WEIGHT);
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut edges = Vec::new();
+        while let Some(row) = result.next().await? {
+            let source: String = row.get("source")?;
+            let target: String = row.get("target")?;
+            let weight: f64 = row.get("weight")?;
+            edges.push((source, target, weight));
+        }
+        Ok(edges)
+    }
+
+    /// Scales the PageRank probabilities so the average reputation is `1.0`, regardless of the
+    /// size of the network
+    fn normalize(scores: HashMap<String, f64>) -> HashMap<String, f64> {
+        let users = scores.len() as f64;
+        scores
+            .into_iter()
+            .map(|(user_id, score)| (user_id, score * users))
+            .collect()
+    }
+}
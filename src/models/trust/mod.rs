@@ -0,0 +1,6 @@
+pub mod global;
+pub mod pagerank;
+pub mod personalized;
+
+pub use global::UserReputation;
+pub use personalized::{TrustScore, TrustScores};
@@ -0,0 +1,304 @@
+use super::pagerank::pagerank;
+use crate::db::connectors::neo4j::get_neo4j_graph;
+use crate::db::kv::index::sorted_sets::SortOrder;
+use crate::models::tag::traits::{TagCollection, TaggersCollection};
+use crate::models::tag::TagDetails;
+use crate::types::DynError;
+use crate::{queries, RedisOps};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Deref;
+use utoipa::ToSchema;
+
+pub const TRUST_PERSONALIZED_KEY_PARTS: [&str; 2] = ["Trust", "Personalized"];
+const CACHE_SORTED_SET_PREFIX: &str = "Cache:Sorted";
+// TTL, 3HR. Same as the WoT tags cache
+const TRUST_CACHE_TTL: i64 = 3 * 60 * 60;
+
+/// Follow distance explored from the viewer when the depth is not provided
+pub const DEFAULT_TRUST_DEPTH: u8 = 3;
+/// Weight of each label a user puts on another user, relative to a follow
+pub const TAG_EDGE_WEIGHT: f64 = 0.5;
+/// Only the most trusted users of a viewer are cached
+const MAX_TRUSTED_USERS: usize = 1000;
+/// Labels of a user or post weighted per request, the most tagged first
+const MAX_WEIGHTED_TAGS: usize = 1000;
+
+/// Trust of a viewer on a user of its network
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct TrustScore {
+    pub user_id: String,
+    /// Personalized PageRank of the user, the stationary probability of reaching it walking
+    /// the follow graph from the viewer
+    pub score: f64,
+}
+
+/// Trust scores of a viewer, the most trusted users first
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+pub struct TrustScores(pub Vec<TrustScore>);
+
+impl RedisOps for TrustScores {}
+
+impl Deref for TrustScores {
+    type Target = Vec<TrustScore>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl TrustScores {
+    /// Retrieves the trust scores of the viewer, computing and caching them on a cache miss
+    ///
+    /// # Arguments
+    /// * `viewer_id` - The user whose network is scored
+    /// * `depth` - Follow distance (1-3) explored from the viewer. Defaults to `DEFAULT_TRUST_DEPTH`
+    /// * `skip` - The number of users to skip for pagination
+    /// * `limit` - The maximum number of users to retrieve
+    pub async fn get_by_id(
+        viewer_id: &str,
+        depth: Option<u8>,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Option<Self>, DynError> {
+        let depth = Self::clamp_depth(depth);
+        let depth_str = depth.to_string();
+        let key_parts = Self::build_key_parts(viewer_id, &depth_str);
+
+        if let Some(scores) = Self::try_from_index_sorted_set(
+            &key_parts,
+            None,
+            None,
+            skip,
+            limit,
+            SortOrder::Descending,
+            Some(CACHE_SORTED_SET_PREFIX),
+        )
+        .await?
+        {
+            return Ok(Some(Self::from_sorted_set(scores)));
+        }
+
+        let scores = Self::compute(viewer_id, depth).await?;
+        if scores.is_empty() {
+            return Ok(None);
+        }
+        Self::put_to_index(&key_parts, &scores).await?;
+
+        let skip = skip.unwrap_or(0);
+        let limit = limit.unwrap_or(scores.len());
+        Ok(Some(Self(
+            scores.into_iter().skip(skip).take(limit).collect(),
+        )))
+    }
+
+    /// Retrieves all the cached trust scores of the viewer as a map from user ID to score.
+    /// Used to weight tags, recommendations and streams by the trust of the viewer
+    pub async fn get_map(
+        viewer_id: &str,
+        depth: Option<u8>,
+    ) -> Result<HashMap<String, f64>, DynError> {
+        Ok(Self::get_by_id(viewer_id, depth, None, None)
+            .await?
+            .map(|scores| {
+                scores
+                    .0
+                    .into_iter()
+                    .map(|trust| (trust.user_id, trust.score))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Retrieves a page of the tags of a user or post weighted by the trust of the viewer on their
+    /// taggers, the most trusted first. The tags are weighted before they are paginated
+    ///
+    /// # Arguments
+    /// * `viewer_id` - The user whose trust weights the tags
+    /// * `user_id` - The tagged user, or the author of the tagged post
+    /// * `extra_param` - The post ID for the tags of a post
+    /// * `skip_tags` - The number of tags to skip for pagination
+    /// * `limit_tags` - The maximum number of tags to retrieve. Defaults to `5`
+    /// * `limit_taggers` - The maximum number of taggers listed per tag
+    /// * `depth` - Follow distance (1-3) of the taggers counted in the tags of the viewer's network
+    pub async fn get_weighted_tags<T: TagCollection + TaggersCollection>(
+        viewer_id: &str,
+        user_id: &str,
+        extra_param: Option<&str>,
+        skip_tags: Option<usize>,
+        limit_tags: Option<usize>,
+        limit_taggers: Option<usize>,
+        depth: Option<u8>,
+    ) -> Result<Option<Vec<TagDetails>>, DynError> {
+        let mut tags = match T::get_by_id(
+            user_id,
+            extra_param,
+            None,
+            Some(MAX_WEIGHTED_TAGS),
+            limit_taggers,
+            Some(viewer_id),
+            depth,
+        )
+        .await?
+        {
+            Some(tags) => tags,
+            None => return Ok(None),
+        };
+        Self::weight_tags::<T>(viewer_id, user_id, extra_param, &mut tags, depth).await?;
+        Ok(Some(
+            tags.into_iter()
+                .skip(skip_tags.unwrap_or(0))
+                .take(limit_tags.unwrap_or(5))
+                .collect(),
+        ))
+    }
+
+    /// Weights the tags of a user or post by the trust of the viewer on their taggers and sorts them,
+    /// the most trusted first
+    ///
+    /// # Arguments
+    /// * `viewer_id` - The user whose trust weights the tags
+    /// * `user_id` - The tagged user, or the author of the tagged post
+    /// * `extra_param` - The post ID for the tags of a post
+    /// * `tags` - The tags to weight
+    /// * `depth` - Follow distance (1-3) explored from the viewer to weight the taggers
+    async fn weight_tags<T: TaggersCollection>(
+        viewer_id: &str,
+        user_id: &str,
+        extra_param: Option<&str>,
+        tags: &mut [TagDetails],
+        depth: Option<u8>,
+    ) -> Result<(), DynError> {
+        let trust = Self::get_map(viewer_id, depth).await?;
+        // The tag details only list the first taggers, all the taggers are read in a single pipeline
+        let keys: Vec<String> = tags
+            .iter()
+            .map(|tag| T::create_label_index(user_id, extra_param, &tag.label, false).join(":"))
+            .collect();
+        let keys: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let taggers_list = T::try_from_multiple_sets(&keys, None, None, None).await?;
+        for (tag, taggers) in tags.iter_mut().zip(taggers_list) {
+            let taggers = taggers.map(|(taggers, _, _)| taggers).unwrap_or_default();
+            tag.trust = Some(taggers.iter().filter_map(|tagger| trust.get(tagger)).sum());
+        }
+        tags.sort_by(|a, b| {
+            b.trust
+                .unwrap_or_default()
+                .total_cmp(&a.trust.unwrap_or_default())
+        });
+        Ok(())
+    }
+
+    /// Computes the personalized PageRank of the users within `depth` follows of the viewer
+    async fn compute(viewer_id: &str, depth: u8) -> Result<Vec<TrustScore>, DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::trust_network_edges(viewer_id, depth, TAG_EDGE_WEIGHT);
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut edges = Vec::new();
+        while let Some(row) = result.next().await? {
+            let source: String = row.get("source")?;
+            let target: String = row.get("target")?;
+            let weight: f64 = row.get("weight")?;
+            edges.push((source, target, weight));
+        }
+
+        let mut scores: Vec<TrustScore> = personalized_pagerank(viewer_id, &edges)
+            .into_iter()
+            .map(|(user_id, score)| TrustScore { user_id, score })
+            .collect();
+        scores.sort_by(|a, b| b.score.total_cmp(&a.score));
+        scores.truncate(MAX_TRUSTED_USERS);
+        Ok(scores)
+    }
+
+    async fn put_to_index(key_parts: &[&str], scores: &[TrustScore]) -> Result<(), DynError> {
+        let sorted_set: Vec<(f64, &str)> = scores
+            .iter()
+            .map(|trust| (trust.score, trust.user_id.as_str()))
+            .collect();
+        Self::put_index_sorted_set(
+            key_parts,
+            &sorted_set,
+            Some(CACHE_SORTED_SET_PREFIX),
+            Some(TRUST_CACHE_TTL),
+        )
+        .await
+    }
+
+    fn from_sorted_set(scores: Vec<(String, f64)>) -> Self {
+        Self(
+            scores
+                .into_iter()
+                .map(|(user_id, score)| TrustScore { user_id, score })
+                .collect(),
+        )
+    }
+
+    fn clamp_depth(depth: Option<u8>) -> u8 {
+        depth.unwrap_or(DEFAULT_TRUST_DEPTH).clamp(1, 3)
+    }
+
+    fn build_key_parts<'a>(viewer_id: &'a str, depth: &'a str) -> Vec<&'a str> {
+        [&TRUST_PERSONALIZED_KEY_PARTS[..], &[viewer_id, depth]].concat()
+    }
+}
+
+/// Personalized PageRank over weighted directed edges, the random walk restarts from the seed.
+/// The seed is not part of the result
+///
+/// # Arguments
+/// * `seed` - The user the walk starts from and jumps back to
+/// * `edges` - `(source, target, weight)` edges, the walk follows them proportionally to their weight
+pub fn personalized_pagerank(seed: &str, edges: &[(String, String, f64)]) -> HashMap<String, f64> {
+    let mut scores = pagerank(&[seed], edges);
+    scores.remove(seed);
+    scores
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(source: &str, target: &str, weight: f64) -> (String, String, f64) {
+        (source.to_string(), target.to_string(), weight)
+    }
+
+    #[test]
+    fn test_personalized_pagerank_decays_with_distance() {
+        let edges = vec![
+            edge("viewer", "friend", 1.0),
+            edge("friend", "friend_of_friend", 1.0),
+            edge("stranger", "friend", 1.0),
+        ];
+        let scores = personalized_pagerank("viewer", &edges);
+
+        assert!(scores["friend"] > scores["friend_of_friend"]);
+        assert!(scores["friend_of_friend"] > 0.0);
+        // Users that cannot be reached from the viewer are not trusted
+        assert!(!scores.contains_key("stranger"));
+        assert!(!scores.contains_key("viewer"));
+        assert!(scores.values().sum::<f64>() < 1.0);
+    }
+
+    #[test]
+    fn test_personalized_pagerank_follows_edge_weights() {
+        let edges = vec![
+            edge("viewer", "tagged_friend", 1.0 + TAG_EDGE_WEIGHT),
+            edge("viewer", "friend", 1.0),
+        ];
+        let scores = personalized_pagerank("viewer", &edges);
+
+        assert!(scores["tagged_friend"] > scores["friend"]);
+    }
+
+    #[test]
+    fn test_personalized_pagerank_without_edges() {
+        assert!(personalized_pagerank("viewer", &[]).is_empty());
+    }
+}
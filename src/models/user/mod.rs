@@ -1,6 +1,9 @@
 mod counts;
 mod details;
 mod muted;
+mod mutuals;
+mod path;
+mod recommendation;
 mod relationship;
 mod search;
 mod stream;
@@ -10,6 +13,12 @@ mod view;
 pub use counts::UserCounts;
 pub use details::UserDetails;
 pub use muted::Muted;
+pub use mutuals::MutualConnections;
+pub use path::FollowPaths;
+pub use recommendation::{
+    RecommendationReason, UserRecommendation, UserRecommendations,
+    CACHE_USER_RECOMMENDATIONS_PREFIX,
+};
 pub use relationship::Relationship;
 pub use search::{UserSearch, USER_NAME_KEY_PARTS};
 pub use stream::{
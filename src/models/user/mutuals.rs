@@ -0,0 +1,58 @@
+use crate::models::follow::{Followers, Following};
+use crate::types::DynError;
+use crate::RedisOps;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::UserCounts;
+
+/// Users the viewer follows that also follow the user, e.g. "followed by X, Y and 12 others you know"
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default, Clone, PartialEq)]
+pub struct MutualConnections {
+    /// Number of users the viewer follows that follow the user
+    pub count: usize,
+    /// The page of those users, sorted by ID
+    pub user_ids: Vec<String>,
+}
+
+impl MutualConnections {
+    /// Retrieves the mutual connections of the viewer and the user from the intersection of the
+    /// Following set of the viewer and the Followers set of the user.
+    ///
+    /// # Arguments
+    /// * `user_id` - The user whose followers are listed
+    /// * `viewer_id` - The user whose follows are listed
+    /// * `skip` - The number of users to skip for pagination
+    /// * `limit` - The maximum number of users to retrieve
+    pub async fn get_by_id(
+        user_id: &str,
+        viewer_id: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Option<Self>, DynError> {
+        let (user_exist, viewer_exist) = tokio::try_join!(
+            UserCounts::get_from_index(user_id),
+            UserCounts::get_from_index(viewer_id),
+        )?;
+
+        // Make sure users exist before get their connections
+        if user_exist.is_none() || viewer_exist.is_none() {
+            return Ok(None);
+        }
+
+        let followers_key = format!("{}:{}", Followers::prefix().await, user_id);
+        let mut user_ids =
+            Following::intersect_index_sets(&[viewer_id], None, &[followers_key]).await?;
+        // The members of an intersection come in no particular order
+        user_ids.sort();
+
+        let count = user_ids.len();
+        let user_ids = user_ids
+            .into_iter()
+            .skip(skip.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+
+        Ok(Some(Self { count, user_ids }))
+    }
+}
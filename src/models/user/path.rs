@@ -0,0 +1,88 @@
+use crate::db::connectors::neo4j::get_neo4j_graph;
+use crate::queries;
+use crate::types::DynError;
+use serde::{Deserialize, Serialize};
+use tokio::time::{timeout, Duration};
+use utoipa::ToSchema;
+
+use super::UserCounts;
+
+/// Follow distance explored from the viewer when the depth is not provided
+pub const DEFAULT_PATH_DEPTH: u8 = 4;
+const MAX_PATH_DEPTH: u8 = 6;
+/// Paths of the same length listed when the limit is not provided
+pub const DEFAULT_PATH_LIMIT: usize = 5;
+const MAX_PATH_LIMIT: usize = 20;
+/// The shortest paths between far away users of a dense graph are expensive to find
+const PATH_QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shortest follow paths from the viewer to the user, explaining how the viewer reaches the user
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default, Clone, PartialEq)]
+pub struct FollowPaths {
+    /// Follows from the viewer to the user, `None` when the user is not reached within the depth
+    pub degrees: Option<u8>,
+    /// The users in between the viewer and the user on each of the shortest paths, from the viewer side
+    pub paths: Vec<Vec<String>>,
+}
+
+impl FollowPaths {
+    /// Retrieves the shortest follow paths from the viewer to the user
+    ///
+    /// # Arguments
+    /// * `user_id` - The user the follows reach
+    /// * `viewer_id` - The user the follows start from
+    /// * `depth` - The maximum number of follows (1-6) of a path. Defaults to `DEFAULT_PATH_DEPTH`
+    /// * `limit` - The maximum number of paths (1-20) to retrieve. Defaults to `DEFAULT_PATH_LIMIT`
+    pub async fn get_by_id(
+        user_id: &str,
+        viewer_id: &str,
+        depth: Option<u8>,
+        limit: Option<usize>,
+    ) -> Result<Option<Self>, DynError> {
+        let (user_exist, viewer_exist) = tokio::try_join!(
+            UserCounts::get_from_index(user_id),
+            UserCounts::get_from_index(viewer_id),
+        )?;
+
+        // Make sure users exist before looking for the paths between them
+        if user_exist.is_none() || viewer_exist.is_none() {
+            return Ok(None);
+        }
+
+        // The shortest path cannot start and end in the same user
+        if user_id == viewer_id {
+            return Ok(Some(Self {
+                degrees: Some(0),
+                paths: vec![],
+            }));
+        }
+
+        let depth = depth.unwrap_or(DEFAULT_PATH_DEPTH).clamp(1, MAX_PATH_DEPTH);
+        let limit = limit.unwrap_or(DEFAULT_PATH_LIMIT).clamp(1, MAX_PATH_LIMIT);
+
+        // The timeout covers the rows streamed after the query starts, not only its execution
+        let fetch = async {
+            let mut result;
+            {
+                let graph = get_neo4j_graph()?;
+                let query = queries::get::shortest_follow_paths(viewer_id, user_id, depth, limit);
+
+                let graph = graph.lock().await;
+                result = graph.execute(query).await?;
+            }
+
+            let mut follow_paths = Self::default();
+            while let Some(row) = result.next().await? {
+                let degrees: i64 = row.get("degrees")?;
+                follow_paths.degrees = Some(degrees as u8);
+                follow_paths.paths.push(row.get("user_ids")?);
+            }
+            Ok::<Self, DynError>(follow_paths)
+        };
+
+        match timeout(PATH_QUERY_TIMEOUT, fetch).await {
+            Ok(follow_paths) => Ok(Some(follow_paths?)),
+            Err(_) => Err("Query timed out".into()),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::models::trust::UserReputation;
+use crate::types::DynError;
+use crate::{get_neo4j_graph, queries, RedisOps};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const CACHE_USER_RECOMMENDATIONS_PREFIX: &str = "Cache:Users:Recommendations";
+// TTL, 12HR. Dropped earlier when the user follows or mutes someone
+const CACHE_USER_RECOMMENDATIONS_TTL: i64 = 12 * 60 * 60;
+/// Candidates ranked and cached per user
+const USER_RECOMMENDED_CANDIDATES: usize = 100;
+/// Candidates retrieved from the graph, the reputation chooses the ones cached among them
+const USER_RECOMMENDATION_POOL: usize = 10 * USER_RECOMMENDED_CANDIDATES;
+/// Labels of the user the candidates tagging with the same labels are looked up by, the most used first
+const RECOMMENDATION_LABELS: usize = 20;
+/// Candidates looked up by each label of the user
+const RECOMMENDATION_CANDIDATES_PER_LABEL: usize = 100;
+/// Only the users that posted at least this many posts are recommended
+const RECOMMENDATION_MIN_POSTS: usize = 5;
+/// Weight of each label both users tag with, relative to a mutual follow
+const COMMON_TAG_WEIGHT: f64 = 0.5;
+
+/// Why a user is recommended
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Default, PartialEq)]
+pub struct RecommendationReason {
+    /// Users followed by the user that follow the recommended user
+    pub mutual_follows: usize,
+    /// Labels both the user and the recommended user tag with
+    pub common_tags: Vec<String>,
+    /// Global reputation of the recommended user, `None` if it has no reputation
+    pub reputation: Option<f64>,
+}
+
+/// A recommended user with the reason it is recommended
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, PartialEq)]
+pub struct UserRecommendation {
+    pub user_id: String,
+    /// One per mutual follow, half per common tag plus `ln(1 + reputation)`
+    pub score: f64,
+    pub reason: RecommendationReason,
+}
+
+/// Users recommended to a user, the best first. The active friends of friends and users that tag with the
+/// same labels are ranked by their mutual follows, their common tags and their reputation. The users already
+/// followed or muted are left out
+#[derive(Serialize, Deserialize, ToSchema, Debug, Default)]
+pub struct UserRecommendations(pub Vec<UserRecommendation>);
+
+impl RedisOps for UserRecommendations {}
+
+impl UserRecommendations {
+    /// Retrieves the recommendations of the user, computing and caching them on a cache miss
+    ///
+    /// # Arguments
+    /// * `user_id` - The user the users are recommended to
+    /// * `skip` - The number of users to skip for pagination
+    /// * `limit` - The maximum number of users to retrieve
+    pub async fn get_by_id(
+        user_id: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Option<Self>, DynError> {
+        let prefix = Some(CACHE_USER_RECOMMENDATIONS_PREFIX.to_string());
+        let recommendations = match Self::try_from_index_json(&[user_id], prefix.clone()).await? {
+            Some(recommendations) => recommendations,
+            None => {
+                let recommendations = Self::compute(user_id).await?;
+                recommendations
+                    .put_index_json(&[user_id], prefix, Some(CACHE_USER_RECOMMENDATIONS_TTL))
+                    .await?;
+                recommendations
+            }
+        };
+
+        let recommendations: Vec<UserRecommendation> = recommendations
+            .0
+            .into_iter()
+            .skip(skip.unwrap_or(0))
+            .take(limit.unwrap_or(usize::MAX))
+            .collect();
+        match recommendations.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(Self(recommendations))),
+        }
+    }
+
+    /// Drops the cached recommendations of the user, e.g. after following someone new
+    pub async fn del_cache(user_id: &str) -> Result<(), DynError> {
+        Self::remove_from_prefixed_index_multiple_json(
+            &[&[user_id]],
+            CACHE_USER_RECOMMENDATIONS_PREFIX,
+        )
+        .await
+    }
+
+    async fn compute(user_id: &str) -> Result<Self, DynError> {
+        let mut result;
+        {
+            let graph = get_neo4j_graph()?;
+            let query = queries::get::recommend_users(
+                user_id,
+                RECOMMENDATION_LABELS,
+                RECOMMENDATION_CANDIDATES_PER_LABEL,
+                RECOMMENDATION_MIN_POSTS,
+                USER_RECOMMENDATION_POOL,
+            );
+
+            let graph = graph.lock().await;
+            result = graph.execute(query).await?;
+        }
+
+        let mut recommendations = Vec::new();
+        while let Some(row) = result.next().await? {
+            let mutual_follows: i64 = row.get("mutual_follows")?;
+            recommendations.push(UserRecommendation {
+                user_id: row.get("user_id")?,
+                score: 0.0,
+                reason: RecommendationReason {
+                    mutual_follows: mutual_follows as usize,
+                    common_tags: row.get("common_tags")?,
+                    reputation: None,
+                },
+            });
+        }
+
+        let user_ids: Vec<&str> = recommendations
+            .iter()
+            .map(|recommendation| recommendation.user_id.as_str())
+            .collect();
+        let reputations = match user_ids.is_empty() {
+            true => vec![],
+            false => UserReputation::get_scores(&user_ids).await?,
+        };
+        for (recommendation, reputation) in recommendations.iter_mut().zip(reputations) {
+            let reason = &mut recommendation.reason;
+            reason.reputation = reputation;
+            recommendation.score = reason.mutual_follows as f64
+                + COMMON_TAG_WEIGHT * reason.common_tags.len() as f64
+                + reputation.unwrap_or(0.0).ln_1p();
+        }
+        recommendations.sort_by(|a, b| b.score.total_cmp(&a.score));
+        recommendations.truncate(USER_RECOMMENDED_CANDIDATES);
+        Ok(Self(recommendations))
+    }
+}
@@ -1,10 +1,13 @@
 use super::UserDetails;
+use crate::models::trust::UserReputation;
 use crate::RedisOps;
 use crate::{models::traits::Collection, types::DynError};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 pub const USER_NAME_KEY_PARTS: [&str; 2] = ["Users", "Name"];
+/// Users matched by name that are ranked by reputation, the first ones by username
+const MAX_REPUTATION_RANKED_USERS: usize = 1000;
 
 #[derive(Serialize, Deserialize, ToSchema, Default)]
 pub struct UserSearch(pub Vec<String>);
@@ -38,6 +41,31 @@ impl UserSearch {
         Ok(None)
     }
 
+    /// Retrieves the users whose username starts with `name`, the most reputable first. The users
+    /// are ranked before they are paginated
+    pub async fn get_by_name_reputation_ranked(
+        name: &str,
+        skip: Option<usize>,
+        limit: Option<usize>,
+    ) -> Result<Option<Self>, DynError> {
+        let mut user_search =
+            match Self::get_by_name(name, None, Some(MAX_REPUTATION_RANKED_USERS)).await? {
+                Some(user_search) => user_search,
+                None => return Ok(None),
+            };
+        UserReputation::rank_user_ids(&mut user_search.0).await?;
+        let user_ids: Vec<String> = user_search
+            .0
+            .into_iter()
+            .skip(skip.unwrap_or(0))
+            .take(limit.unwrap_or(MAX_REPUTATION_RANKED_USERS))
+            .collect();
+        match user_ids.is_empty() {
+            true => Ok(None),
+            false => Ok(Some(Self(user_ids))),
+        }
+    }
+
     pub async fn get_from_index(
         name: &str,
         skip: Option<usize>,
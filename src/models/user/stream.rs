@@ -1,17 +1,16 @@
-use super::{Muted, UserCounts, UserSearch, UserView};
+use super::{Muted, UserCounts, UserRecommendations, UserSearch, UserView};
 use crate::models::follow::{Followers, Following, Friends, UserFollows};
+use crate::models::trust::{TrustScores, UserReputation};
 use crate::types::DynError;
 use crate::{db::kv::index::sorted_sets::SortOrder, RedisOps};
-use crate::{get_neo4j_graph, queries};
 use serde::{Deserialize, Serialize};
 use tokio::task::spawn;
 use utoipa::ToSchema;
 
 pub const USER_MOSTFOLLOWED_KEY_PARTS: [&str; 2] = ["Users", "MostFollowed"];
 pub const USER_PIONEERS_KEY_PARTS: [&str; 2] = ["Users", "Pioneers"];
-pub const CACHE_USER_RECOMMENDED_KEY_PARTS: [&str; 3] = ["Cache", "Users", "Recommended"];
-// TTL, 12HR
-pub const CACHE_USER_RECOMMENDED_TTL: i64 = 12 * 60 * 60;
+// Number of recommended users reordered by trust
+const USER_RECOMMENDED_CANDIDATES: usize = 30;
 
 #[derive(Deserialize, ToSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
@@ -22,6 +21,7 @@ pub enum UserStreamSource {
     Muted,
     MostFollowed,
     Pioneers,
+    MostReputable,
     Recommended,
 }
 
@@ -119,72 +119,47 @@ impl UserStream {
         let score = (counts.tagged + counts.posts) as f64 * (counts.followers as f64).sqrt();
         Self::put_index_sorted_set(&USER_PIONEERS_KEY_PARTS, &[(score, user_id)], None, None).await
     }
-    /// Retrieves recommended user IDs based on the specified criteria.
-    async fn get_recommended_ids(
+    /// Retrieves the recommended users of `user_id` ranked by its trust scores instead of their recommendation score
+    pub async fn get_trust_weighted_recommended(
         user_id: &str,
+        viewer_id: Option<&str>,
+        skip: Option<usize>,
         limit: Option<usize>,
-    ) -> Result<Option<Vec<String>>, DynError> {
-        let count = limit.unwrap_or(5) as isize;
-
-        // Attempt to get cached data from Redis
-        if let Some(cached_data) = Self::try_get_cached_recommended(user_id, count).await? {
-            return Ok(Some(cached_data));
-        }
-
-        // Cache miss; proceed to query Neo4j
-        let mut result;
-        {
-            let graph = get_neo4j_graph()?;
-            // Query Neo4j for 30 user IDs
-            let query = queries::get::recommend_users(user_id, 30);
-
-            let graph = graph.lock().await;
-            result = graph.execute(query).await?;
-        }
-
-        let mut user_ids = Vec::new();
-
-        while let Some(row) = result.next().await? {
-            if let Some(user_id) = row.get::<Option<String>>("recommended_user_id")? {
-                user_ids.push(user_id);
-            }
-        }
-
-        if user_ids.is_empty() {
-            Ok(None)
-        } else {
-            Self::cache_recommended_users(user_id, &user_ids).await?;
-            if let Some(limit) = limit {
-                user_ids.truncate(limit);
+        depth: Option<u8>,
+    ) -> Result<Option<Self>, DynError> {
+        let mut user_ids =
+            match Self::get_recommended_ids(user_id, None, Some(USER_RECOMMENDED_CANDIDATES))
+                .await?
+            {
+                Some(user_ids) => user_ids,
+                None => return Ok(None),
             };
-            Ok(Some(user_ids))
-        }
+        let trust = TrustScores::get_map(user_id, None).await?;
+        let trust_of = |id: &String| trust.get(id).copied().unwrap_or(0.0);
+        user_ids.sort_by(|a, b| trust_of(b).total_cmp(&trust_of(a)));
+        let user_ids: Vec<String> = user_ids
+            .into_iter()
+            .skip(skip.unwrap_or(0))
+            .take(limit.unwrap_or(5))
+            .collect();
+        Self::from_listed_user_ids(&user_ids, viewer_id, depth).await
     }
 
-    async fn try_get_cached_recommended(
+    /// Retrieves the IDs of the users recommended to `user_id`, the best first
+    async fn get_recommended_ids(
         user_id: &str,
-        count: isize,
+        skip: Option<usize>,
+        limit: Option<usize>,
     ) -> Result<Option<Vec<String>>, DynError> {
-        let key_parts = &["Cache", "Recommended", user_id];
-        Self::try_get_random_from_index_set(
-            key_parts,
-            count,
-            Some(CACHE_USER_RECOMMENDED_KEY_PARTS.join(":")),
-        )
-        .await
-    }
-
-    /// Helper method to cache recommended users in Redis with a TTL.
-    async fn cache_recommended_users(user_id: &str, user_ids: &[String]) -> Result<(), DynError> {
-        let values: Vec<&str> = user_ids.iter().map(|s| s.as_str()).collect();
-        // Cache the result in Redis with a TTL of 12 hours
-        Self::put_index_set(
-            &[user_id],
-            &values,
-            Some(CACHE_USER_RECOMMENDED_TTL),
-            Some(CACHE_USER_RECOMMENDED_KEY_PARTS.join(":")),
-        )
-        .await
+        let recommendations =
+            UserRecommendations::get_by_id(user_id, skip, Some(limit.unwrap_or(5))).await?;
+        Ok(recommendations.map(|recommendations| {
+            recommendations
+                .0
+                .into_iter()
+                .map(|recommendation| recommendation.user_id)
+                .collect()
+        }))
     }
 
     // Get list of users based on the specified reach type
@@ -248,11 +223,13 @@ impl UserStream {
             )
             .await?
             .map(|set| set.into_iter().map(|(user_id, _score)| user_id).collect()),
+            UserStreamSource::MostReputable => UserReputation::get_ranking(skip, limit).await?,
             UserStreamSource::Recommended => {
                 UserStream::get_recommended_ids(
                     user_id.ok_or(
                         "User ID should be provided for user streams with source 'recommended'",
                     )?,
+                    skip,
                     limit,
                 )
                 .await?
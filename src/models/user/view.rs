@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::{Relationship, UserCounts, UserDetails};
+use super::{MutualConnections, Relationship, UserCounts, UserDetails};
 use crate::models::tag::traits::TagCollection;
 use crate::models::tag::user::TagUser;
 use crate::models::tag::TagDetails;
@@ -14,6 +14,9 @@ pub struct UserView {
     pub counts: UserCounts,
     pub tags: Vec<TagDetails>,
     pub relationship: Relationship,
+    /// Users the viewer follows that follow the user, only present when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mutuals: Option<MutualConnections>,
 }
 
 impl UserView {
@@ -45,6 +48,7 @@ impl UserView {
             counts,
             relationship,
             tags,
+            mutuals: None,
         }))
     }
 }
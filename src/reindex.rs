@@ -1,8 +1,11 @@
 use crate::db::kv::flush::clear_redis;
 use crate::models::follow::{Followers, Following, UserFollows};
-use crate::models::post::Bookmark;
+use crate::models::post::{Bookmark, HomeTimeline, PostStream};
+use crate::models::tag::history::TagHistory;
+use crate::models::tag::list::UserList;
 use crate::models::tag::post::TagPost;
-use crate::models::tag::search::TagSearch;
+use crate::models::tag::related::RelatedTags;
+use crate::models::tag::search::{TagSearch, UserTagSearch};
 use crate::models::tag::stream::HotTags;
 use crate::models::tag::traits::TagCollection;
 use crate::models::tag::user::TagUser;
@@ -36,7 +39,7 @@ pub async fn reindex() {
         .expect("Failed indexing User Details");
     //TODO use collections for every other model
 
-    for user_id in user_ids {
+    for user_id in user_ids.clone() {
         user_tasks.spawn(async move {
             if let Err(e) = reindex_user(&user_id).await {
                 log::error!("Failed to reindex user {}: {:?}", user_id, e);
@@ -73,6 +76,39 @@ pub async fn reindex() {
         .await
         .expect("Failed to store the global post tags");
 
+    UserTagSearch::reindex()
+        .await
+        .expect("Failed to store the global user tags");
+
+    RelatedTags::reindex()
+        .await
+        .expect("Failed to store the related tags");
+
+    TagHistory::reindex()
+        .await
+        .expect("Failed to store the tag history");
+
+    PostStream::reindex_engagement_windows()
+        .await
+        .expect("Failed to store the post engagement of the current windows");
+
+    // The home timelines are built from the indexed posts of the followed users
+    if HomeTimeline::is_enabled() {
+        let mut home_timeline_tasks = JoinSet::new();
+        for user_id in user_ids {
+            home_timeline_tasks.spawn(async move {
+                if let Err(e) = HomeTimeline::reindex(&user_id).await {
+                    log::error!("Failed to reindex home timelines of {}: {:?}", user_id, e);
+                }
+            });
+        }
+        while let Some(res) = home_timeline_tasks.join_next().await {
+            if let Err(e) = res {
+                log::error!("Home timeline reindexing task failed: {:?}", e);
+            }
+        }
+    }
+
     info!("Reindexing completed successfully.");
 }
 
@@ -83,7 +119,9 @@ pub async fn reindex_user(user_id: &str) -> Result<(), DynError> {
         Followers::reindex(user_id),
         Following::reindex(user_id),
         Muted::reindex(user_id),
-        TagUser::reindex(user_id, None)
+        TagUser::reindex(user_id, None),
+        UserList::reindex(user_id),
+        PostStream::reindex_tagged(user_id)
     )?;
     Ok(())
 }
@@ -95,6 +133,9 @@ pub async fn reindex_post(author_id: &str, post_id: &str) -> Result<(), DynError
         PostRelationships::reindex(author_id, post_id),
         TagPost::reindex(author_id, Some(post_id))
     )?;
+    // The trending score is computed from the indexed counts, details and tags
+    PostStream::update_root_trending_score(author_id, post_id).await?;
+    PostStream::reindex_mentions(author_id, post_id).await?;
     Ok(())
 }
 
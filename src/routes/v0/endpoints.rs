@@ -10,6 +10,9 @@ pub const INFO_ROUTE: &str = concatcp!(VERSION_ROUTE, "/info");
 const USER_PREFIX: &str = concatcp!(VERSION_ROUTE, "/user");
 pub const USER_ROUTE: &str = concatcp!(USER_PREFIX, "/{user_id}");
 pub const RELATIONSHIP_ROUTE: &str = concatcp!(USER_ROUTE, "/relationship/{viewer_id}");
+pub const USER_MUTUALS_ROUTE: &str = concatcp!(USER_ROUTE, "/mutuals/{viewer_id}");
+pub const USER_PATH_ROUTE: &str = concatcp!(USER_ROUTE, "/path/{viewer_id}");
+pub const USER_RECOMMENDATIONS_ROUTE: &str = concatcp!(USER_ROUTE, "/recommendations");
 pub const USER_COUNTS_ROUTE: &str = concatcp!(USER_ROUTE, "/counts");
 pub const USER_DETAILS_ROUTE: &str = concatcp!(USER_ROUTE, "/details");
 pub const USER_TAGS_ROUTE: &str = concatcp!(USER_ROUTE, "/tags");
@@ -19,6 +22,7 @@ pub const USER_FOLLOWING_ROUTE: &str = concatcp!(USER_ROUTE, "/following");
 pub const USER_FRIENDS_ROUTE: &str = concatcp!(USER_ROUTE, "/friends");
 pub const USER_MUTED_ROUTE: &str = concatcp!(USER_ROUTE, "/muted");
 pub const USER_AVATAR_ROUTE: &str = concatcp!(USER_ROUTE, "/avatar");
+pub const USER_TRUST_ROUTE: &str = concatcp!(USER_ROUTE, "/trust");
 
 // -- POST endpoints --
 pub const POST_PREFIX: &str = concatcp!(VERSION_ROUTE, "/post");
@@ -47,6 +51,7 @@ pub const STREAM_TAGS_REACH_ROUTE: &str =
 // -- SEARCH endpoints --
 const SEARCH_PREFIX: &str = concatcp!(VERSION_ROUTE, "/search");
 pub const SEARCH_USERS_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/users");
+pub const SEARCH_USERS_BY_TAG_ROUTE: &str = concatcp!(SEARCH_USERS_ROUTE, "/tags/{label}");
 pub const SEARCH_TAGS_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/tags/{label}");
 
 // TAG endpoints
@@ -54,6 +59,8 @@ pub const SEARCH_TAGS_ROUTE: &str = concatcp!(SEARCH_PREFIX, "/tags/{label}");
 const TAG_PREFIX: &str = concatcp!(VERSION_ROUTE, "/tags");
 pub const TAGS_HOT_ROUTE: &str = concatcp!(TAG_PREFIX, "/hot");
 pub const TAG_TAGGERS_ROUTE: &str = concatcp!(TAG_PREFIX, "/taggers/{label}");
+pub const TAG_RELATED_ROUTE: &str = concatcp!(TAG_PREFIX, "/{label}/related");
+pub const TAG_HISTORY_ROUTE: &str = concatcp!(TAG_PREFIX, "/{label}/history");
 
 // FILE endpoints
 // Axum routes
@@ -1,6 +1,7 @@
 use crate::models::tag::post::TagPost;
 use crate::models::tag::traits::{TagCollection, TaggersCollection};
 use crate::models::tag::TagDetails;
+use crate::models::trust::TrustScores;
 use crate::routes::v0::endpoints::{POST_TAGGERS_ROUTE, POST_TAGS_ROUTE};
 use crate::routes::v0::types::TaggersInfo;
 use crate::routes::v0::user::tags::TaggersQuery;
@@ -23,8 +24,11 @@ use utoipa::OpenApi;
         ("skip_tags" = Option<usize>, Query, description = "Skip N tags. Defaults to `0`"),
         ("limit_tags" = Option<usize>, Query, description = "Upper limit on the number of tags for the posts. Defaults to `5`"),
         ("limit_taggers" = Option<usize>, Query, description = "Upper limit on the number of taggers per tag. Defaults to `5`"),
+        ("depth" = Option<usize>, Query, description = "Viewer trusted network depth, user following users distance. Numbers bigger than 3, will be ignored"),
+        ("trust_weighted" = Option<bool>, Query, description = "Weight and sort the tags by the trust of the viewer on their taggers. Requires `viewer_id`")
     ),
     responses(
+        (status = 400, description = "Invalid input"),
         (status = 404, description = "Post not found"),
         (status = 200, description = "Post tags", body = Vec<TagDetails>),
         (status = 500, description = "Internal server error")
@@ -35,20 +39,43 @@ pub async fn post_tags_handler(
     Query(query): Query<TagsQuery>,
 ) -> Result<Json<Vec<TagDetails>>> {
     info!(
-        "GET {POST_TAGS_ROUTE} author_id:{}, post_id: {}, skip_tags:{:?}, limit_tags:{:?}, limit_taggers:{:?}",
-        author_id, post_id, query.limit_tags, query.skip_tags, query.limit_taggers
+        "GET {POST_TAGS_ROUTE} author_id:{}, post_id: {}, skip_tags:{:?}, limit_tags:{:?}, limit_taggers:{:?}, viewer_id:{:?}, depth:{:?}",
+        author_id, post_id, query.limit_tags, query.skip_tags, query.limit_taggers, query.viewer_id, query.depth
     );
-    match TagPost::get_by_id(
-        &author_id,
-        Some(&post_id),
-        query.skip_tags,
-        query.limit_tags,
-        query.limit_taggers,
-        query.viewer_id.as_deref(),
-        None, // Avoid by default WoT tags in a Post
-    )
-    .await
-    {
+    let trust_weighted = query.trust_weighted.unwrap_or(false);
+    if trust_weighted && query.viewer_id.is_none() {
+        return Err(Error::InvalidInput {
+            message: String::from("viewer_id should be provided for trust weighted tags"),
+        });
+    }
+    let tags = match (trust_weighted, query.viewer_id.as_deref()) {
+        // The tags are weighted before they are paginated
+        (true, Some(viewer_id)) => {
+            TrustScores::get_weighted_tags::<TagPost>(
+                viewer_id,
+                &author_id,
+                Some(&post_id),
+                query.skip_tags,
+                query.limit_tags,
+                query.limit_taggers,
+                query.depth,
+            )
+            .await
+        }
+        _ => {
+            TagPost::get_by_id(
+                &author_id,
+                Some(&post_id),
+                query.skip_tags,
+                query.limit_tags,
+                query.limit_taggers,
+                query.viewer_id.as_deref(),
+                query.depth,
+            )
+            .await
+        }
+    };
+    match tags {
         Ok(Some(tags)) => Ok(Json(tags)),
         Ok(None) => Err(Error::PostNotFound { author_id, post_id }),
         Err(source) => Err(Error::InternalServerError { source }),
@@ -66,7 +93,8 @@ pub async fn post_tags_handler(
         ("post_id" = String, Path, description = "Post ID"),
         ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
         ("skip" = Option<usize>, Query, description = "Number of taggers to skip for pagination. Defaults to `0`"),
-        ("limit" = Option<usize>, Query, description = "Number of taggers to return for pagination. Defaults to `40`")
+        ("limit" = Option<usize>, Query, description = "Number of taggers to return for pagination. Defaults to `40`"),
+        ("depth" = Option<usize>, Query, description = "Viewer trusted network depth, user following users distance. Numbers bigger than 3, will be ignored")
     ),
     responses(
         (status = 200, description = "Post tags", body = TaggersInfo),
@@ -88,7 +116,7 @@ pub async fn post_taggers_handler(
         &label,
         taggers_query.pagination,
         taggers_query.tags_query.viewer_id.as_deref(),
-        None,
+        taggers_query.tags_query.depth,
     )
     .await
     {
@@ -19,7 +19,8 @@ use utoipa::OpenApi;
         ("post_id" = String, Path, description = "Post Crockford32 ID"),
         ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
         ("limit_tags" = Option<usize>, Query, description = "Upper limit on the number of tags for the post"),
-        ("limit_taggers" = Option<usize>, Query, description = "Upper limit on the number of taggers per tag")
+        ("limit_taggers" = Option<usize>, Query, description = "Upper limit on the number of taggers per tag"),
+        ("depth" = Option<usize>, Query, description = "Viewer trusted network depth for the tags, user following users distance. Numbers bigger than 3, will be ignored")
     ),
     responses(
         (status = 200, description = "Post", body = PostView),
@@ -32,20 +33,21 @@ pub async fn post_view_handler(
     Query(query): Query<TagsQuery>,
 ) -> Result<Json<PostView>> {
     info!(
-        "GET {POST_ROUTE} author_id:{}, post_id:{}, viewer_id:{}, limit_tags:{:?}, limit_taggers:{:?}",
+        "GET {POST_ROUTE} author_id:{}, post_id:{}, viewer_id:{}, limit_tags:{:?}, limit_taggers:{:?}, depth:{:?}",
         author_id,
         post_id,
         query.viewer_id.clone().unwrap_or_default(),
         query.limit_tags,
-        query.limit_taggers
+        query.limit_taggers,
+        query.depth
     );
-    // Avoid by default WoT tags in a Post. We could add as `depth` argument for that specific use case
     match PostView::get_by_id(
         &author_id,
         &post_id,
         query.viewer_id.as_deref(),
         query.limit_tags,
         query.limit_taggers,
+        query.depth,
     )
     .await
     {
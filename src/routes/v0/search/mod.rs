@@ -9,6 +9,7 @@ mod users;
 pub fn routes() -> Router {
     register_routes!(Router::new(),
         endpoints::SEARCH_USERS_ROUTE => users::search_users_handler,
+        endpoints::SEARCH_USERS_BY_TAG_ROUTE => users::search_users_by_tag_handler,
         endpoints::SEARCH_TAGS_ROUTE => tags::search_post_tags_handler
     )
 }
@@ -1,8 +1,10 @@
-use crate::models::user::UserSearch;
-use crate::routes::v0::endpoints::SEARCH_USERS_ROUTE;
+use crate::models::tag::search::{UserTagSearch, UserTagSorting};
+use crate::models::user::{UserSearch, UserStream};
+use crate::routes::v0::endpoints::{SEARCH_USERS_BY_TAG_ROUTE, SEARCH_USERS_ROUTE};
+use crate::routes::v0::types::{parse_string_to_bool, parse_string_to_u8};
 use crate::types::Pagination;
 use crate::{Error, Result};
-use axum::extract::Query;
+use axum::extract::{Path, Query};
 use axum::Json;
 use log::info;
 use serde::Deserialize;
@@ -13,6 +15,8 @@ pub struct SearchQuery {
     username: Option<String>,
     #[serde(flatten)]
     pagination: Pagination,
+    #[serde(default, deserialize_with = "parse_string_to_bool")]
+    reputation_ranked: Option<bool>,
 }
 
 #[utoipa::path(
@@ -23,7 +27,8 @@ pub struct SearchQuery {
     params(
         ("username" = Option<String>, Query, description = "Username to search for"),
         ("skip" = Option<usize>, Query, description = "Skip N results"),
-        ("limit" = Option<usize>, Query, description = "Limit the number of results")
+        ("limit" = Option<usize>, Query, description = "Limit the number of results"),
+        ("reputation_ranked" = Option<bool>, Query, description = "Sort the matched users by their global reputation instead of by username. Defaults to `false`")
     ),
     responses(
         (status = 200, description = "Search results", body = UserSearch),
@@ -47,7 +52,11 @@ pub async fn search_users_handler(Query(query): Query<SearchQuery>) -> Result<Js
     let skip = query.pagination.skip.unwrap_or(0);
     let limit = query.pagination.limit.unwrap_or(200);
 
-    match UserSearch::get_by_name(username, Some(skip), Some(limit)).await {
+    let user_search = match query.reputation_ranked.unwrap_or(false) {
+        true => UserSearch::get_by_name_reputation_ranked(username, Some(skip), Some(limit)).await,
+        false => UserSearch::get_by_name(username, Some(skip), Some(limit)).await,
+    };
+    match user_search {
         Ok(Some(user_search)) => Ok(Json(user_search)),
         Ok(None) => Err(Error::UserNotFound {
             user_id: username.clone(),
@@ -56,6 +65,80 @@ pub async fn search_users_handler(Query(query): Query<SearchQuery>) -> Result<Js
     }
 }
 
+#[derive(Deserialize, Debug)]
+pub struct SearchUsersByTagQuery {
+    sorting: Option<UserTagSorting>,
+    viewer_id: Option<String>,
+    #[serde(default, deserialize_with = "parse_string_to_u8")]
+    depth: Option<u8>,
+    #[serde(flatten)]
+    pagination: Pagination,
+}
+
+#[utoipa::path(
+    get,
+    path = SEARCH_USERS_BY_TAG_ROUTE,
+    description = "Search users by the tags applied to them",
+    tag = "Search",
+    params(
+        ("label" = String, Path, description = "Tag name"),
+        ("sorting" = Option<UserTagSorting>, Query, description = "Ranking of the users: tag_count, unique_taggers or wot. Defaults to `tag_count`"),
+        ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID. Required by the `wot` sorting"),
+        ("depth" = Option<usize>, Query, description = "Follow distance (1-3) of the taggers counted by the `wot` sorting. Defaults to `2`"),
+        ("skip" = Option<usize>, Query, description = "Skip N users. Defaults to `0`"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N users. Defaults to `20`")
+    ),
+    responses(
+        (status = 200, description = "Users tagged with the label", body = UserStream),
+        (status = 400, description = "Invalid input"),
+        (status = 404, description = "No users found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn search_users_by_tag_handler(
+    Path(label): Path<String>,
+    Query(query): Query<SearchUsersByTagQuery>,
+) -> Result<Json<UserStream>> {
+    info!(
+        "GET {SEARCH_USERS_BY_TAG_ROUTE} label:{}, query: {:?}",
+        label, query
+    );
+
+    let sorting = query.sorting.unwrap_or_default();
+    if sorting == UserTagSorting::Wot && query.viewer_id.is_none() {
+        return Err(Error::InvalidInput {
+            message: "The wot sorting requires a viewer_id".to_string(),
+        });
+    }
+
+    let skip = query.pagination.skip.unwrap_or(0);
+    let limit = query.pagination.limit.unwrap_or(20).min(100);
+
+    let user_ids = match UserTagSearch::get_by_label(
+        &label,
+        sorting,
+        query.viewer_id.as_deref(),
+        query.depth,
+        skip,
+        limit,
+    )
+    .await
+    {
+        Ok(Some(user_ids)) => user_ids,
+        Ok(None) => return Err(Error::UserNotFound { user_id: label }),
+        Err(source) => return Err(Error::InternalServerError { source }),
+    };
+
+    match UserStream::from_listed_user_ids(&user_ids.0, query.viewer_id.as_deref(), None).await {
+        Ok(Some(stream)) => Ok(Json(stream)),
+        Ok(None) => Err(Error::UserNotFound { user_id: label }),
+        Err(source) => Err(Error::InternalServerError { source }),
+    }
+}
+
 #[derive(OpenApi)]
-#[openapi(paths(search_users_handler), components(schemas(UserSearch)))]
+#[openapi(
+    paths(search_users_handler, search_users_by_tag_handler),
+    components(schemas(UserSearch, UserTagSorting))
+)]
 pub struct SearchUsersApiDocs;
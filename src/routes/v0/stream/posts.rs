@@ -1,7 +1,8 @@
 use crate::routes::v0::endpoints::STREAM_POSTS_ROUTE;
-use crate::types::StreamSorting;
+use crate::routes::v0::types::TimeframeQuery;
+use crate::types::{StreamSorting, Timeframe};
 use crate::{
-    models::post::{PostStream, StreamSource},
+    models::post::{PostStream, StreamSource, TagFilter},
     types::Pagination,
 };
 use crate::{Error, Result as AppResult};
@@ -23,7 +24,13 @@ pub struct PostStreamQuery {
     pub viewer_id: Option<String>,
     #[serde(default, deserialize_with = "deserialize_comma_separated")]
     pub tags: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_comma_separated")]
+    pub all_tags: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "deserialize_comma_separated")]
+    pub exclude_tags: Option<Vec<String>>,
     pub kind: Option<PubkyAppPostKind>,
+    #[serde(flatten)]
+    pub timeframe: TimeframeQuery,
 }
 
 impl PostStreamQuery {
@@ -32,6 +39,25 @@ impl PostStreamQuery {
         self.pagination.limit = Some(self.pagination.limit.unwrap_or(10).min(30));
         self.sorting.get_or_insert(StreamSorting::Timeline);
     }
+
+    /// Gathers the tags to include and exclude, `None` without tags
+    pub fn tag_filter(&mut self) -> Result<Option<TagFilter>, Error> {
+        let tags = TagFilter {
+            any: self.tags.take().unwrap_or_default(),
+            all: self.all_tags.take().unwrap_or_default(),
+            none: self.exclude_tags.take().unwrap_or_default(),
+        };
+        // Enforce maximum number of tags
+        if [&tags.any, &tags.all, &tags.none]
+            .iter()
+            .any(|labels| labels.len() > MAX_TAGS)
+        {
+            return Err(Error::InvalidInput {
+                message: format!("Too many tags provided; maximum allowed is {}", MAX_TAGS),
+            });
+        }
+        Ok(Some(tags).filter(|tags| !tags.is_empty()))
+    }
 }
 
 // Custom deserializer for comma-separated tags
@@ -56,14 +82,25 @@ where
     path = STREAM_POSTS_ROUTE,
     tag = "Stream",
     params(
-        ("source" = Option<StreamSource>, Query, description = "Source of posts for streams with viewer (following, followers, friends, bookmarks, replies, all)"),
+        ("source" = Option<StreamSource>, Query, description = "Source of posts for streams with viewer (following, followers, friends, network, bookmarks, replies, tag_list, mentions, tagged, recommended, all)"),
         ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
         ("observer_id" = Option<String>, Query, description = "Observer Pubky ID. The central point for streams with Reach"),
         ("author_id" = Option<String>, Query, description = "Filter posts by an specific author User ID"),
         ("post_id" = Option<String>, Query, description = "This parameter is needed when we want to retrieve the replies stream for a post"),
-        ("sorting" = Option<StreamSorting>, Query, description = "StreamSorting method"),
+        ("label" = Option<String>, Query, description = "Label of the list for the `tag_list` source, or the optional label of the `tagged` source"),
+        ("user_id" = Option<String>, Query, description = "Mentioned User ID for the `mentions` source"),
+        ("tagger_id" = Option<String>, Query, description = "Tagger User ID for the `tagged` source"),
+        ("include_friends" = Option<bool>, Query, description = "Also include the users listed by the friends of the observer in the `tag_list` source. Defaults to `false`"),
+        ("depth" = Option<u8>, Query, description = "Follow distance (1-3) of the users from the observer in the `network` source. Defaults to `2`"),
+        ("sorting" = Option<StreamSorting>, Query, description = "StreamSorting method. The `trust_weighted` sorting requires `viewer_id`"),
         ("tags" = Option<Vec<String>>, Query, description = "Filter by a list of comma-separated tags (max 5). E.g.,`&tags=dev,free,opensource`. Only posts matching at least one of the tags will be returned."),
+        ("all_tags" = Option<Vec<String>>, Query, description = "Filter by a list of comma-separated tags (max 5). Only posts matching all the tags will be returned. Can be combined with `tags`"),
+        ("exclude_tags" = Option<Vec<String>>, Query, description = "Filter by a list of comma-separated tags (max 5). Posts matching any of the tags will not be returned. Can be combined with `tags` and `all_tags`"),
         ("kind" = Option<PubkyAppPostKind>, Query, description = "Specifies the type of posts to retrieve: short, long, image, video, link and file"),
+        ("timeframe" = Option<Timeframe>, Query, description = "Only count the engagement created in this timeframe (today, this_month, all_time, last_24_hours, last_7_days, last_30_days). Requires `total_engagement` sorting. Defaults to `all_time`"),
+        ("from" = Option<i64>, Query, description = "Only count the engagement created after this Unix timestamp in milliseconds. Not compatible with `timeframe`"),
+        ("to" = Option<i64>, Query, description = "Only count the engagement created before this Unix timestamp in milliseconds. Defaults to now"),
+        ("timezone" = Option<String>, Query, description = "UTC offset where `today` and `this_month` start, e.g. `+02:00`. Defaults to UTC"),
         ("skip" = Option<usize>, Query, description = "Skip N posts"),
         ("limit" = Option<usize>, Query, description = "Retrieve N posts"),
         ("start" = Option<usize>, Query, description = "The start of the stream timeframe or score. Posts with a timestamp/score greater than this value will be excluded from the results"),
@@ -82,6 +119,15 @@ where
     - `post_replies`: Requires `author_id` and `post_id` to filter replies to a specific post.
     - `author`:  Requires  `author_id` to filter posts by a specific author.
     - `author_replies`:  Requires  `author_id` to filter replies by a specific author.
+    - `network`: Requires `observer_id` to retrieve the posts of the users within `depth` follows of the observer.
+      With `trust_weighted` sorting the trust of the viewer is explored as deep as the network.
+    - `tag_list`: Requires `observer_id` and `label` to retrieve the posts of the users the observer tagged with the label.
+    - `mentions`: Requires `user_id` to retrieve the posts of other users mentioning it.
+    - `recommended`: Requires `viewer_id` to retrieve the posts recommended to the viewer, blending the posts of its
+      network, the posts with the labels it tags with the most and the trending posts. The sorting is ignored and the
+      posts cannot be filtered by tags or kind.
+    - `tagged`: Requires `tagger_id` to retrieve the posts tagged by the user, optionally only the ones tagged with `label`.
+      With `timeline` sorting the posts are sorted by the time they were tagged.
     
     Ensure that you provide the necessary parameters based on the selected `source`. If the required parameter is not
     provided, the provided `source` will be ignored and the stream type will default to `all`"
@@ -93,25 +139,47 @@ pub async fn stream_posts_handler(
 
     query.initialize_defaults();
 
-    // Enforce maximum number of tags
-    if let Some(ref tags) = query.tags {
-        if tags.len() > MAX_TAGS {
+    let tags = query.tag_filter()?;
+
+    let mut source = query.source.unwrap_or_default(); // StreamSource::All is default
+    let sorting = query.sorting.unwrap_or_default(); // StreamSorting::Timeline) is default
+
+    // The posts are recommended to the viewer, ranked by their own blend of sources
+    if let StreamSource::Recommended { viewer_id } = &mut source {
+        let Some(query_viewer_id) = &query.viewer_id else {
             return Err(Error::InvalidInput {
-                message: format!("Too many tags provided; maximum allowed is {}", MAX_TAGS),
+                message: "The recommended source requires a viewer_id".to_string(),
+            });
+        };
+        if tags.is_some() || query.kind.is_some() {
+            return Err(Error::InvalidInput {
+                message: "The recommended source cannot be filtered by tags or kind".to_string(),
             });
         }
+        viewer_id.clone_from(query_viewer_id);
     }
 
-    let source = query.source.unwrap_or_default(); // StreamSource::All is default
-    let sorting = query.sorting.unwrap_or_default(); // StreamSorting::Timeline) is default
+    if sorting == StreamSorting::TrustWeighted && query.viewer_id.is_none() {
+        return Err(Error::InvalidInput {
+            message: "The trust_weighted sorting requires a viewer_id".to_string(),
+        });
+    }
+
+    let timeframe = query.timeframe.resolve()?;
+    if timeframe.is_some() && sorting != StreamSorting::TotalEngagement {
+        return Err(Error::InvalidInput {
+            message: "The timeframe can only be used with total_engagement sorting".to_string(),
+        });
+    }
 
     match PostStream::get_posts(
         source,
         query.pagination,
         sorting,
         query.viewer_id,
-        query.tags,
+        tags,
         query.kind,
+        timeframe,
     )
     .await
     {
@@ -18,6 +18,7 @@ pub struct UserStreamQuery {
     limit: Option<usize>,
     source: Option<UserStreamSource>,
     depth: Option<u8>,
+    trust_weighted: Option<bool>,
 }
 
 #[utoipa::path(
@@ -31,7 +32,8 @@ pub struct UserStreamQuery {
         ("skip" = Option<usize>, Query, description = "Skip N followers"),
         ("limit" = Option<usize>, Query, description = "Retrieve N followers"),
         ("source" = Option<UserStreamSource>, Query, description = "Source of users for the stream."),
-        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored")
+        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored"),
+        ("trust_weighted" = Option<bool>, Query, description = "Rank the 'recommended' users by the trust of user_id instead of their mutual follows, common tags and reputation. Defaults to `false`")
     ),
     responses(
         (status = 200, description = "Users stream", body = UserStream),
@@ -86,16 +88,40 @@ pub async fn stream_users_handler(
         }
     }
 
-    match UserStream::get_by_id(
-        query.user_id.as_deref(),
-        query.viewer_id.as_deref(),
-        Some(skip),
-        Some(limit),
-        source.clone(),
-        query.depth,
-    )
-    .await
-    {
+    let stream = match (
+        query.trust_weighted.unwrap_or(false),
+        &source,
+        &query.user_id,
+    ) {
+        (true, UserStreamSource::Recommended, Some(user_id)) => {
+            UserStream::get_trust_weighted_recommended(
+                user_id,
+                query.viewer_id.as_deref(),
+                Some(skip),
+                Some(limit),
+                query.depth,
+            )
+            .await
+        }
+        (true, _, _) => {
+            return Err(Error::InvalidInput {
+                message: "trust_weighted can only be used with source 'recommended'".to_string(),
+            })
+        }
+        (false, _, _) => {
+            UserStream::get_by_id(
+                query.user_id.as_deref(),
+                query.viewer_id.as_deref(),
+                Some(skip),
+                Some(limit),
+                source.clone(),
+                query.depth,
+            )
+            .await
+        }
+    };
+
+    match stream {
         Ok(Some(stream)) => Ok(Json(stream)),
         Ok(None) => Err(Error::EmptyStream {
             message: format!(
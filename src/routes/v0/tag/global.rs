@@ -3,6 +3,7 @@ use crate::models::tag::stream::{HotTag, HotTags, TagStreamReach};
 use crate::models::tag::TaggedType;
 use crate::models::tag::Taggers as TaggersType;
 use crate::routes::v0::endpoints::{TAGS_HOT_ROUTE, TAG_TAGGERS_ROUTE};
+use crate::routes::v0::types::{parse_string_to_f64, TimeframeQuery};
 use crate::types::{Pagination, Timeframe};
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
@@ -16,9 +17,12 @@ pub struct HotTagsQuery {
     user_id: Option<String>,
     reach: Option<TagStreamReach>,
     taggers_limit: Option<usize>,
-    timeframe: Option<Timeframe>,
+    #[serde(flatten)]
+    timeframe: TimeframeQuery,
     #[serde(flatten)]
     pagination: Pagination,
+    #[serde(default, deserialize_with = "parse_string_to_f64")]
+    min_reputation: Option<f64>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -27,7 +31,8 @@ pub struct TagTaggersQuery {
     pagination: Pagination,
     user_id: Option<String>,
     reach: Option<TagStreamReach>,
-    timeframe: Option<Timeframe>,
+    #[serde(flatten)]
+    timeframe: TimeframeQuery,
 }
 
 pub struct HotTagsInput {
@@ -36,6 +41,8 @@ pub struct HotTagsInput {
     pub limit: usize,
     pub taggers_limit: usize,
     pub tagged_type: Option<TaggedType>,
+    /// Only count the tags of the taggers with at least this global reputation
+    pub min_reputation: Option<f64>,
 }
 
 impl HotTagsInput {
@@ -52,6 +59,7 @@ impl HotTagsInput {
             skip,
             taggers_limit,
             tagged_type,
+            min_reputation: None,
         }
     }
 }
@@ -67,7 +75,10 @@ impl HotTagsInput {
         ("user_id" = Option<String>, Query, description = "User ID to base reach on"),
         ("skip" = Option<usize>, Query, description = "Skip N taggers. Defaults to `0`"),
         ("limit" = Option<usize>, Query, description = "Retrieve N tagggers. Defaults to `20`"),
-        ("timeframe" = Option<Timeframe>, Query, description = "Retrieve taggers for this specific timeframe (not applied for reach). Defaults to `all_time`"),
+        ("timeframe" = Option<Timeframe>, Query, description = "Retrieve taggers for this specific timeframe (not applied for reach): today, this_month, all_time, last_24_hours, last_7_days, last_30_days. Defaults to `all_time`"),
+        ("from" = Option<i64>, Query, description = "Start of a custom timeframe, Unix timestamp in milliseconds. Not compatible with `timeframe`"),
+        ("to" = Option<i64>, Query, description = "End of a custom timeframe, Unix timestamp in milliseconds. Defaults to now"),
+        ("timezone" = Option<String>, Query, description = "UTC offset where `today` and `this_month` start, e.g. `+02:00`. Defaults to UTC"),
     ),
     responses(
         (status = 200, description = "Taggers", body = TaggersType),
@@ -93,7 +104,7 @@ pub async fn tag_taggers_handler(
 
     let skip = query.pagination.skip.unwrap_or(0);
     let limit = query.pagination.limit.unwrap_or(20).min(20);
-    let timeframe = query.timeframe.unwrap_or(Timeframe::AllTime);
+    let timeframe = query.timeframe.resolve()?.unwrap_or(Timeframe::AllTime);
 
     match Taggers::get_global_taggers(
         label.clone(),
@@ -122,7 +133,11 @@ pub async fn tag_taggers_handler(
         ("taggers_limit" = Option<usize>, Query, description = "Retrieve N user_id for each tag. Defaults to `20`"),
         ("skip" = Option<usize>, Query, description = "Skip N tags. Defaults to `0`"),
         ("limit" = Option<usize>, Query, description = "Retrieve N tag. Defaults to `40`"),
-        ("timeframe" = Option<Timeframe>, Query, description = "Retrieve hot tags for this specific timeframe. Defaults to `all_time`"),
+        ("timeframe" = Option<Timeframe>, Query, description = "Retrieve hot tags for this specific timeframe: today, this_month, all_time, last_24_hours, last_7_days, last_30_days. Defaults to `all_time`"),
+        ("from" = Option<i64>, Query, description = "Start of a custom timeframe, Unix timestamp in milliseconds. Not compatible with `timeframe`"),
+        ("to" = Option<i64>, Query, description = "End of a custom timeframe, Unix timestamp in milliseconds. Defaults to now"),
+        ("timezone" = Option<String>, Query, description = "UTC offset where `today` and `this_month` start, e.g. `+02:00`. Defaults to UTC"),
+        ("min_reputation" = Option<f64>, Query, description = "Ignore the tags of the taggers whose global reputation is lower. `1.0` is the average reputation"),
     ),
     responses(
         (status = 200, description = "Retrieve tags by reach cluster", body = Vec<HotTag>),
@@ -143,7 +158,7 @@ pub async fn hot_tags_handler(Query(query): Query<HotTagsQuery>) -> Result<Json<
     let skip = query.pagination.skip.unwrap_or(0);
     let limit = query.pagination.limit.unwrap_or(40).min(40);
     let taggers_limit = query.taggers_limit.unwrap_or(20).min(20);
-    let timeframe = query.timeframe.unwrap_or(Timeframe::AllTime);
+    let timeframe = query.timeframe.resolve()?.unwrap_or(Timeframe::AllTime);
 
     let input = HotTagsInput {
         timeframe,
@@ -151,6 +166,7 @@ pub async fn hot_tags_handler(Query(query): Query<HotTagsQuery>) -> Result<Json<
         limit,
         taggers_limit,
         tagged_type: Some(TaggedType::Post),
+        min_reputation: query.min_reputation,
     };
 
     match HotTags::get_hot_tags(query.user_id, query.reach, &input).await {
@@ -0,0 +1,71 @@
+use crate::models::tag::history::{TagHistory, TagHistoryGranularity, TagHistoryPoint};
+use crate::models::tag::TaggedType;
+use crate::routes::v0::endpoints::TAG_HISTORY_ROUTE;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use log::{error, info};
+use serde::Deserialize;
+use utoipa::OpenApi;
+
+const MAX_HISTORY_BUCKETS: usize = 500;
+
+#[derive(Deserialize, Debug)]
+pub struct TagHistoryQuery {
+    tagged_type: Option<TaggedType>,
+    granularity: Option<TagHistoryGranularity>,
+    buckets: Option<usize>,
+    end: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = TAG_HISTORY_ROUTE,
+    description = "Usage history of a tag as a time series",
+    tag = "Tags",
+    params(
+        ("label" = String, Path, description = "Tag name"),
+        ("tagged_type" = Option<TaggedType>, Query, description = "Tags on Post or User. Defaults to `Post`"),
+        ("granularity" = Option<TagHistoryGranularity>, Query, description = "Bucket size: hour | day | week. Defaults to `day`"),
+        ("buckets" = Option<usize>, Query, description = "Number of buckets, up to 500. Defaults to `30`"),
+        ("end" = Option<i64>, Query, description = "Unix timestamp in milliseconds inside the last bucket. Defaults to now"),
+    ),
+    responses(
+        (status = 200, description = "Tag count and unique taggers per bucket, the oldest first", body = TagHistory),
+        (status = 400, description = "Invalid input"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn tag_history_handler(
+    Path(label): Path<String>,
+    Query(query): Query<TagHistoryQuery>,
+) -> Result<Json<TagHistory>> {
+    info!(
+        "GET {TAG_HISTORY_ROUTE} label:{}, query: {:?}",
+        label, query
+    );
+
+    let buckets = query.buckets.unwrap_or(30);
+    if buckets == 0 || buckets > MAX_HISTORY_BUCKETS {
+        return Err(Error::InvalidInput {
+            message: format!("buckets should be between 1 and {MAX_HISTORY_BUCKETS}"),
+        });
+    }
+    let tagged_type = query.tagged_type.unwrap_or(TaggedType::Post);
+    let granularity = query.granularity.unwrap_or_default();
+
+    match TagHistory::get_by_label(&label, tagged_type, granularity, query.end, buckets).await {
+        Ok(history) => Ok(Json(history)),
+        Err(source) => {
+            error!("Internal Server ERROR: {:?}", source);
+            Err(Error::InternalServerError { source })
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(tag_history_handler),
+    components(schemas(TagHistory, TagHistoryPoint, TagHistoryGranularity))
+)]
+pub struct TagHistoryApiDoc;
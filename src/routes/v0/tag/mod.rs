@@ -4,13 +4,17 @@ use axum::Router;
 use utoipa::OpenApi;
 
 mod global;
+mod history;
+mod related;
 
 pub use global::HotTagsInput;
 
 pub fn routes() -> Router {
     register_routes!(Router::new(),
         endpoints::TAGS_HOT_ROUTE => global::hot_tags_handler,
-        endpoints::TAG_TAGGERS_ROUTE => global::tag_taggers_handler
+        endpoints::TAG_TAGGERS_ROUTE => global::tag_taggers_handler,
+        endpoints::TAG_RELATED_ROUTE => related::related_tags_handler,
+        endpoints::TAG_HISTORY_ROUTE => history::tag_history_handler
     )
 }
 
@@ -20,6 +24,9 @@ pub struct TagApiDoc;
 
 impl TagApiDoc {
     pub fn merge_docs() -> utoipa::openapi::OpenApi {
-        global::TagGlobalApiDoc::openapi()
+        let mut combined = global::TagGlobalApiDoc::openapi();
+        combined.merge(related::TagRelatedApiDoc::openapi());
+        combined.merge(history::TagHistoryApiDoc::openapi());
+        combined
     }
 }
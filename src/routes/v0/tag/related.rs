@@ -0,0 +1,77 @@
+use crate::models::tag::related::{RelatedTag, RelatedTags};
+use crate::models::tag::stream::TagStreamReach;
+use crate::models::tag::TaggedType;
+use crate::routes::v0::endpoints::TAG_RELATED_ROUTE;
+use crate::types::Pagination;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use log::{error, info};
+use serde::Deserialize;
+use utoipa::OpenApi;
+
+#[derive(Deserialize, Debug)]
+pub struct RelatedTagsQuery {
+    tagged_type: Option<TaggedType>,
+    user_id: Option<String>,
+    reach: Option<TagStreamReach>,
+    #[serde(flatten)]
+    pagination: Pagination,
+}
+
+#[utoipa::path(
+    get,
+    path = TAG_RELATED_ROUTE,
+    description = "Tags that co-occur with a tag on the same posts or users",
+    tag = "Tags",
+    params(
+        ("label" = String, Path, description = "Tag name"),
+        ("tagged_type" = Option<TaggedType>, Query, description = "Co-occurrences on the same Post or User. Defaults to `Post`"),
+        ("user_id" = Option<String>, Query, description = "User Pubky ID. Only counts the tags of the users in its reach"),
+        ("reach" = Option<TagStreamReach>, Query, description = "Reach type: follower | following | friends"),
+        ("skip" = Option<usize>, Query, description = "Skip N tags. Defaults to `0`"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N tags. Defaults to `20`"),
+    ),
+    responses(
+        (status = 200, description = "Related tags, the strongest first", body = RelatedTags),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn related_tags_handler(
+    Path(label): Path<String>,
+    Query(query): Query<RelatedTagsQuery>,
+) -> Result<Json<RelatedTags>> {
+    info!(
+        "GET {TAG_RELATED_ROUTE} label:{}, query: {:?}",
+        label, query
+    );
+
+    // Check if user_id and reach are provided together
+    if query.user_id.is_some() ^ query.reach.is_some() {
+        return Err(Error::InvalidInput {
+            message: String::from("user_id and reach should be both provided together"),
+        });
+    }
+
+    let skip = query.pagination.skip.unwrap_or(0);
+    let limit = query.pagination.limit.unwrap_or(20).min(40);
+    let tagged_type = query.tagged_type.unwrap_or(TaggedType::Post);
+
+    match RelatedTags::get_related(&label, tagged_type, query.user_id, query.reach, skip, limit)
+        .await
+    {
+        Ok(Some(related_tags)) => Ok(Json(related_tags)),
+        Ok(None) => Ok(Json(RelatedTags::default())),
+        Err(source) => {
+            error!("Internal Server ERROR: {:?}", source);
+            Err(Error::InternalServerError { source })
+        }
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(related_tags_handler),
+    components(schemas(RelatedTags, RelatedTag))
+)]
+pub struct TagRelatedApiDoc;
@@ -3,6 +3,9 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::models::tag::Taggers;
+pub(crate) use crate::types::{parse_string_to_bool, parse_string_to_u8};
+use crate::types::{parse_utc_offset, Timeframe};
+use crate::Error;
 
 #[derive(Default, Deserialize, Debug, ToSchema)]
 pub struct TagsQuery {
@@ -12,16 +15,70 @@ pub struct TagsQuery {
     pub viewer_id: Option<String>,
     #[serde(default, deserialize_with = "parse_string_to_u8")]
     pub depth: Option<u8>,
+    #[serde(default, deserialize_with = "parse_string_to_bool")]
+    pub trust_weighted: Option<bool>,
 }
 
-// Parsing strings or floats into f64
-fn parse_string_to_u8<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+/// Timeframe of the activity counted by an endpoint, either a named timeframe or a `from`/`to` range.
+/// The `timezone` moves the start of `today` and `this_month` to the local midnight
+#[derive(Default, Deserialize, Debug, ToSchema)]
+pub struct TimeframeQuery {
+    pub timeframe: Option<Timeframe>,
+    #[serde(default, deserialize_with = "parse_string_to_i64")]
+    pub from: Option<i64>,
+    #[serde(default, deserialize_with = "parse_string_to_i64")]
+    pub to: Option<i64>,
+    pub timezone: Option<String>,
+}
+
+impl TimeframeQuery {
+    /// Resolves the query parameters into a timeframe, `None` when neither a timeframe nor a range is provided
+    pub fn resolve(self) -> Result<Option<Timeframe>, Error> {
+        let invalid = |message: &str| Error::InvalidInput {
+            message: message.to_string(),
+        };
+        let timeframe = match (self.timeframe, self.from, self.to) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                return Err(invalid("Use either timeframe or from and to"))
+            }
+            (None, None, Some(_)) => return Err(invalid("to requires from")),
+            (None, Some(from), Some(to)) if to <= from => {
+                return Err(invalid("to should be greater than from"))
+            }
+            (None, Some(from), to) => Some(Timeframe::Custom { from, to }),
+            (timeframe, None, None) => timeframe,
+        };
+        match self.timezone {
+            Some(timezone) => {
+                let offset = parse_utc_offset(&timezone)
+                    .ok_or_else(|| invalid("timezone should be a UTC offset, e.g. +02:00"))?;
+                Ok(timeframe.map(|timeframe| timeframe.in_timezone(offset)))
+            }
+            None => Ok(timeframe),
+        }
+    }
+}
+
+// Parsing strings into i64, the flattened queries deserialize every value as a string
+pub(crate) fn parse_string_to_i64<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        Some(s) => s.parse::<i64>().map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+// Parsing strings into f64, the flattened queries deserialize every value as a string
+pub(crate) fn parse_string_to_f64<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s: Option<String> = Option::deserialize(deserializer)?;
     match s {
-        Some(s) => s.parse::<u8>().map(Some).map_err(de::Error::custom),
+        Some(s) => s.parse::<f64>().map(Some).map_err(de::Error::custom),
         None => Ok(None),
     }
 }
@@ -8,8 +8,12 @@ mod counts;
 mod details;
 mod follows;
 mod muted;
+mod mutuals;
+mod path;
+mod recommendations;
 mod relationship;
 pub mod tags;
+mod trust;
 mod view;
 
 pub fn routes() -> Router {
@@ -17,6 +21,9 @@ pub fn routes() -> Router {
         endpoints::USER_ROUTE => view::user_view_handler,
         endpoints::USER_DETAILS_ROUTE => details::user_details_handler,
         endpoints::RELATIONSHIP_ROUTE => relationship::user_relationship_handler,
+        endpoints::USER_MUTUALS_ROUTE => mutuals::user_mutuals_handler,
+        endpoints::USER_PATH_ROUTE => path::user_path_handler,
+        endpoints::USER_RECOMMENDATIONS_ROUTE => recommendations::user_recommendations_handler,
         endpoints::USER_TAGS_ROUTE => tags::user_tags_handler,
         endpoints::USER_TAGGERS_ROUTE => tags::user_taggers_handler,
         endpoints::USER_COUNTS_ROUTE => counts::user_counts_handler,
@@ -25,6 +32,7 @@ pub fn routes() -> Router {
         endpoints::USER_FRIENDS_ROUTE => follows::user_friends_handler,
         endpoints::USER_MUTED_ROUTE => muted::user_muted_handler,
         endpoints::USER_AVATAR_ROUTE => avatar::user_avatar_handler,
+        endpoints::USER_TRUST_ROUTE => trust::user_trust_handler,
     )
 }
 
@@ -38,10 +46,14 @@ impl UserApiDoc {
         combined.merge(counts::UserCountsApiDoc::openapi());
         combined.merge(details::UserDetailsApiDoc::openapi());
         combined.merge(relationship::RelationshipApiDoc::openapi());
+        combined.merge(mutuals::UserMutualsApiDoc::openapi());
+        combined.merge(path::UserPathApiDoc::openapi());
+        combined.merge(recommendations::UserRecommendationsApiDoc::openapi());
         combined.merge(tags::UserTagsApiDoc::openapi());
         combined.merge(follows::UserFollowsApiDoc::openapi());
         combined.merge(muted::UserMutedApiDoc::openapi());
         combined.merge(avatar::UserAvatarApiDoc::openapi());
+        combined.merge(trust::UserTrustApiDoc::openapi());
         combined
     }
 }
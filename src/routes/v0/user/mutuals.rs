@@ -0,0 +1,48 @@
+use crate::models::user::MutualConnections;
+use crate::routes::v0::endpoints::USER_MUTUALS_ROUTE;
+use crate::types::Pagination;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use log::info;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    path = USER_MUTUALS_ROUTE,
+    description = "Users the viewer follows that also follow the user",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("viewer_id" = String, Path, description = "Viewer Pubky ID"),
+        ("skip" = Option<usize>, Query, description = "Skip N mutual connections"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N mutual connections")
+    ),
+    responses(
+        (status = 200, description = "Mutual connections of the user and the viewer", body = MutualConnections),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_mutuals_handler(
+    Path((user_id, viewer_id)): Path<(String, String)>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<MutualConnections>> {
+    info!(
+        "GET {USER_MUTUALS_ROUTE} user_id:{}, viewer_id:{}",
+        user_id, viewer_id
+    );
+
+    let skip = query.skip.unwrap_or(0);
+    let limit = query.limit.unwrap_or(200);
+
+    match MutualConnections::get_by_id(&user_id, &viewer_id, Some(skip), Some(limit)).await {
+        Ok(Some(mutuals)) => Ok(Json(mutuals)),
+        Ok(None) => Err(Error::UserNotFound { user_id }),
+        Err(source) => Err(Error::InternalServerError { source }),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(user_mutuals_handler), components(schemas(MutualConnections)))]
+pub struct UserMutualsApiDoc;
@@ -0,0 +1,51 @@
+use crate::models::user::FollowPaths;
+use crate::routes::v0::endpoints::USER_PATH_ROUTE;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use log::info;
+use serde::Deserialize;
+use utoipa::OpenApi;
+
+#[derive(Deserialize, Debug)]
+pub struct PathQuery {
+    depth: Option<u8>,
+    limit: Option<usize>,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_PATH_ROUTE,
+    description = "Degrees of separation and shortest follow paths from the viewer to the user",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("viewer_id" = String, Path, description = "Viewer Pubky ID, the follows start from it"),
+        ("depth" = Option<usize>, Query, description = "Maximum number of follows (1-6) of a path. Defaults to `4`"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N shortest paths (1-20). Defaults to `5`")
+    ),
+    responses(
+        (status = 200, description = "Shortest follow paths, without paths when the user is not reached within the depth", body = FollowPaths),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_path_handler(
+    Path((user_id, viewer_id)): Path<(String, String)>,
+    Query(query): Query<PathQuery>,
+) -> Result<Json<FollowPaths>> {
+    info!(
+        "GET {USER_PATH_ROUTE} user_id:{}, viewer_id:{}, query: {:?}",
+        user_id, viewer_id, query
+    );
+
+    match FollowPaths::get_by_id(&user_id, &viewer_id, query.depth, query.limit).await {
+        Ok(Some(paths)) => Ok(Json(paths)),
+        Ok(None) => Err(Error::UserNotFound { user_id }),
+        Err(source) => Err(Error::InternalServerError { source }),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(user_path_handler), components(schemas(FollowPaths)))]
+pub struct UserPathApiDoc;
@@ -0,0 +1,58 @@
+use crate::models::user::{
+    RecommendationReason, UserCounts, UserRecommendation, UserRecommendations,
+};
+use crate::routes::v0::endpoints::USER_RECOMMENDATIONS_ROUTE;
+use crate::types::Pagination;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use log::info;
+use utoipa::OpenApi;
+
+#[utoipa::path(
+    get,
+    path = USER_RECOMMENDATIONS_ROUTE,
+    description = "Users recommended to the user with the reason of each recommendation",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("skip" = Option<usize>, Query, description = "Skip N recommendations"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N recommendations")
+    ),
+    responses(
+        (status = 200, description = "Recommended users, the best first", body = UserRecommendations),
+        (status = 204, description = "No users to recommend"),
+        (status = 404, description = "User not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_recommendations_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<Pagination>,
+) -> Result<Json<UserRecommendations>> {
+    info!("GET {USER_RECOMMENDATIONS_ROUTE} user_id:{}", user_id);
+
+    let skip = query.skip.unwrap_or(0);
+    let limit = query.limit.unwrap_or(10);
+
+    match UserCounts::get_from_index(&user_id).await {
+        Ok(Some(_)) => (),
+        Ok(None) => return Err(Error::UserNotFound { user_id }),
+        Err(source) => return Err(Error::InternalServerError { source }),
+    }
+
+    match UserRecommendations::get_by_id(&user_id, Some(skip), Some(limit)).await {
+        Ok(Some(recommendations)) => Ok(Json(recommendations)),
+        Ok(None) => Err(Error::EmptyStream {
+            message: format!("No users to recommend to {}", user_id),
+        }),
+        Err(source) => Err(Error::InternalServerError { source }),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(user_recommendations_handler),
+    components(schemas(UserRecommendations, UserRecommendation, RecommendationReason))
+)]
+pub struct UserRecommendationsApiDoc;
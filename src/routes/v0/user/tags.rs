@@ -1,6 +1,7 @@
 use crate::models::tag::traits::{TagCollection, TaggersCollection};
 use crate::models::tag::user::TagUser;
 use crate::models::tag::TagDetails;
+use crate::models::trust::TrustScores;
 use crate::routes::v0::endpoints::{USER_TAGGERS_ROUTE, USER_TAGS_ROUTE};
 use crate::routes::v0::types::TaggersInfo;
 use crate::routes::v0::TagsQuery;
@@ -23,10 +24,12 @@ use utoipa::OpenApi;
         ("limit_tags" = Option<usize>, Query, description = "Upper limit on the number of tags for the user. **Default** value 5"),
         ("limit_taggers" = Option<usize>, Query, description = "Upper limit on the number of taggers per tag. **Default** value 5"),
         ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
-        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored")
+        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored"),
+        ("trust_weighted" = Option<bool>, Query, description = "Weight and sort the tags by the trust of the viewer on their taggers. Requires `viewer_id`")
     ),
     responses(
         (status = 200, description = "User tags", body = TagDetails),
+        (status = 400, description = "Invalid input"),
         (status = 404, description = "User not found"),
         (status = 500, description = "Internal server error")
     )
@@ -40,17 +43,41 @@ pub async fn user_tags_handler(
         user_id, query.skip_tags, query.limit_tags, query.limit_taggers, query.viewer_id, query.depth
     );
 
-    match TagUser::get_by_id(
-        &user_id,
-        None,
-        query.skip_tags,
-        query.limit_tags,
-        query.limit_taggers,
-        query.viewer_id.as_deref(),
-        query.depth,
-    )
-    .await
-    {
+    let trust_weighted = query.trust_weighted.unwrap_or(false);
+    if trust_weighted && query.viewer_id.is_none() {
+        return Err(Error::InvalidInput {
+            message: String::from("viewer_id should be provided for trust weighted tags"),
+        });
+    }
+
+    let tags = match (trust_weighted, query.viewer_id.as_deref()) {
+        // The tags are weighted before they are paginated
+        (true, Some(viewer_id)) => {
+            TrustScores::get_weighted_tags::<TagUser>(
+                viewer_id,
+                &user_id,
+                None,
+                query.skip_tags,
+                query.limit_tags,
+                query.limit_taggers,
+                query.depth,
+            )
+            .await
+        }
+        _ => {
+            TagUser::get_by_id(
+                &user_id,
+                None,
+                query.skip_tags,
+                query.limit_tags,
+                query.limit_taggers,
+                query.viewer_id.as_deref(),
+                query.depth,
+            )
+            .await
+        }
+    };
+    match tags {
         Ok(Some(tags)) => Ok(Json(tags)),
         Ok(None) => Err(Error::UserNotFound { user_id }),
         Err(source) => Err(Error::InternalServerError { source }),
@@ -0,0 +1,61 @@
+use crate::models::trust::{TrustScore, TrustScores};
+use crate::routes::v0::endpoints::USER_TRUST_ROUTE;
+use crate::routes::v0::types::parse_string_to_u8;
+use crate::types::Pagination;
+use crate::{Error, Result};
+use axum::extract::{Path, Query};
+use axum::Json;
+use log::info;
+use serde::Deserialize;
+use utoipa::OpenApi;
+
+#[derive(Deserialize, Debug)]
+pub struct TrustQuery {
+    #[serde(flatten)]
+    pagination: Pagination,
+    #[serde(default, deserialize_with = "parse_string_to_u8")]
+    depth: Option<u8>,
+}
+
+#[utoipa::path(
+    get,
+    path = USER_TRUST_ROUTE,
+    description = "Personalized trust scores of the users in the network of a user",
+    tag = "User",
+    params(
+        ("user_id" = String, Path, description = "User Pubky ID"),
+        ("depth" = Option<usize>, Query, description = "Follow distance (1-3) of the scored users. Defaults to `3`"),
+        ("skip" = Option<usize>, Query, description = "Skip N users. Defaults to `0`"),
+        ("limit" = Option<usize>, Query, description = "Retrieve N users. Defaults to `20`")
+    ),
+    responses(
+        (status = 200, description = "Trust scores, the most trusted users first", body = TrustScores),
+        (status = 404, description = "User network not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn user_trust_handler(
+    Path(user_id): Path<String>,
+    Query(query): Query<TrustQuery>,
+) -> Result<Json<TrustScores>> {
+    info!(
+        "GET {USER_TRUST_ROUTE} user_id:{}, query: {:?}",
+        user_id, query
+    );
+
+    let skip = query.pagination.skip.unwrap_or(0);
+    let limit = query.pagination.limit.unwrap_or(20).min(100);
+
+    match TrustScores::get_by_id(&user_id, query.depth, Some(skip), Some(limit)).await {
+        Ok(Some(scores)) => Ok(Json(scores)),
+        Ok(None) => Err(Error::UserNotFound { user_id }),
+        Err(source) => Err(Error::InternalServerError { source }),
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(user_trust_handler),
+    components(schemas(TrustScores, TrustScore))
+)]
+pub struct UserTrustApiDoc;
@@ -1,5 +1,5 @@
 use crate::models::tag::TagDetails;
-use crate::models::user::UserView;
+use crate::models::user::{MutualConnections, UserView};
 use crate::routes::v0::endpoints::USER_ROUTE;
 use crate::{Error, Result};
 use axum::extract::{Path, Query};
@@ -8,10 +8,14 @@ use log::info;
 use serde::Deserialize;
 use utoipa::OpenApi;
 
+/// Mutual connections listed in the profile, the rest are only counted
+const MUTUALS_PREVIEW: usize = 3;
+
 #[derive(Deserialize)]
 pub struct ProfileQuery {
     viewer_id: Option<String>,
     depth: Option<u8>,
+    include_mutuals: Option<bool>,
 }
 
 #[utoipa::path(
@@ -22,7 +26,8 @@ pub struct ProfileQuery {
     params(
         ("user_id" = String, Path, description = "User Pubky ID"),
         ("viewer_id" = Option<String>, Query, description = "Viewer Pubky ID"),
-        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored")
+        ("depth" = Option<usize>, Query, description = "User trusted network depth, user following users distance. Numbers bigger than 4, will be ignored"),
+        ("include_mutuals" = Option<bool>, Query, description = "Include the first users the viewer follows that also follow the user, with their count. Requires `viewer_id`. Defaults to `false`")
     ),
     responses(
         (status = 200, description = "User Profile", body = UserView),
@@ -39,13 +44,26 @@ pub async fn user_view_handler(
         user_id, query.viewer_id, query.depth
     );
 
-    match UserView::get_by_id(&user_id, query.viewer_id.as_deref(), query.depth).await {
-        Ok(Some(user)) => Ok(Json(user)),
-        Ok(None) => Err(Error::UserNotFound { user_id }),
-        Err(source) => Err(Error::InternalServerError { source }),
+    let mut user =
+        match UserView::get_by_id(&user_id, query.viewer_id.as_deref(), query.depth).await {
+            Ok(Some(user)) => user,
+            Ok(None) => return Err(Error::UserNotFound { user_id }),
+            Err(source) => return Err(Error::InternalServerError { source }),
+        };
+
+    if let (Some(true), Some(viewer_id)) = (query.include_mutuals, &query.viewer_id) {
+        user.mutuals =
+            MutualConnections::get_by_id(&user_id, viewer_id, None, Some(MUTUALS_PREVIEW))
+                .await
+                .map_err(|source| Error::InternalServerError { source })?;
     }
+
+    Ok(Json(user))
 }
 
 #[derive(OpenApi)]
-#[openapi(paths(user_view_handler), components(schemas(UserView, TagDetails)))]
+#[openapi(
+    paths(user_view_handler),
+    components(schemas(UserView, TagDetails, MutualConnections))
+)]
 pub struct UserViewApiDoc;
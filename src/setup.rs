@@ -1,4 +1,5 @@
 use crate::db::graph::setup::setup_graph;
+use crate::models::post::HomeTimeline;
 use crate::{
     db::connectors::{
         neo4j::{Neo4jConnector, NEO4J_CONNECTOR},
@@ -49,5 +50,7 @@ impl StackManager {
         // Initialize Redis and Neo4j
         Self::setup_redis(config).await;
         Self::setup_neo4j(config).await;
+
+        HomeTimeline::setup(config);
     }
 }
@@ -1,8 +1,10 @@
 mod pagination;
+mod parse;
 mod timeframe;
 
 pub use pagination::Pagination;
-pub use timeframe::Timeframe;
+pub use parse::{parse_string_to_bool, parse_string_to_u8};
+pub use timeframe::{parse_utc_offset, Timeframe};
 
 use serde::{Deserialize, Serialize};
 use std::error::Error;
@@ -16,4 +18,7 @@ pub enum StreamSorting {
     #[default]
     Timeline,
     TotalEngagement,
+    Trending,
+    /// Posts of the users trusted by the viewer, ranked by the trust on the author with a time decay
+    TrustWeighted,
 }
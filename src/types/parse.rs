@@ -0,0 +1,26 @@
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
+
+// Parsing strings into u8, the query values are received as strings
+pub fn parse_string_to_u8<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        Some(s) => s.parse::<u8>().map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+// Parsing strings into bool, the flattened queries deserialize every value as a string
+pub fn parse_string_to_bool<'de, D>(deserializer: D) -> Result<Option<bool>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s: Option<String> = Option::deserialize(deserializer)?;
+    match s {
+        Some(s) => s.parse::<bool>().map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
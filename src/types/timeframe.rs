@@ -1,14 +1,29 @@
-use chrono::Datelike;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Utc};
 use serde::Deserialize;
 use std::fmt::Display;
 use utoipa::ToSchema;
 
-#[derive(Deserialize, Debug, ToSchema, Clone)]
+#[derive(Deserialize, Debug, ToSchema, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Timeframe {
     Today,
     ThisMonth,
     AllTime,
+    /// Rolling window of the last 24 hours
+    #[serde(rename = "last_24_hours")]
+    Last24Hours,
+    /// Rolling window of the last 7 days
+    #[serde(rename = "last_7_days")]
+    Last7Days,
+    /// Rolling window of the last 30 days
+    #[serde(rename = "last_30_days")]
+    Last30Days,
+    /// Explicit range of Unix timestamps in milliseconds, open ended until now without `to`
+    #[serde(skip)]
+    Custom {
+        from: i64,
+        to: Option<i64>,
+    },
 }
 
 impl Display for Timeframe {
@@ -17,6 +32,11 @@ impl Display for Timeframe {
             Timeframe::Today => write!(f, "Today"),
             Timeframe::ThisMonth => write!(f, "ThisMonth"),
             Timeframe::AllTime => write!(f, "AllTime"),
+            Timeframe::Last24Hours => write!(f, "Last24Hours"),
+            Timeframe::Last7Days => write!(f, "Last7Days"),
+            Timeframe::Last30Days => write!(f, "Last30Days"),
+            Timeframe::Custom { from, to: Some(to) } => write!(f, "Custom:{from}:{to}"),
+            Timeframe::Custom { from, to: None } => write!(f, "Custom:{from}:Now"),
         }
     }
 }
@@ -40,15 +60,137 @@ impl Timeframe {
                 .and_utc()
                 .timestamp_millis(),
             Timeframe::AllTime => 0,
+            Timeframe::Last24Hours => (now - Duration::hours(24)).timestamp_millis(),
+            Timeframe::Last7Days => (now - Duration::days(7)).timestamp_millis(),
+            Timeframe::Last30Days => (now - Duration::days(30)).timestamp_millis(),
+            Timeframe::Custom { from, to } => return (*from, to.unwrap_or(now.timestamp_millis())),
         };
         (start, now.timestamp_millis())
     }
 
+    /// Starts the calendar timeframes at midnight in the timezone of the offset instead of UTC.
+    /// The rolling windows and the explicit ranges do not depend on the timezone
+    pub fn in_timezone(self, offset: FixedOffset) -> Self {
+        if offset.local_minus_utc() == 0 {
+            return self;
+        }
+        let now = Utc::now().with_timezone(&offset);
+        let start = match self {
+            Timeframe::Today => now.date_naive(),
+            Timeframe::ThisMonth => now.date_naive().with_day(1).unwrap_or_default(),
+            timeframe => return timeframe,
+        };
+        let from = start
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+            .and_utc()
+            .timestamp_millis()
+            - i64::from(offset.local_minus_utc()) * 1000;
+        Timeframe::Custom { from, to: None }
+    }
+
+    /// Identifies the window of the timeframe that contains the timestamp, e.g. `2024-10-31` for `Today`
+    /// or `2024-10` for `ThisMonth`. The rest of timeframes are not split in windows
+    pub fn to_window_id(&self, timestamp: i64) -> Option<String> {
+        let datetime = DateTime::<Utc>::from_timestamp_millis(timestamp)?;
+        match self {
+            Timeframe::Today => Some(datetime.format("%Y-%m-%d").to_string()),
+            Timeframe::ThisMonth => Some(datetime.format("%Y-%m").to_string()),
+            _ => None,
+        }
+    }
+
+    /// Identifies the window of the timeframe that contains the current time
+    pub fn to_current_window_id(&self) -> Option<String> {
+        self.to_window_id(Utc::now().timestamp_millis())
+    }
+
+    /// Seconds an index of one window is kept after its last update
+    pub fn to_window_ttl(&self) -> i64 {
+        match self {
+            Timeframe::Today => 60 * 60 * 24 * 2,
+            Timeframe::ThisMonth => 60 * 60 * 24 * 32,
+            _ => 0,
+        }
+    }
+
     pub fn to_cache_period(&self) -> i64 {
         match self {
             Timeframe::Today => 60 * 60,
             Timeframe::ThisMonth => 60 * 60 * 24,
             Timeframe::AllTime => 60 * 60 * 24,
+            // The rolling windows move while cached, the shorter the window the sooner it expires
+            Timeframe::Last24Hours => 60 * 10,
+            Timeframe::Last7Days => 60 * 60,
+            Timeframe::Last30Days => 60 * 60 * 24,
+            Timeframe::Custom { to: None, .. } => 60 * 60,
+            Timeframe::Custom { to: Some(_), .. } => 60 * 60 * 24,
         }
     }
 }
+
+/// Parses a UTC offset such as `+02:00`, `-0530`, `+9` or `Z`. The `+` sign decoded from a query
+/// string as a space is accepted
+pub fn parse_utc_offset(offset: &str) -> Option<FixedOffset> {
+    let offset = offset.trim_end();
+    if !offset.is_ascii() {
+        return None;
+    }
+    if offset.eq_ignore_ascii_case("z") || offset.eq_ignore_ascii_case("utc") {
+        return FixedOffset::east_opt(0);
+    }
+    let (sign, digits) = match offset.chars().next()? {
+        '+' | ' ' => (1, &offset[1..]),
+        '-' => (-1, &offset[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_utc_offset() {
+        let offset = |seconds| FixedOffset::east_opt(seconds);
+        assert_eq!(parse_utc_offset("+02:00"), offset(2 * 3600));
+        assert_eq!(parse_utc_offset(" 02:00"), offset(2 * 3600));
+        assert_eq!(parse_utc_offset("-0530"), offset(-(5 * 3600 + 30 * 60)));
+        assert_eq!(parse_utc_offset("+9"), offset(9 * 3600));
+        assert_eq!(parse_utc_offset("Z"), offset(0));
+        assert_eq!(parse_utc_offset("Europe/Madrid"), None);
+        assert_eq!(parse_utc_offset("+25:00"), None);
+    }
+
+    #[test]
+    fn test_timeframe_in_timezone() {
+        let offset = FixedOffset::east_opt(-5 * 3600).unwrap();
+        let Timeframe::Custom { from, to: None } = Timeframe::Today.in_timezone(offset) else {
+            panic!("Today should start at the local midnight");
+        };
+        let local_midnight = DateTime::from_timestamp_millis(from)
+            .unwrap()
+            .with_timezone(&offset);
+        assert_eq!(local_midnight.format("%H:%M").to_string(), "00:00");
+        assert!(from <= Utc::now().timestamp_millis());
+
+        // UTC keeps the indexed calendar windows
+        let utc = FixedOffset::east_opt(0).unwrap();
+        assert_eq!(Timeframe::Today.in_timezone(utc), Timeframe::Today);
+        assert_eq!(
+            Timeframe::Last7Days.in_timezone(offset),
+            Timeframe::Last7Days
+        );
+    }
+}
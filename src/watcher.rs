@@ -1,8 +1,11 @@
 use log::error;
 use log::info;
+use pubky_nexus::models::notification::webhook::Webhook;
+use pubky_nexus::models::tag::label::TagAlias;
+use pubky_nexus::models::trust::UserReputation;
 use pubky_nexus::PubkyConnector;
 use pubky_nexus::{Config, EventProcessor, StackManager};
-use tokio::time::{sleep, Duration};
+use tokio::time::{interval, sleep, Duration};
 
 /// Watches over a homeserver `/events` and writes into the Nexus databases
 #[tokio::main]
@@ -13,6 +16,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Sync + Send>> {
 
     PubkyConnector::initialise(&config).await?;
 
+    if let Some(webhooks_file) = &config.webhooks_file {
+        Webhook::register_from_file(webhooks_file).await?;
+        info!("Registered webhooks from {}", webhooks_file);
+    }
+    if let Some(tag_aliases_file) = &config.tag_aliases_file {
+        TagAlias::register_from_file(tag_aliases_file).await?;
+        info!("Registered tag aliases from {}", tag_aliases_file);
+    }
+
+    // Refresh the global reputation periodically, the first tick completes immediately
+    let reputation_seeds = config.reputation_seeds.clone();
+    let mut reputation_interval = interval(Duration::from_secs(
+        config.reputation_refresh_interval.max(1),
+    ));
+    tokio::spawn(async move {
+        loop {
+            reputation_interval.tick().await;
+            match UserReputation::refresh(&reputation_seeds).await {
+                Ok(count) => info!("Refreshed the global reputation of {} users", count),
+                Err(e) => error!("Failed to refresh the global reputation: {:?}", e),
+            }
+        }
+    });
+
     let mut event_processor = EventProcessor::from_config(&config).await?;
 
     loop {
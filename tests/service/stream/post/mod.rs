@@ -7,6 +7,7 @@ pub mod kind;
 pub mod post_replies;
 pub mod posts;
 pub mod reach;
+pub mod recommended;
 pub mod tags;
 pub mod utils;
 
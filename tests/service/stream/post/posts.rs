@@ -293,3 +293,12 @@ async fn test_stream_invalid_sorting() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_timeframe_requires_total_engagement() -> Result<()> {
+    // The timeframe only applies to the total engagement sorting
+    let endpoint = "/v0/stream/posts?sorting=timeline&timeframe=today";
+    invalid_get_request(endpoint, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
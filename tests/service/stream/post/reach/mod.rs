@@ -1,3 +1,4 @@
 pub mod engagement;
+pub mod network;
 pub mod timeline;
 pub mod utils;
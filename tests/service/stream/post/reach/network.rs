@@ -0,0 +1,84 @@
+use crate::service::{
+    stream::post::{AMSTERDAM, ROOT_PATH},
+    utils::{get_request, invalid_get_request},
+};
+use anyhow::Result;
+use axum::http::StatusCode;
+
+fn post_keys(body: &serde_json::Value) -> Vec<String> {
+    body.as_array()
+        .expect("Post stream should be an array")
+        .iter()
+        .map(|post| {
+            format!(
+                "{}:{}",
+                post["details"]["author"].as_str().unwrap(),
+                post["details"]["id"].as_str().unwrap()
+            )
+        })
+        .collect()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_network_of_depth_one_is_following() -> Result<()> {
+    let path = format!("{ROOT_PATH}?source=following&observer_id={AMSTERDAM}&limit=100");
+    let mut following = post_keys(&get_request(&path).await?);
+
+    let path = format!("{ROOT_PATH}?source=network&observer_id={AMSTERDAM}&depth=1&limit=100");
+    let mut network = post_keys(&get_request(&path).await?);
+
+    // The posts with the same timestamp can come in any order
+    network.sort();
+    following.sort();
+    assert_eq!(network, following);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_network_reaches_further_with_depth() -> Result<()> {
+    let path = format!("{ROOT_PATH}?source=network&observer_id={AMSTERDAM}&depth=1&limit=100");
+    let first_hop = post_keys(&get_request(&path).await?);
+
+    // The default depth is 2
+    let path = format!("{ROOT_PATH}?source=network&observer_id={AMSTERDAM}&limit=100");
+    let second_hop = post_keys(&get_request(&path).await?);
+
+    assert!(second_hop.len() >= first_hop.len());
+    for post_key in &first_hop {
+        assert!(
+            second_hop.contains(post_key),
+            "The posts of the followed users should be in the deeper network"
+        );
+    }
+
+    // None of the posts of the observer are part of its network
+    for post_key in &second_hop {
+        assert!(!post_key.starts_with(AMSTERDAM));
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_network_by_engagement() -> Result<()> {
+    let path = format!(
+        "{ROOT_PATH}?source=network&observer_id={AMSTERDAM}&depth=2&sorting=total_engagement"
+    );
+    let network = post_keys(&get_request(&path).await?);
+
+    assert!(!network.is_empty());
+    for post_key in &network {
+        assert!(!post_key.starts_with(AMSTERDAM));
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_network_with_invalid_depth() -> Result<()> {
+    let path = format!("{ROOT_PATH}?source=network&observer_id={AMSTERDAM}&depth=two");
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
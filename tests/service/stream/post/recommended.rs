@@ -0,0 +1,64 @@
+use crate::service::{
+    stream::post::{ROOT_PATH, USER_ID},
+    utils::{get_request, invalid_get_request},
+};
+use anyhow::Result;
+use axum::http::StatusCode;
+
+fn post_keys(body: &serde_json::Value) -> Vec<String> {
+    body.as_array()
+        .expect("Post stream should be an array")
+        .iter()
+        .map(|post| {
+            format!(
+                "{}:{}",
+                post["details"]["author"].as_str().unwrap(),
+                post["details"]["id"].as_str().unwrap()
+            )
+        })
+        .collect()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_recommended() -> Result<()> {
+    let path = format!("{ROOT_PATH}?source=recommended&viewer_id={USER_ID}&limit=30");
+    let recommended = post_keys(&get_request(&path).await?);
+    assert!(!recommended.is_empty());
+
+    // The posts of the viewer and the posts it already bookmarked are left out
+    let path = format!("{ROOT_PATH}?source=bookmarks&observer_id={USER_ID}&limit=30");
+    let bookmarked = post_keys(&get_request(&path).await?);
+    for post_key in &recommended {
+        assert!(!post_key.starts_with(USER_ID));
+        assert!(!bookmarked.contains(post_key));
+    }
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_recommended_pagination() -> Result<()> {
+    let path = format!("{ROOT_PATH}?source=recommended&viewer_id={USER_ID}&limit=6");
+    let recommended = post_keys(&get_request(&path).await?);
+
+    // The cached candidates keep their order between the pages
+    let path = format!("{ROOT_PATH}?source=recommended&viewer_id={USER_ID}&limit=3");
+    let mut pages = post_keys(&get_request(&path).await?);
+    let path = format!("{ROOT_PATH}?source=recommended&viewer_id={USER_ID}&skip=3&limit=3");
+    pages.extend(post_keys(&get_request(&path).await?));
+
+    assert_eq!(pages, recommended);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_recommended_invalid_queries() -> Result<()> {
+    let path = format!("{ROOT_PATH}?source=recommended");
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    let path = format!("{ROOT_PATH}?source=recommended&viewer_id={USER_ID}&tags=dev");
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
@@ -369,3 +369,47 @@ async fn test_post_specific_tag_with_no_result() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_by_all_tags() -> Result<()> {
+    let path = format!("{ROOT_PATH}?all_tags={TAG_LABEL_2},{TAG_LABEL_3}&sorting=timeline");
+    let body = get_request(&path).await?;
+    let post_stream: PostStream = serde_json::from_value(body)?;
+
+    assert!(!post_stream.0.is_empty(), "Post stream should not be empty");
+    for post in post_stream.0 {
+        for label in [TAG_LABEL_2, TAG_LABEL_3] {
+            assert!(
+                post.tags.iter().any(|tag| tag.label == label),
+                "Post should be tagged with all the requested tags"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_excluding_tags() -> Result<()> {
+    let path = format!(
+        "{ROOT_PATH}?tags={TAG_LABEL_2}&exclude_tags={TAG_LABEL_3}&sorting=total_engagement"
+    );
+    let body = get_request(&path).await?;
+    let post_stream: PostStream = serde_json::from_value(body)?;
+
+    assert!(!post_stream.0.is_empty(), "Post stream should not be empty");
+    for post in post_stream.0 {
+        assert!(post.tags.iter().any(|tag| tag.label == TAG_LABEL_2));
+        assert!(
+            post.tags.iter().all(|tag| tag.label != TAG_LABEL_3),
+            "Post should not be tagged with the excluded tag"
+        );
+    }
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_stream_posts_too_many_excluded_tags() -> Result<()> {
+    let path = format!("{ROOT_PATH}?exclude_tags=a,b,c,d,e,f");
+    invalid_get_request(&path, StatusCode::BAD_REQUEST).await?;
+    Ok(())
+}
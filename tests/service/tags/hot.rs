@@ -278,6 +278,45 @@ async fn test_hot_tags_by_following_reach_with_skip_limit() -> Result<()> {
     Ok(())
 }
 
+#[tokio_shared_rt::test(shared)]
+async fn test_global_hot_tags_with_custom_timeframe() -> Result<()> {
+    // A range open until now from the UTC midnight is the same as today
+    let midnight = Utc::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_utc()
+        .timestamp_millis();
+    let body = get_request(&format!("/v0/tags/hot?from={midnight}")).await?;
+    let tags = body.as_array().expect("Stream tags should be an array");
+    analyse_hot_tags_structure(tags);
+
+    let hot_tag = StreamTagMockup::new(String::from("today"), 3, 3, 3);
+    compare_unit_hot_tag(&tags[0], hot_tag);
+
+    // The rolling windows include the tags of today
+    let body = get_request("/v0/tags/hot?timeframe=last_7_days").await?;
+    let tags = body.as_array().expect("Stream tags should be an array");
+    analyse_hot_tags_structure(tags);
+    assert!(tags.iter().any(|tag| tag["label"] == "today"));
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_global_hot_tags_with_invalid_timeframe() -> Result<()> {
+    for endpoint in [
+        "/v0/tags/hot?timeframe=today&from=0",
+        "/v0/tags/hot?to=1000",
+        "/v0/tags/hot?from=2000&to=1000",
+        "/v0/tags/hot?timeframe=today&timezone=Europe/Madrid",
+    ] {
+        invalid_get_request(endpoint, StatusCode::BAD_REQUEST).await?;
+    }
+
+    Ok(())
+}
+
 #[tokio_shared_rt::test(shared)]
 async fn test_hot_tags_by_reach_no_user_id() -> Result<()> {
     let endpoint = "/v0/tags/hot?reach=following";
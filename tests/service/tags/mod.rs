@@ -3,6 +3,7 @@ use pubky_nexus::types::DynError;
 use crate::service::utils::get_request;
 pub mod hot;
 pub mod post;
+pub mod related;
 pub mod search;
 pub mod user;
 pub mod utils;
@@ -0,0 +1,26 @@
+use anyhow::Result;
+use reqwest::StatusCode;
+
+use crate::service::utils::{get_request, invalid_get_request};
+
+const PEER_PUBKY: &str = "o1gg96ewuojmopcjbz8895478wdtxtzzuxnfjjz8o8e77csa1ngo";
+
+#[tokio_shared_rt::test(shared)]
+async fn test_related_tags_of_unknown_label() -> Result<()> {
+    let body = get_request("/v0/tags/this_label_is_never_used/related").await?;
+    assert!(body.is_array());
+    assert_eq!(body.as_array().unwrap().len(), 0);
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_related_tags_reach_requires_user_id() -> Result<()> {
+    let endpoint = format!("/v0/tags/pubky/related?user_id={}", PEER_PUBKY);
+    invalid_get_request(&endpoint, StatusCode::BAD_REQUEST).await?;
+
+    let endpoint = "/v0/tags/pubky/related?reach=following";
+    invalid_get_request(endpoint, StatusCode::BAD_REQUEST).await?;
+
+    Ok(())
+}
@@ -151,3 +151,161 @@ async fn test_get_friends() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_mutuals() -> Result<()> {
+    let user_id = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+    let viewer_id = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+
+    let to_ids = |res: serde_json::Value| -> Vec<String> {
+        res.as_array()
+            .unwrap()
+            .iter()
+            .map(|id| id.as_str().unwrap().to_string())
+            .collect()
+    };
+    let followers = to_ids(get_request(&format!("/v0/user/{}/followers", user_id)).await?);
+    let following = to_ids(get_request(&format!("/v0/user/{}/following", viewer_id)).await?);
+
+    // The users the viewer follows that follow the user
+    let mut expected_ids: Vec<String> = following
+        .into_iter()
+        .filter(|id| followers.contains(id))
+        .collect();
+    expected_ids.sort();
+
+    let res = get_request(&format!("/v0/user/{}/mutuals/{}", user_id, viewer_id)).await?;
+    assert_eq!(res["count"], expected_ids.len());
+    assert_eq!(res["user_ids"], serde_json::json!(expected_ids));
+
+    // The count is kept on every page
+    let res = get_request(&format!(
+        "/v0/user/{}/mutuals/{}?skip=1&limit=1",
+        user_id, viewer_id
+    ))
+    .await?;
+    assert_eq!(res["count"], expected_ids.len());
+    assert_eq!(
+        res["user_ids"],
+        serde_json::json!(expected_ids.iter().skip(1).take(1).collect::<Vec<_>>())
+    );
+
+    // The profile includes the first mutual connections when requested
+    let res = get_request(&format!("/v0/user/{}?viewer_id={}", user_id, viewer_id)).await?;
+    assert!(res.get("mutuals").is_none());
+    let res = get_request(&format!(
+        "/v0/user/{}?viewer_id={}&include_mutuals=true",
+        user_id, viewer_id
+    ))
+    .await?;
+    assert_eq!(res["mutuals"]["count"], expected_ids.len());
+
+    // Test non-existing user
+    invalid_get_request(
+        &format!("/v0/user/{}/mutuals/{}", "bad_user_id", viewer_id),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_follow_paths() -> Result<()> {
+    let user_id = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+    let follower_id = "y4euc58gnmxun9wo87gwmanu6kztt9pgw1zz1yp1azp7trrsjamy";
+
+    // A follower reaches the user in one follow, without users in between
+    let res = get_request(&format!("/v0/user/{}/path/{}", user_id, follower_id)).await?;
+    assert_eq!(res["degrees"], 1);
+    assert_eq!(res["paths"], serde_json::json!([[]]));
+
+    // The followers of the follower that do not follow the user reach it through someone
+    let res = get_request(&format!("/v0/user/{}/followers", follower_id)).await?;
+    let user_followers = get_request(&format!("/v0/user/{}/followers", user_id)).await?;
+    let user_followers = user_followers.as_array().unwrap();
+    let second_degree = res
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_str().unwrap())
+        .find(|id| *id != user_id && !user_followers.iter().any(|follower| follower == id));
+    if let Some(viewer_id) = second_degree {
+        let res = get_request(&format!("/v0/user/{}/path/{}", user_id, viewer_id)).await?;
+        assert_eq!(res["degrees"], 2);
+        for path in res["paths"].as_array().unwrap() {
+            assert_eq!(path.as_array().unwrap().len(), 1);
+        }
+
+        // Not reached within a single follow
+        let res = get_request(&format!("/v0/user/{}/path/{}?depth=1", user_id, viewer_id)).await?;
+        assert!(res["degrees"].is_null());
+        assert_eq!(res["paths"], serde_json::json!([]));
+    }
+
+    // A user is at zero degrees of itself
+    let res = get_request(&format!("/v0/user/{}/path/{}", user_id, user_id)).await?;
+    assert_eq!(res["degrees"], 0);
+
+    // Test non-existing user
+    invalid_get_request(
+        &format!("/v0/user/{}/path/{}", "bad_user_id", follower_id),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_get_recommendations() -> Result<()> {
+    let user_id = "4snwyct86m383rsduhw5xgcxpw7c63j3pq8x4ycqikxgik8y64ro";
+    let res = get_request(&format!("/v0/user/{}/recommendations?limit=5", user_id)).await?;
+
+    let recommendations = res.as_array().unwrap();
+    assert!(!recommendations.is_empty(), "Expected users to recommend");
+    assert!(recommendations.len() <= 5);
+
+    let following = get_request(&format!("/v0/user/{}/following?limit=1000", user_id)).await?;
+    let following = following.as_array().unwrap();
+    let mut last_score = f64::MAX;
+    for recommendation in recommendations {
+        let recommended_id = &recommendation["user_id"];
+        assert_ne!(recommended_id, user_id);
+        assert!(
+            !following.contains(recommended_id),
+            "Followed users are not recommended"
+        );
+
+        // Each recommendation is explained by mutual follows or common tags
+        let reason = &recommendation["reason"];
+        assert!(
+            reason["mutual_follows"].as_u64().unwrap() > 0
+                || !reason["common_tags"].as_array().unwrap().is_empty()
+        );
+
+        // The best recommendations come first
+        let score = recommendation["score"].as_f64().unwrap();
+        assert!(score <= last_score);
+        last_score = score;
+    }
+
+    // The pages follow the same ranking
+    let res = get_request(&format!(
+        "/v0/user/{}/recommendations?skip=1&limit=1",
+        user_id
+    ))
+    .await?;
+    if let Some(second) = recommendations.get(1) {
+        assert_eq!(res[0]["user_id"], second["user_id"]);
+    }
+
+    // Test non-existing user
+    invalid_get_request(
+        &format!("/v0/user/{}/recommendations", "bad_user_id"),
+        StatusCode::NOT_FOUND,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -51,8 +51,8 @@ async fn test_homeserver_unfollow_notification() -> Result<()> {
 
     assert_eq!(
         notifications.len(),
-        2,
-        "Followee should have 2 notifications after unfollow"
+        1,
+        "Followee should only have the lost friend notification after unfollow"
     );
     if let NotificationBody::LostFriend { unfollowed_by } = &notifications[0].body {
         assert_eq!(
@@ -66,14 +66,14 @@ async fn test_homeserver_unfollow_notification() -> Result<()> {
     // Step 6: Followee unfollows the follower (no new notification should be generated)
     test.del(&follow_back_uri).await?;
 
-    // Verify the follower gets no new notification after unfollow
+    // Verify the follower gets no new notification after unfollow and the new friend one is removed
     let notifications_follower = Notification::get_by_id(&follower_id, Pagination::default())
         .await
         .unwrap();
     assert_eq!(
         notifications_follower.len(),
-        1,
-        "Follower should have no new notifications after unfollow"
+        0,
+        "Follower should have no notifications after unfollow"
     );
 
     // Cleanup
@@ -132,19 +132,10 @@ async fn test_homeserver_sequential_unfollow() -> Result<()> {
 
     assert_eq!(
         notifications_danonino.len(),
-        2,
-        "Follower should have 2 notifications: NewFriend and LostFriend"
+        1,
+        "Follower should only have the LostFriend notification, NewFriend is removed"
     );
 
-    if let NotificationBody::NewFriend { followed_by } = &notifications_danonino[1].body {
-        assert_eq!(
-            followed_by, &followee_id,
-            "Notification should contain the correct followee"
-        );
-    } else {
-        panic!("Expected a new friend notification, found something else");
-    }
-
     if let NotificationBody::LostFriend { unfollowed_by } = &notifications_danonino[0].body {
         assert_eq!(
             unfollowed_by, &followee_id,
@@ -0,0 +1,102 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+use pubky_nexus::models::post::{HomeTimeline, PostDetails};
+
+async fn get_home_timeline(user_id: &str, friends: bool) -> Option<Vec<String>> {
+    HomeTimeline::get_posts(user_id, friends, None, None, None, Some(10))
+        .await
+        .unwrap()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_follow_home_timeline() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Alice", "Bob"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_follow_home_timeline".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:HomeTimeline:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (alice_id, bob_id) = (&user_ids[0], &user_ids[1]);
+
+    let mut post = PubkyAppPost {
+        content: "Watcher:HomeTimeline:Alice:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+
+    // Bob follows Alice, the home timeline of Bob is built from the posts of Alice
+    test.create_follow(bob_id, alice_id).await?;
+    let first_post_id = test.create_post(alice_id, &post).await?;
+    let first_post_key = format!("{alice_id}:{first_post_id}");
+    HomeTimeline::reindex(bob_id).await.unwrap();
+    assert_eq!(
+        get_home_timeline(bob_id, false).await,
+        Some(vec![first_post_key.clone()])
+    );
+    assert_eq!(get_home_timeline(bob_id, true).await, None);
+
+    // Alice follows Bob back, the friends timeline of Bob is backfilled
+    test.create_follow(alice_id, bob_id).await?;
+    HomeTimeline::follow(alice_id, bob_id, true).await.unwrap();
+    assert_eq!(
+        get_home_timeline(bob_id, true).await,
+        Some(vec![first_post_key.clone()])
+    );
+
+    // The new posts of Alice are fanned out to the home timelines of Bob
+    post.content = "Watcher:HomeTimeline:Alice:SecondPost".to_string();
+    let second_post_id = test.create_post(alice_id, &post).await?;
+    let second_post_key = format!("{alice_id}:{second_post_id}");
+    let details = PostDetails::get_by_id(alice_id, &second_post_id)
+        .await
+        .unwrap()
+        .expect("The post should be indexed");
+    HomeTimeline::fan_out(alice_id, &second_post_id, details.indexed_at)
+        .await
+        .unwrap();
+    for friends in [false, true] {
+        assert_eq!(
+            get_home_timeline(bob_id, friends).await,
+            Some(vec![second_post_key.clone(), first_post_key.clone()])
+        );
+    }
+
+    // Deleted posts leave the home timelines
+    HomeTimeline::remove_post(alice_id, &second_post_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        get_home_timeline(bob_id, false).await,
+        Some(vec![first_post_key.clone()])
+    );
+
+    // Bob unfollows Alice and they stop being friends
+    HomeTimeline::unfollow(bob_id, alice_id, true)
+        .await
+        .unwrap();
+    assert_eq!(get_home_timeline(bob_id, false).await, None);
+    assert_eq!(get_home_timeline(bob_id, true).await, None);
+    assert_eq!(get_home_timeline(alice_id, true).await, None);
+
+    // Cleanup
+    for post_id in [&first_post_id, &second_post_id] {
+        test.cleanup_post(alice_id, post_id).await?;
+    }
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
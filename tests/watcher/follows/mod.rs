@@ -3,6 +3,7 @@ mod del_friends;
 mod del_notification;
 mod del_sequential;
 mod fail_index;
+mod home_timeline;
 mod put;
 mod put_friends;
 mod put_notification;
@@ -66,8 +66,8 @@ async fn test_delete_post_that_reposted_notification() -> Result<()> {
 
     assert_eq!(
         notifications.len(),
-        2,
-        "The poster should exactly have 2 notifications"
+        1,
+        "The poster should exactly have 1 notification, the stale one was removed"
     );
 
     let notification = &notifications[0];
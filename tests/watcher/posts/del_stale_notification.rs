@@ -0,0 +1,101 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+use pubky_nexus::{
+    models::notification::{Notification, NotificationBody},
+    types::Pagination,
+};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_delete_post_removes_stale_notifications() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(4);
+    for name in ["Alice", "Bob", "Carol", "Dave"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_delete_post_removes_stale_notifications".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:StaleNotification:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (alice_id, bob_id, carol_id, dave_id) =
+        (&user_ids[0], &user_ids[1], &user_ids[2], &user_ids[3]);
+
+    // Alice starts the thread
+    let root_post = PubkyAppPost {
+        content: "Watcher:StaleNotification:Alice:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let root_post_id = test.create_post(alice_id, &root_post).await?;
+
+    // Bob replies to Alice
+    let bob_reply = PubkyAppPost {
+        content: "Watcher:StaleNotification:Bob:Reply".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(format!(
+            "pubky://{alice_id}/pub/pubky.app/posts/{root_post_id}"
+        )),
+        embed: None,
+        attachments: None,
+    };
+    let bob_reply_id = test.create_post(bob_id, &bob_reply).await?;
+
+    // Carol replies to Bob and mentions Dave
+    let carol_reply = PubkyAppPost {
+        content: format!("Watcher:StaleNotification:Carol:Reply pk:{dave_id}"),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(format!(
+            "pubky://{bob_id}/pub/pubky.app/posts/{bob_reply_id}"
+        )),
+        embed: None,
+        attachments: None,
+    };
+    let carol_reply_id = test.create_post(carol_id, &carol_reply).await?;
+    let carol_reply_uri = format!("pubky://{carol_id}/pub/pubky.app/posts/{carol_reply_id}");
+
+    // Carol deletes her reply
+    test.cleanup_post(carol_id, &carol_reply_id).await?;
+
+    // Alice keeps the reply from Bob, the thread reply from Carol is removed
+    let notifications = Notification::get_by_id(alice_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert!(!notifications
+        .iter()
+        .any(|notification| notification.body.references_uri(&carol_reply_uri)));
+
+    // Bob only has the notification about the deleted reply
+    let notifications = Notification::get_by_id(bob_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+    if let NotificationBody::PostDeleted { deleted_uri, .. } = &notifications[0].body {
+        assert_eq!(deleted_uri, &carol_reply_uri);
+    } else {
+        panic!("Expected a POST_DELETED notification, found something else");
+    }
+
+    // The mention of Dave is removed
+    let notifications = Notification::get_by_id(dave_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 0);
+
+    // Cleanup
+    test.cleanup_post(bob_id, &bob_reply_id).await?;
+    test.cleanup_post(alice_id, &root_post_id).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
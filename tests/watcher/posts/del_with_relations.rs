@@ -63,7 +63,7 @@ async fn test_delete_post_with_relationships() -> Result<()> {
     );
 
     // Attempt to get post view; should not exist
-    let post_view = PostView::get_by_id(&user_id, &post_id, None, None, None)
+    let post_view = PostView::get_by_id(&user_id, &post_id, None, None, None, None)
         .await
         .unwrap();
     assert!(post_view.is_some(), "Post view should exist after deletion");
@@ -60,7 +60,7 @@ async fn test_delete_post_without_relationships() -> Result<()> {
     );
 
     // Attempt to get post view; should not exist
-    let post_view = PostView::get_by_id(&user_id, &post_id, None, None, None)
+    let post_view = PostView::get_by_id(&user_id, &post_id, None, None, None, None)
         .await
         .unwrap();
     assert!(
@@ -189,7 +189,7 @@ async fn test_delete_post_that_reposted() -> Result<()> {
     );
 
     // Attempt to get REPOST view; should not exist
-    let post_view = PostView::get_by_id(&user_id, &repost_id, None, None, None)
+    let post_view = PostView::get_by_id(&user_id, &repost_id, None, None, None, None)
         .await
         .unwrap();
     assert!(
@@ -319,7 +319,7 @@ async fn test_delete_post_that_replied() -> Result<()> {
     );
 
     // Attempt to get post REPLY view; should not exist
-    let post_view = PostView::get_by_id(&user_id, &reply_id, None, None, None)
+    let post_view = PostView::get_by_id(&user_id, &reply_id, None, None, None, None)
         .await
         .unwrap();
     assert!(
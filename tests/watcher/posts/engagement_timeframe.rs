@@ -0,0 +1,135 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppPostKind, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::post::{PostStream, StreamSource, POST_TOTAL_ENGAGEMENT_KEY_PARTS};
+use pubky_nexus::types::{Pagination, StreamSorting, Timeframe};
+use pubky_nexus::RedisOps;
+
+async fn find_window_engagement(timeframe: &Timeframe, post_key: &[&str]) -> Option<isize> {
+    let timeframe_str = timeframe.to_string();
+    let window_id = timeframe.to_current_window_id().unwrap();
+    let key_parts = [
+        &POST_TOTAL_ENGAGEMENT_KEY_PARTS[..],
+        &[timeframe_str.as_str(), window_id.as_str()],
+    ]
+    .concat();
+    PostStream::check_sorted_set_member(None, &key_parts, post_key)
+        .await
+        .unwrap()
+}
+
+async fn stream_post_keys(timeframe: Timeframe) -> Vec<String> {
+    PostStream::get_posts(
+        StreamSource::All,
+        Pagination::default(),
+        StreamSorting::TotalEngagement,
+        None,
+        None,
+        None,
+        Some(timeframe),
+    )
+    .await
+    .unwrap()
+    .map(|stream| {
+        stream
+            .0
+            .into_iter()
+            .map(|post| format!("{}:{}", post.details.author, post.details.id))
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_post_engagement_timeframe() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Alice", "Bob"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_post_engagement_timeframe".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:PostEngagementTimeframe:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (alice_id, bob_id) = (&user_ids[0], &user_ids[1]);
+
+    let post = PubkyAppPost {
+        content: "Watcher:PostEngagementTimeframe:Alice:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(alice_id, &post).await?;
+    let post_uri = format!("pubky://{alice_id}/pub/pubky.app/posts/{post_id}");
+    let post_key = format!("{alice_id}:{post_id}");
+
+    // Without engagement the post is not in the windows
+    for timeframe in [Timeframe::Today, Timeframe::ThisMonth] {
+        assert!(!stream_post_keys(timeframe).await.contains(&post_key));
+    }
+
+    // Bob replies and tags the post
+    let reply = PubkyAppPost {
+        content: "Watcher:PostEngagementTimeframe:Bob:Reply".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(post_uri.clone()),
+        embed: None,
+        attachments: None,
+    };
+    let reply_id = test.create_post(bob_id, &reply).await?;
+
+    let tag = PubkyAppTag {
+        uri: post_uri.clone(),
+        label: "timeframe".to_string(),
+        created_at: Utc::now().timestamp_millis(),
+    };
+    let tag_url = format!("pubky://{bob_id}/pub/pubky.app/tags/{}", tag.create_id());
+    test.put(&tag_url, tag).await?;
+
+    for timeframe in [Timeframe::Today, Timeframe::ThisMonth] {
+        assert_eq!(
+            find_window_engagement(&timeframe, &[alice_id, &post_id]).await,
+            Some(2)
+        );
+        let post_keys = stream_post_keys(timeframe).await;
+        assert!(post_keys.contains(&post_key));
+        // Replies are not part of the engagement streams
+        assert!(!post_keys.contains(&format!("{bob_id}:{reply_id}")));
+    }
+
+    // Removing the engagement also removes it from the windows
+    test.del(&tag_url).await?;
+    test.cleanup_post(bob_id, &reply_id).await?;
+
+    for timeframe in [Timeframe::Today, Timeframe::ThisMonth] {
+        assert_eq!(
+            find_window_engagement(&timeframe, &[alice_id, &post_id]).await,
+            Some(0)
+        );
+        assert!(!stream_post_keys(timeframe).await.contains(&post_key));
+    }
+
+    // Deleting the post removes it from the windows
+    test.cleanup_post(alice_id, &post_id).await?;
+    for timeframe in [Timeframe::Today, Timeframe::ThisMonth] {
+        assert_eq!(
+            find_window_engagement(&timeframe, &[alice_id, &post_id]).await,
+            None
+        );
+    }
+
+    // Cleanup
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
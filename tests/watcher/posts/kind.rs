@@ -0,0 +1,88 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+use pubky_nexus::models::post::{PostStream, StreamSource};
+use pubky_nexus::types::{Pagination, StreamSorting};
+use pubky_nexus::PubkyConnector;
+
+async fn get_kind_post_ids(source: StreamSource, kind: PubkyAppPostKind) -> Vec<String> {
+    PostStream::get_posts(
+        source,
+        Pagination::default(),
+        StreamSorting::Timeline,
+        None,
+        None,
+        Some(kind),
+        None,
+    )
+    .await
+    .unwrap()
+    .map(|stream| stream.0.into_iter().map(|post| post.details.id).collect())
+    .unwrap_or_default()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_post_kind_stream() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let keypair = Keypair::random();
+    let user = PubkyAppUser {
+        bio: Some("test_homeserver_post_kind_stream".to_string()),
+        image: None,
+        links: None,
+        name: "Watcher:KindStream:Author".to_string(),
+        status: None,
+    };
+    let author_id = test.create_user(&keypair, &user).await?;
+    let author = StreamSource::Author {
+        author_id: author_id.clone(),
+    };
+
+    let mut post = PubkyAppPost {
+        content: "Watcher:KindStream:Author:Post".to_string(),
+        kind: PubkyAppPostKind::Image,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(&author_id, &post).await?;
+
+    let image_posts = get_kind_post_ids(author.clone(), PubkyAppPostKind::Image).await;
+    assert_eq!(image_posts, vec![post_id.clone()]);
+    let image_posts = get_kind_post_ids(StreamSource::All, PubkyAppPostKind::Image).await;
+    assert!(image_posts.contains(&post_id));
+    let link_posts = get_kind_post_ids(author.clone(), PubkyAppPostKind::Link).await;
+    assert!(link_posts.is_empty());
+
+    // The edited post moves to the streams of its new kind
+    post.content = "Watcher:KindStream:Author:Post:Edited".to_string();
+    post.kind = PubkyAppPostKind::Link;
+    let post_url = format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}");
+    let pubky_client = PubkyConnector::get_pubky_client()?;
+    pubky_client
+        .put(post_url.as_str())
+        .json(&post)
+        .send()
+        .await?;
+    test.ensure_event_processing_complete().await?;
+
+    let image_posts = get_kind_post_ids(author.clone(), PubkyAppPostKind::Image).await;
+    assert!(image_posts.is_empty());
+    let image_posts = get_kind_post_ids(StreamSource::All, PubkyAppPostKind::Image).await;
+    assert!(!image_posts.contains(&post_id));
+    let link_posts = get_kind_post_ids(author.clone(), PubkyAppPostKind::Link).await;
+    assert_eq!(link_posts, vec![post_id.clone()]);
+
+    // Deleting the post removes it from the streams of every kind
+    test.cleanup_post(&author_id, &post_id).await?;
+    let link_posts = get_kind_post_ids(author.clone(), PubkyAppPostKind::Link).await;
+    assert!(link_posts.is_empty());
+    let link_posts = get_kind_post_ids(StreamSource::All, PubkyAppPostKind::Link).await;
+    assert!(!link_posts.contains(&post_id));
+
+    // Cleanup
+    test.cleanup_user(&author_id).await?;
+
+    Ok(())
+}
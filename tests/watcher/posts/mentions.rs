@@ -0,0 +1,77 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+use pubky_nexus::models::post::{PostStream, StreamSource};
+use pubky_nexus::types::{Pagination, StreamSorting};
+
+async fn get_mentions(user_id: &str) -> Option<PostStream> {
+    PostStream::get_posts(
+        StreamSource::Mentions {
+            user_id: user_id.to_string(),
+        },
+        Pagination::default(),
+        StreamSorting::Timeline,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_mentions_stream() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Author", "Mentioned"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_mentions_stream".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:MentionsStream:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (author_id, mentioned_id) = (&user_ids[0], &user_ids[1]);
+
+    // The author mentions the user and themselves
+    let post = PubkyAppPost {
+        content: format!("Watcher:MentionsStream:Author:Post pk:{mentioned_id} pk:{author_id}"),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(author_id, &post).await?;
+
+    let stream = get_mentions(mentioned_id)
+        .await
+        .expect("The mentioned user should have a mentions stream");
+    assert!(stream
+        .0
+        .iter()
+        .any(|post| post.details.author == *author_id && post.details.id == post_id));
+
+    // Self mentions are not part of the stream
+    if let Some(stream) = get_mentions(author_id).await {
+        assert!(stream.0.iter().all(|post| post.details.id != post_id));
+    }
+
+    // Deleting the post removes it from the stream
+    test.cleanup_post(author_id, &post_id).await?;
+    if let Some(stream) = get_mentions(mentioned_id).await {
+        assert!(stream.0.iter().all(|post| post.details.id != post_id));
+    }
+
+    // Cleanup
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
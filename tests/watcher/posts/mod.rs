@@ -1,6 +1,7 @@
 mod attachments;
 mod del_reply_notification;
 mod del_reply_parent_notification;
+mod del_stale_notification;
 
 mod del_bookmarked_notification;
 mod del_repost_notification;
@@ -13,9 +14,12 @@ mod edit_reply_parent_notification;
 mod edit_reposted_notification;
 mod edit_tagged_notification;
 mod engagement;
+mod engagement_timeframe;
 mod fail_reply;
 mod fail_repost;
 mod fail_user;
+mod kind;
+mod mentions;
 mod pioneer;
 mod raw;
 mod reply;
@@ -28,4 +32,6 @@ mod retry_all;
 mod retry_post;
 mod retry_reply;
 mod retry_repost;
+mod thread_reply_notification;
+mod trending;
 pub mod utils;
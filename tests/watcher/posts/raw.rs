@@ -93,7 +93,7 @@ async fn test_homeserver_put_post_event() -> Result<()> {
     test.cleanup_post(&user_id, &post_id).await?;
 
     // // TODO: Impl DEL post. Assert the new post does not exist in Nexus
-    // let result_post = PostView::get_by_id(&user_id, &post_id, None, None, None)
+    // let result_post = PostView::get_by_id(&user_id, &post_id, None, None, None, None)
     //     .await
     //     .unwrap();
 
@@ -171,7 +171,7 @@ async fn test_homeserver_post_repost() -> Result<()> {
 
     // // TODO: Impl DEL post. Assert the repost does not exist in Nexus
     test.cleanup_post(&user_id, &repost_id).await?;
-    // let result_post = PostView::get_by_id(&user_id, &post_id, None, None, None)
+    // let result_post = PostView::get_by_id(&user_id, &post_id, None, None, None, None)
     //     .await
     //     .unwrap();
 
@@ -0,0 +1,150 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppPostKind, PubkyAppUser};
+use pubky_nexus::models::notification::{Notification, NotificationBody};
+use pubky_nexus::types::Pagination;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_thread_reply_notification() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(4);
+    for name in ["Alice", "Bob", "Carol", "Dave"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_thread_reply_notification".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:ThreadReplyNotification:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (alice_id, bob_id, carol_id, dave_id) =
+        (&user_ids[0], &user_ids[1], &user_ids[2], &user_ids[3]);
+
+    // Alice starts the thread
+    let root_post = PubkyAppPost {
+        content: "Watcher:ThreadReplyNotification:Alice:Post".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let root_post_id = test.create_post(alice_id, &root_post).await?;
+    let root_uri = format!("pubky://{alice_id}/pub/pubky.app/posts/{root_post_id}");
+
+    // Bob replies to Alice
+    let bob_reply = PubkyAppPost {
+        content: "Watcher:ThreadReplyNotification:Bob:Reply".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(root_uri.clone()),
+        embed: None,
+        attachments: None,
+    };
+    let bob_reply_id = test.create_post(bob_id, &bob_reply).await?;
+    let bob_reply_uri = format!("pubky://{bob_id}/pub/pubky.app/posts/{bob_reply_id}");
+
+    // Carol replies to Bob
+    let carol_reply = PubkyAppPost {
+        content: "Watcher:ThreadReplyNotification:Carol:Reply".to_string(),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(bob_reply_uri.clone()),
+        embed: None,
+        attachments: None,
+    };
+    let carol_reply_id = test.create_post(carol_id, &carol_reply).await?;
+    let carol_reply_uri = format!("pubky://{carol_id}/pub/pubky.app/posts/{carol_reply_id}");
+
+    // Bob is the direct parent author, he only gets the REPLY notification
+    let notifications = Notification::get_by_id(bob_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0].body,
+        NotificationBody::Reply { .. }
+    ));
+
+    // Alice started the thread, she gets a THREAD_REPLY notification from Carol
+    let notifications = Notification::get_by_id(alice_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        notifications.len(),
+        2,
+        "Alice should have the reply from Bob and the thread reply from Carol"
+    );
+    if let NotificationBody::ThreadReply {
+        replied_by,
+        root_post_uri,
+        parent_post_uri,
+        reply_uri,
+    } = &notifications[0].body
+    {
+        assert_eq!(replied_by, carol_id);
+        assert_eq!(root_post_uri, &root_uri);
+        assert_eq!(parent_post_uri, &bob_reply_uri);
+        assert_eq!(reply_uri, &carol_reply_uri);
+    } else {
+        panic!("Expected a THREAD_REPLY notification, found something else");
+    }
+
+    // Dave replies to Carol and mentions Bob
+    let dave_reply = PubkyAppPost {
+        content: format!("Watcher:ThreadReplyNotification:Dave:Reply pk:{bob_id}"),
+        kind: PubkyAppPostKind::Short,
+        parent: Some(carol_reply_uri.clone()),
+        embed: None,
+        attachments: None,
+    };
+    let dave_reply_id = test.create_post(dave_id, &dave_reply).await?;
+
+    // Alice gets a second THREAD_REPLY notification
+    let notifications = Notification::get_by_id(alice_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 3);
+    assert!(matches!(
+        notifications[0].body,
+        NotificationBody::ThreadReply { .. }
+    ));
+
+    // Bob was mentioned, the mention notification is not duplicated with a thread reply
+    let notifications = Notification::get_by_id(bob_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 2);
+    assert!(matches!(
+        notifications[0].body,
+        NotificationBody::Mention { .. }
+    ));
+
+    // Carol is the direct parent author of Dave reply
+    let notifications = Notification::get_by_id(carol_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert!(matches!(
+        notifications[0].body,
+        NotificationBody::Reply { .. }
+    ));
+
+    // Dave does not get notified about his own reply
+    let notifications = Notification::get_by_id(dave_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 0);
+
+    // Cleanup
+    test.cleanup_post(dave_id, &dave_reply_id).await?;
+    test.cleanup_post(carol_id, &carol_reply_id).await?;
+    test.cleanup_post(bob_id, &bob_reply_id).await?;
+    test.cleanup_post(alice_id, &root_post_id).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
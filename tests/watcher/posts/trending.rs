@@ -0,0 +1,128 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppPostKind, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::post::{PostStream, StreamSource, TagFilter};
+use pubky_nexus::types::{Pagination, StreamSorting};
+
+async fn stream_post_ids(
+    source: StreamSource,
+    sorting: StreamSorting,
+    tags: Option<Vec<String>>,
+) -> Vec<String> {
+    PostStream::get_posts(
+        source,
+        Pagination::default(),
+        sorting,
+        None,
+        tags.map(TagFilter::from),
+        None,
+        None,
+    )
+    .await
+    .unwrap()
+    .map(|stream| stream.0.into_iter().map(|post| post.details.id).collect())
+    .unwrap_or_default()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_post_trending() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Alice", "Bob"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_post_trending".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:PostTrending:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (alice_id, bob_id) = (&user_ids[0], &user_ids[1]);
+    let follow_uri = test.create_follow(bob_id, alice_id).await?;
+
+    // Alice writes an older and a newer post
+    let mut post_ids = Vec::with_capacity(2);
+    for name in ["Older", "Newer"] {
+        let post = PubkyAppPost {
+            content: format!("Watcher:PostTrending:Alice:{name}"),
+            kind: PubkyAppPostKind::Short,
+            parent: None,
+            embed: None,
+            attachments: None,
+        };
+        post_ids.push(test.create_post(alice_id, &post).await?);
+    }
+    let (older_id, newer_id) = (&post_ids[0], &post_ids[1]);
+    let following = StreamSource::Following {
+        observer_id: bob_id.to_string(),
+    };
+
+    // Without engagement the newer post trends first
+    let trending = stream_post_ids(following.clone(), StreamSorting::Trending, None).await;
+    assert_eq!(trending, vec![newer_id.clone(), older_id.clone()]);
+
+    // Bob engages with the older post
+    let label = "watcher_trending";
+    let mut tag_urls = Vec::with_capacity(2);
+    for label in [label, "watcher_trending_more"] {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{alice_id}/pub/pubky.app/posts/{older_id}"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{bob_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    // The engaged post trends above the newer one, the timeline is unchanged
+    let trending = stream_post_ids(following.clone(), StreamSorting::Trending, None).await;
+    assert_eq!(trending, vec![older_id.clone(), newer_id.clone()]);
+    let timeline = stream_post_ids(following.clone(), StreamSorting::Timeline, None).await;
+    assert_eq!(timeline, vec![newer_id.clone(), older_id.clone()]);
+
+    let global = stream_post_ids(StreamSource::All, StreamSorting::Trending, None).await;
+    let older_position = global.iter().position(|id| id == older_id);
+    let newer_position = global.iter().position(|id| id == newer_id);
+    assert!(older_position.is_some() && newer_position.is_some());
+    assert!(older_position < newer_position);
+
+    // Per tag trending
+    let tagged = stream_post_ids(
+        StreamSource::All,
+        StreamSorting::Trending,
+        Some(vec![label.to_string()]),
+    )
+    .await;
+    assert!(tagged.contains(older_id));
+
+    // Removing the engagement brings back the newer post
+    for tag_url in &tag_urls {
+        test.del(tag_url).await?;
+    }
+    let trending = stream_post_ids(following.clone(), StreamSorting::Trending, None).await;
+    assert_eq!(trending, vec![newer_id.clone(), older_id.clone()]);
+    let tagged = stream_post_ids(
+        StreamSource::All,
+        StreamSorting::Trending,
+        Some(vec![label.to_string()]),
+    )
+    .await;
+    assert!(!tagged.contains(older_id));
+
+    // Cleanup
+    test.del(&follow_uri).await?;
+    for post_id in &post_ids {
+        test.cleanup_post(alice_id, post_id).await?;
+    }
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::tag::history::{TagHistory, TagHistoryGranularity};
+use pubky_nexus::models::tag::TaggedType;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tag_history() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(3);
+    for name in ["Author", "Tagger1", "Tagger2"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_tag_history".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:TagHistory:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (author_id, tagger_1_id, tagger_2_id) = (&user_ids[0], &user_ids[1], &user_ids[2]);
+
+    let mut post_ids = Vec::with_capacity(2);
+    for index in 0..2 {
+        let post = PubkyAppPost {
+            content: format!("Watcher:TagHistory:Author:Post{index}"),
+            kind: PubkyAppPost::default().kind,
+            parent: None,
+            embed: None,
+            attachments: None,
+        };
+        post_ids.push(test.create_post(author_id, &post).await?);
+    }
+
+    let label = "whistory";
+    let mut tag_urls = Vec::with_capacity(3);
+    for (tagger_id, post_id) in [
+        (tagger_1_id, &post_ids[0]),
+        (tagger_1_id, &post_ids[1]),
+        (tagger_2_id, &post_ids[0]),
+    ] {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    let current_bucket = |granularity| async move {
+        let history = TagHistory::get_by_label(label, TaggedType::Post, granularity, None, 2)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 2);
+        let last = history[1].clone();
+        (last.tag_count, last.unique_taggers)
+    };
+
+    // Every granularity counts the tags of the current bucket
+    for granularity in [
+        TagHistoryGranularity::Hour,
+        TagHistoryGranularity::Day,
+        TagHistoryGranularity::Week,
+    ] {
+        assert_eq!(current_bucket(granularity).await, (3, 2));
+    }
+
+    // The first tagger still has a tag with the label in the bucket
+    test.del(&tag_urls[1]).await?;
+    assert_eq!(current_bucket(TagHistoryGranularity::Day).await, (2, 2));
+
+    // The first tagger has no tags with the label left
+    test.del(&tag_urls[0]).await?;
+    assert_eq!(current_bucket(TagHistoryGranularity::Day).await, (1, 1));
+
+    // User tags have their own history
+    let user_history =
+        TagHistory::get_by_label(label, TaggedType::User, TagHistoryGranularity::Day, None, 1)
+            .await
+            .unwrap();
+    assert_eq!(user_history[0].tag_count, 0);
+
+    // Cleanup
+    test.del(&tag_urls[2]).await?;
+    for post_id in &post_ids {
+        test.cleanup_post(author_id, post_id).await?;
+    }
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
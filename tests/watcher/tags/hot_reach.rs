@@ -0,0 +1,83 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::tag::stream::{HotTags, TagStreamReach};
+use pubky_nexus::models::tag::TaggedType;
+use pubky_nexus::routes::v0::tag::HotTagsInput;
+use pubky_nexus::types::Timeframe;
+
+async fn following_hot_labels(user_id: &str) -> Vec<String> {
+    let input = HotTagsInput::new(Timeframe::AllTime, 40, 0, 20, Some(TaggedType::Post));
+    HotTags::get_hot_tags(
+        Some(user_id.to_string()),
+        Some(TagStreamReach::Following),
+        &input,
+    )
+    .await
+    .unwrap()
+    .unwrap_or_default()
+    .iter()
+    .map(|hot_tag| hot_tag.label.clone())
+    .collect()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_hot_tags_by_reach_cache() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Viewer", "Tagger"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_hot_tags_by_reach_cache".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:HotTagsReach:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (viewer_id, tagger_id) = (&user_ids[0], &user_ids[1]);
+
+    let post = PubkyAppPost {
+        content: "Watcher:HotTagsReach:Tagger:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(tagger_id, &post).await?;
+
+    // The viewer follows the tagger and caches its empty hot tags
+    let follow_uri = test.create_follow(viewer_id, tagger_id).await?;
+    assert!(following_hot_labels(viewer_id).await.is_empty());
+
+    // A new tag in the reach of the viewer invalidates the cache
+    let label = "whot_reach";
+    let tag = PubkyAppTag {
+        uri: format!("pubky://{tagger_id}/pub/pubky.app/posts/{post_id}"),
+        label: label.to_string(),
+        created_at: Utc::now().timestamp_millis(),
+    };
+    let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+    test.put(&tag_url, tag).await?;
+    assert_eq!(
+        following_hot_labels(viewer_id).await,
+        vec![label.to_string()]
+    );
+
+    // Unfollowing the tagger invalidates the cache
+    test.del(&follow_uri).await?;
+    assert!(following_hot_labels(viewer_id).await.is_empty());
+
+    // Cleanup
+    test.del(&tag_url).await?;
+    test.cleanup_post(tagger_id, &post_id).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use super::utils::find_post_tag;
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::tag::label::{canonical_label, normalize_label, TagAlias};
+use pubky_nexus::models::tag::post::TagPost;
+use pubky_nexus::models::tag::traits::TagCollection;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tag_label_canonical() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let canonical = "wlabel canon";
+    let alias = "wlabel_alias";
+    TagAlias::new(alias, canonical).put().await.unwrap();
+    assert_eq!(canonical_label(" WLabel_Alias ").await.unwrap(), canonical);
+    assert_eq!(normalize_label(" Ｗlabel\u{FE0F}  Canon "), canonical);
+
+    let mut user_ids = Vec::with_capacity(4);
+    for name in ["Author", "TaggerA", "TaggerB", "TaggerC"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_tag_label_canonical".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:TagLabel:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let author_id = &user_ids[0];
+
+    let post = PubkyAppPost {
+        content: "Watcher:TagLabel:Author:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(author_id, &post).await?;
+
+    // Each tagger writes the label in a different way
+    let labels = ["WLabel Canon", " wlabel  canon ", alias];
+    let mut tag_urls = Vec::with_capacity(labels.len());
+    for (tagger_id, label) in user_ids[1..].iter().zip(labels) {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    // GRAPH_OP: All the tags share the canonical label
+    let post_tag = find_post_tag(author_id, &post_id, canonical)
+        .await
+        .unwrap()
+        .expect("Failed to find post tag in graph database");
+    assert_eq!(post_tag.taggers_count, 3);
+    assert!(find_post_tag(author_id, &post_id, alias)
+        .await
+        .unwrap()
+        .is_none());
+
+    // CACHE_OP: The post has a single label
+    let cache_post_tag =
+        TagPost::get_from_index(author_id, Some(&post_id), None, None, None, None, false)
+            .await
+            .unwrap()
+            .expect("Failed to find post tags in the index");
+    assert_eq!(cache_post_tag.len(), 1);
+    assert_eq!(cache_post_tag[0].label, canonical);
+    assert_eq!(cache_post_tag[0].taggers_count, 3);
+
+    // Cleanup
+    for tag_url in &tag_urls {
+        test.del(tag_url).await?;
+    }
+    test.cleanup_post(author_id, &post_id).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+    TagAlias::delete(alias).await.unwrap();
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tag_alias_merge() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let canonical = "wmerge new";
+    let alias = "wmerge old";
+
+    let mut user_ids = Vec::with_capacity(4);
+    for name in ["Author", "TaggerA", "TaggerB", "TaggerC"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_tag_alias_merge".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:TagMerge:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let author_id = &user_ids[0];
+
+    let post = PubkyAppPost {
+        content: "Watcher:TagMerge:Author:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(author_id, &post).await?;
+
+    // The tags are stored before the alias is registered
+    let labels = ["WMerge Old", "wmerge new", "WMerge Old"];
+    let mut tag_urls = Vec::with_capacity(labels.len());
+    for (tagger_id, label) in user_ids[1..].iter().zip(labels) {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    TagAlias::new(alias, canonical).put().await.unwrap();
+
+    // GRAPH_OP: The alias tags are merged into the canonical label
+    let post_tag = find_post_tag(author_id, &post_id, canonical)
+        .await
+        .unwrap()
+        .expect("Failed to find post tag in graph database");
+    assert_eq!(post_tag.taggers_count, 3);
+    assert!(find_post_tag(author_id, &post_id, alias)
+        .await
+        .unwrap()
+        .is_none());
+
+    // CACHE_OP: The indexes of the post follow the merge
+    let cache_post_tag =
+        TagPost::get_from_index(author_id, Some(&post_id), None, None, None, None, false)
+            .await
+            .unwrap()
+            .expect("Failed to find post tags in the index");
+    assert_eq!(cache_post_tag.len(), 1);
+    assert_eq!(cache_post_tag[0].label, canonical);
+    assert_eq!(cache_post_tag[0].taggers_count, 3);
+
+    // The label is displayed as most taggers wrote it
+    let post_tags = TagPost::get_by_id(author_id, Some(&post_id), None, None, None, None, None)
+        .await
+        .unwrap()
+        .expect("Failed to find post tags");
+    assert_eq!(post_tags[0].label, canonical);
+    assert_eq!(post_tags[0].original_label, "WMerge Old");
+
+    // Cleanup
+    for tag_url in &tag_urls {
+        test.del(tag_url).await?;
+    }
+    test.cleanup_post(author_id, &post_id).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+    TagAlias::delete(alias).await.unwrap();
+
+    Ok(())
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_tag_alias_chain() -> Result<()> {
+    WatcherTest::setup().await?;
+
+    let (first, second, canonical) = ("wchain first", "wchain second", "wchain canon");
+    TagAlias::new(first, second).put().await.unwrap();
+    TagAlias::new(second, canonical).put().await.unwrap();
+    assert_eq!(canonical_label(first).await.unwrap(), canonical);
+
+    // Registering the same alias again keeps it
+    TagAlias::new(second, canonical).put().await.unwrap();
+    assert_eq!(canonical_label(second).await.unwrap(), canonical);
+
+    // An alias of an alias is stored with the label it resolves to
+    let third = "wchain third";
+    TagAlias::new(third, first).put().await.unwrap();
+    let stored = TagAlias::get_by_alias(third).await.unwrap().unwrap();
+    assert_eq!(stored.canonical, canonical);
+
+    // An alias resolving to itself is rejected
+    assert!(TagAlias::new(canonical, first).put().await.is_err());
+    assert_eq!(canonical_label(canonical).await.unwrap(), canonical);
+
+    // Cleanup
+    for alias in [first, second, third] {
+        TagAlias::delete(alias).await.unwrap();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::post::{PostStream, StreamSource};
+use pubky_nexus::types::{Pagination, StreamSorting};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_tag_list_stream() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(4);
+    for name in ["Observer", "Friend", "Listed", "FriendListed"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_tag_list_stream".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:TagList:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (observer_id, friend_id, listed_id, friend_listed_id) =
+        (&user_ids[0], &user_ids[1], &user_ids[2], &user_ids[3]);
+
+    let mut follow_uris = Vec::with_capacity(2);
+    for (follower_id, followee_id) in [(observer_id, friend_id), (friend_id, observer_id)] {
+        follow_uris.push(test.create_follow(follower_id, followee_id).await?);
+    }
+
+    // The observer and its friend list a user each with the same label
+    let label = "wlist_devs";
+    let mut tag_urls = Vec::with_capacity(2);
+    for (tagger_id, tagged_id) in [(observer_id, listed_id), (friend_id, friend_listed_id)] {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{tagged_id}/pub/pubky.app/profile.json"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    let mut post_ids = Vec::with_capacity(2);
+    for author_id in [listed_id, friend_listed_id] {
+        let post = PubkyAppPost {
+            content: format!("Watcher:TagList:{author_id}:Post"),
+            kind: PubkyAppPost::default().kind,
+            parent: None,
+            embed: None,
+            attachments: None,
+        };
+        post_ids.push(test.create_post(author_id, &post).await?);
+    }
+
+    let list_stream = |include_friends| async move {
+        PostStream::get_posts(
+            StreamSource::TagList {
+                observer_id: observer_id.to_string(),
+                label: label.to_string(),
+                include_friends,
+            },
+            Pagination::default(),
+            StreamSorting::Timeline,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .expect("The list stream should have posts")
+    };
+
+    // Only the posts of the users listed by the observer
+    let stream = list_stream(None).await;
+    assert_eq!(stream.0.len(), 1);
+    assert_eq!(stream.0[0].details.author, *listed_id);
+    assert_eq!(stream.0[0].details.id, post_ids[0]);
+
+    // The lists of the friends are merged, the newest post first
+    let stream = list_stream(Some(true)).await;
+    assert_eq!(stream.0.len(), 2);
+    assert_eq!(stream.0[0].details.author, *friend_listed_id);
+    assert_eq!(stream.0[1].details.author, *listed_id);
+
+    // Cleanup
+    for (author_id, post_id) in [listed_id, friend_listed_id].iter().zip(&post_ids) {
+        test.cleanup_post(author_id, post_id).await?;
+    }
+    for tag_url in &tag_urls {
+        test.del(tag_url).await?;
+    }
+    for follow_uri in &follow_uris {
+        test.del(follow_uri).await?;
+    }
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
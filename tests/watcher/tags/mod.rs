@@ -1,12 +1,20 @@
 mod fail_index;
+mod history;
+mod hot_reach;
+mod label;
+mod list;
 mod multi_user;
 mod post_del;
 mod post_multi_user;
 mod post_notification;
 mod post_put;
+mod post_tagged_stream;
+mod post_wot;
+mod related;
 mod retry_post_tag;
 mod retry_user_tag;
 mod user_notification;
+mod user_search;
 mod user_to_self_put;
 mod user_to_user_del;
 mod user_to_user_put;
@@ -198,8 +198,8 @@ async fn test_homeserver_multi_user_tags() -> Result<()> {
         .unwrap();
     assert_eq!(
         notifications.len(),
-        5,
-        "Taggerd user should have 5 notification"
+        0,
+        "Tagged user notifications should be removed with the tags"
     );
 
     Ok(())
@@ -280,8 +280,8 @@ async fn test_homeserver_multi_user_posts_tags() -> Result<()> {
         .unwrap();
     assert_eq!(
         notifications.len(),
-        5,
-        "Post author should have 5 notification"
+        0,
+        "Post author notifications should be removed with the tags"
     );
 
     Ok(())
@@ -0,0 +1,102 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::post::{PostStream, StreamSource};
+use pubky_nexus::types::{Pagination, StreamSorting};
+
+async fn get_tagged_post_ids(tagger_id: &str, label: Option<&str>) -> Vec<String> {
+    PostStream::get_posts(
+        StreamSource::Tagged {
+            tagger_id: tagger_id.to_string(),
+            label: label.map(String::from),
+        },
+        Pagination::default(),
+        StreamSorting::Timeline,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap()
+    .map(|stream| stream.0.into_iter().map(|post| post.details.id).collect())
+    .unwrap_or_default()
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_post_tagged_stream() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Author", "Tagger"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_post_tagged_stream".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:TaggedStream:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (author_id, tagger_id) = (&user_ids[0], &user_ids[1]);
+
+    let mut post_ids = Vec::with_capacity(2);
+    for index in 0..2 {
+        let post = PubkyAppPost {
+            content: format!("Watcher:TaggedStream:Author:Post{index}"),
+            kind: PubkyAppPost::default().kind,
+            parent: None,
+            embed: None,
+            attachments: None,
+        };
+        post_ids.push(test.create_post(author_id, &post).await?);
+    }
+
+    // The first post is tagged with two labels, then the second post with one
+    let mut tag_urls = Vec::with_capacity(3);
+    for (post_id, label) in [
+        (&post_ids[0], "tagged_stream_a"),
+        (&post_ids[0], "tagged_stream_b"),
+        (&post_ids[1], "tagged_stream_a"),
+    ] {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    // Sorted by the time they were tagged, the latest first
+    let tagged = get_tagged_post_ids(tagger_id, None).await;
+    assert_eq!(tagged, vec![post_ids[1].clone(), post_ids[0].clone()]);
+    let tagged = get_tagged_post_ids(tagger_id, Some("tagged_stream_b")).await;
+    assert_eq!(tagged, vec![post_ids[0].clone()]);
+
+    // The post stays in the stream while the tagger keeps a label on it
+    test.del(&tag_urls[0]).await?;
+    let tagged = get_tagged_post_ids(tagger_id, None).await;
+    assert_eq!(tagged, vec![post_ids[1].clone(), post_ids[0].clone()]);
+    let tagged = get_tagged_post_ids(tagger_id, Some("tagged_stream_a")).await;
+    assert_eq!(tagged, vec![post_ids[1].clone()]);
+
+    test.del(&tag_urls[1]).await?;
+    let tagged = get_tagged_post_ids(tagger_id, None).await;
+    assert_eq!(tagged, vec![post_ids[1].clone()]);
+
+    // Cleanup
+    test.del(&tag_urls[2]).await?;
+    for post_id in &post_ids {
+        test.cleanup_post(author_id, post_id).await?;
+    }
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
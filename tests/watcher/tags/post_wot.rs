@@ -0,0 +1,104 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::tag::post::TagPost;
+use pubky_nexus::models::tag::traits::{TagCollection, TaggersCollection};
+use pubky_nexus::types::Pagination;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_post_tags_web_of_trust() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(4);
+    for name in ["Viewer", "Author", "Trusted", "Stranger"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_post_tags_web_of_trust".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:PostTagsWoT:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (viewer_id, author_id, trusted_id, stranger_id) =
+        (&user_ids[0], &user_ids[1], &user_ids[2], &user_ids[3]);
+
+    let post = PubkyAppPost {
+        content: "Watcher:PostTagsWoT:Author:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(author_id, &post).await?;
+
+    // The viewer only follows the trusted tagger
+    let follow_uri = test.create_follow(viewer_id, trusted_id).await?;
+
+    let (trusted_label, stranger_label) = ("wot_trusted", "wot_stranger");
+    let mut tag_urls = Vec::with_capacity(2);
+    for (tagger_id, label) in [(trusted_id, trusted_label), (stranger_id, stranger_label)] {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    // Global tags include both labels
+    let global_tags = TagPost::get_by_id(author_id, Some(&post_id), None, None, None, None, None)
+        .await
+        .unwrap()
+        .expect("Failed to find post tags");
+    assert_eq!(global_tags.len(), 2);
+
+    // WoT tags only include the labels of the viewer network
+    let wot_tags = TagPost::get_by_id(
+        author_id,
+        Some(&post_id),
+        None,
+        None,
+        None,
+        Some(viewer_id),
+        Some(1),
+    )
+    .await
+    .unwrap()
+    .expect("Failed to find post WoT tags");
+    assert_eq!(wot_tags.len(), 1);
+    assert_eq!(wot_tags[0].label, trusted_label);
+    assert_eq!(wot_tags[0].taggers[0], *trusted_id);
+
+    // The taggers are served from the WoT cache
+    let taggers = TagPost::get_tagger_by_id(
+        author_id,
+        Some(&post_id),
+        trusted_label,
+        Pagination::default(),
+        Some(viewer_id),
+        Some(1),
+    )
+    .await
+    .unwrap()
+    .expect("Failed to find post WoT taggers");
+    assert_eq!(taggers.users, vec![trusted_id.to_string()]);
+    assert!(!taggers.relationship);
+
+    // Cleanup
+    for tag_url in &tag_urls {
+        test.del(tag_url).await?;
+    }
+    test.del(&follow_uri).await?;
+    test.cleanup_post(author_id, &post_id).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
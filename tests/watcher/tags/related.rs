@@ -0,0 +1,133 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppPost, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::tag::related::{RelatedTags, TAG_RELATED_KEY_PARTS};
+use pubky_nexus::models::tag::{stream::TagStreamReach, TaggedType};
+use pubky_nexus::RedisOps;
+
+async fn find_related_strength(
+    label: &str,
+    related_label: &str,
+    user_id: Option<&str>,
+) -> Option<f64> {
+    let reach = user_id.map(|_| TagStreamReach::Following);
+    RelatedTags::get_related(
+        label,
+        TaggedType::Post,
+        user_id.map(String::from),
+        reach,
+        0,
+        20,
+    )
+    .await
+    .unwrap()
+    .unwrap_or_default()
+    .iter()
+    .find(|related| related.label == related_label)
+    .map(|related| related.strength)
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_related_tags() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(3);
+    for name in ["Author", "TaggerA", "TaggerB"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_related_tags".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:RelatedTags:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (author_id, tagger_a_id, tagger_b_id) = (&user_ids[0], &user_ids[1], &user_ids[2]);
+
+    let post = PubkyAppPost {
+        content: "Watcher:RelatedTags:Author:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(author_id, &post).await?;
+    let post_uri = format!("pubky://{author_id}/pub/pubky.app/posts/{post_id}");
+
+    let (label, related_label) = ("watcher_related_rust", "watcher_related_tokio");
+
+    // Both taggers add both labels to the post
+    let mut tag_urls = Vec::with_capacity(4);
+    for tagger_id in [tagger_a_id, tagger_b_id] {
+        for label in [label, related_label] {
+            let tag = PubkyAppTag {
+                uri: post_uri.clone(),
+                label: label.to_string(),
+                created_at: Utc::now().timestamp_millis(),
+            };
+            let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+            test.put(&tag_url, tag).await?;
+            tag_urls.push(tag_url);
+        }
+    }
+
+    // The co-occurrence is counted once per post, in both directions
+    assert_eq!(
+        find_related_strength(label, related_label, None).await,
+        Some(1.0)
+    );
+    assert_eq!(
+        find_related_strength(related_label, label, None).await,
+        Some(1.0)
+    );
+
+    // The reach variant only counts the tags of the followed users
+    assert_eq!(
+        find_related_strength(label, related_label, Some(author_id)).await,
+        None
+    );
+    let follow_uri = test.create_follow(author_id, tagger_a_id).await?;
+    assert_eq!(
+        find_related_strength(label, related_label, Some(author_id)).await,
+        Some(1.0)
+    );
+
+    // The co-occurrence remains while one tagger keeps the label
+    test.del(&tag_urls[1]).await?;
+    assert_eq!(
+        find_related_strength(label, related_label, None).await,
+        Some(1.0)
+    );
+
+    // And it is removed with the last tagger of the label
+    test.del(&tag_urls[3]).await?;
+    assert_eq!(
+        find_related_strength(label, related_label, None).await,
+        None
+    );
+    assert_eq!(
+        find_related_strength(related_label, label, None).await,
+        None
+    );
+    // The labels without co-occurrences leave the index
+    let key_parts = [&TAG_RELATED_KEY_PARTS[..], &["Post", label]].concat();
+    let member = RelatedTags::check_sorted_set_member(None, &key_parts, &[related_label])
+        .await
+        .unwrap();
+    assert_eq!(member, None);
+
+    // Cleanup
+    test.del(&follow_uri).await?;
+    for tag_url in [&tag_urls[0], &tag_urls[2]] {
+        test.del(tag_url).await?;
+    }
+    test.cleanup_post(author_id, &post_id).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::tag::search::{UserTagSearch, UserTagSorting};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_search_users_by_tag() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(6);
+    for name in [
+        "Viewer", "Tagger1", "Tagger2", "Tagger3", "Designer", "Junior",
+    ] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_search_users_by_tag".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:UserTagSearch:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (viewer_id, tagger_1_id, tagger_2_id, tagger_3_id, designer_id, junior_id) = (
+        &user_ids[0],
+        &user_ids[1],
+        &user_ids[2],
+        &user_ids[3],
+        &user_ids[4],
+        &user_ids[5],
+    );
+
+    // The viewer only follows the first tagger
+    let follow_uri = test.create_follow(viewer_id, tagger_1_id).await?;
+
+    let (label, other_label) = ("wsearch_designer", "wsearch_other");
+    let mut tag_urls = Vec::with_capacity(5);
+    for (tagger_id, tagged_id, label) in [
+        (tagger_1_id, designer_id, label),
+        (tagger_2_id, designer_id, label),
+        (tagger_2_id, junior_id, label),
+        (tagger_1_id, junior_id, other_label),
+        (tagger_3_id, junior_id, other_label),
+    ] {
+        let tag = PubkyAppTag {
+            uri: format!("pubky://{tagged_id}/pub/pubky.app/profile.json"),
+            label: label.to_string(),
+            created_at: Utc::now().timestamp_millis(),
+        };
+        let tag_url = format!("pubky://{tagger_id}/pub/pubky.app/tags/{}", tag.create_id());
+        test.put(&tag_url, tag).await?;
+        tag_urls.push(tag_url);
+    }
+
+    let search = |sorting| async move {
+        UserTagSearch::get_by_label(label, sorting, Some(viewer_id.as_str()), Some(1), 0, 10)
+            .await
+            .unwrap()
+            .map(|search| search.0)
+            .unwrap_or_default()
+    };
+
+    // The designer has more tags with the label
+    let tag_count = search(UserTagSorting::TagCount).await;
+    assert_eq!(
+        tag_count,
+        vec![designer_id.to_string(), junior_id.to_string()]
+    );
+
+    // The junior is tagged by more users, with any label
+    let unique_taggers = search(UserTagSorting::UniqueTaggers).await;
+    assert_eq!(
+        unique_taggers,
+        vec![junior_id.to_string(), designer_id.to_string()]
+    );
+
+    // Only the tagger followed by the viewer tagged the designer with the label
+    let wot = search(UserTagSorting::Wot).await;
+    assert_eq!(wot, vec![designer_id.to_string()]);
+
+    // The junior leaves the results with its last tag of the label
+    test.del(&tag_urls[2]).await?;
+    let tag_count = search(UserTagSorting::TagCount).await;
+    assert_eq!(tag_count, vec![designer_id.to_string()]);
+
+    // Cleanup
+    for (index, tag_url) in tag_urls.iter().enumerate() {
+        if index != 2 {
+            test.del(tag_url).await?;
+        }
+    }
+    test.del(&follow_uri).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::PubkyAppUser;
+use pubky_nexus::{models::notification::Notification, types::Pagination};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_delete_user_removes_follow_notifications() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Alice", "Bob"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_delete_user_removes_follow_notifications".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:UserDeleteNotification:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (alice_id, bob_id) = (&user_ids[0], &user_ids[1]);
+
+    // Alice follows Bob
+    test.create_follow(alice_id, bob_id).await?;
+    let notifications = Notification::get_by_id(bob_id, Pagination::default())
+        .await
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+
+    // Alice deletes her profile, the follow keeps it as [DELETED]
+    test.cleanup_user(alice_id).await?;
+
+    // Bob is not notified anymore about the follow of the deleted user
+    let notifications = Notification::get_by_id(bob_id, Pagination::default())
+        .await
+        .unwrap();
+    assert!(!notifications
+        .iter()
+        .any(|notification| notification.body.references_user(alice_id)));
+
+    // Cleanup
+    test.cleanup_user(bob_id).await?;
+
+    Ok(())
+}
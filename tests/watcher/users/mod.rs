@@ -1,5 +1,8 @@
 mod avatar;
+mod del_notification;
 mod del_with_relations;
 mod del_without_relations;
 mod raw;
+mod reputation;
+mod trust;
 pub mod utils;
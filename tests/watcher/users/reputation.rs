@@ -0,0 +1,63 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::PubkyAppUser;
+use pubky_nexus::models::trust::UserReputation;
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_global_reputation() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(5);
+    for name in ["Seed", "Honest", "Sybil1", "Sybil2", "Spammer"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_global_reputation".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:Reputation:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (seed_id, honest_id, sybil_1_id, sybil_2_id, spammer_id) = (
+        &user_ids[0],
+        &user_ids[1],
+        &user_ids[2],
+        &user_ids[3],
+        &user_ids[4],
+    );
+
+    // The sybils boost the spammer, but nobody with reputation follows them
+    let mut follow_uris = Vec::with_capacity(5);
+    for (follower_id, followee_id) in [
+        (seed_id, honest_id),
+        (sybil_1_id, sybil_2_id),
+        (sybil_2_id, sybil_1_id),
+        (sybil_1_id, spammer_id),
+        (sybil_2_id, spammer_id),
+    ] {
+        follow_uris.push(test.create_follow(follower_id, followee_id).await?);
+    }
+
+    // The reputation is computed without replacing the one of the shared index and graph
+    let reputation = UserReputation::compute(&[seed_id.to_string()])
+        .await
+        .unwrap();
+
+    let honest = reputation.get(honest_id.as_str());
+    assert!(*honest.expect("The followed user should have reputation") > 0.0);
+    assert!(!reputation.contains_key(spammer_id.as_str()));
+    assert!(!reputation.contains_key(sybil_1_id.as_str()));
+    assert!(!reputation.contains_key(sybil_2_id.as_str()));
+
+    // Cleanup
+    for follow_uri in &follow_uris {
+        test.del(follow_uri).await?;
+    }
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
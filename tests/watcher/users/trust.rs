@@ -0,0 +1,92 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use pubky::Keypair;
+use pubky_app_specs::{PubkyAppPost, PubkyAppUser};
+use pubky_nexus::models::post::{PostStream, StreamSource};
+use pubky_nexus::models::trust::TrustScores;
+use pubky_nexus::types::{Pagination, StreamSorting};
+
+#[tokio_shared_rt::test(shared)]
+async fn test_homeserver_personalized_trust() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let mut user_ids = Vec::with_capacity(4);
+    for name in ["Viewer", "Friend", "FriendOfFriend", "Stranger"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_homeserver_personalized_trust".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:Trust:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (viewer_id, friend_id, fof_id, stranger_id) =
+        (&user_ids[0], &user_ids[1], &user_ids[2], &user_ids[3]);
+
+    // viewer -> friend -> friend of friend. The stranger follows the viewer network
+    let mut follow_uris = Vec::with_capacity(3);
+    for (follower_id, followee_id) in [
+        (viewer_id, friend_id),
+        (friend_id, fof_id),
+        (stranger_id, friend_id),
+    ] {
+        follow_uris.push(test.create_follow(follower_id, followee_id).await?);
+    }
+
+    let post = PubkyAppPost {
+        content: "Watcher:Trust:FriendOfFriend:Post".to_string(),
+        kind: PubkyAppPost::default().kind,
+        parent: None,
+        embed: None,
+        attachments: None,
+    };
+    let post_id = test.create_post(fof_id, &post).await?;
+
+    // The trust decays with the distance and the stranger is not trusted
+    let trust = TrustScores::get_by_id(viewer_id, None, None, None)
+        .await
+        .unwrap()
+        .expect("The viewer should have trust scores");
+    let ranked: Vec<&str> = trust.iter().map(|trust| trust.user_id.as_str()).collect();
+    assert_eq!(ranked, vec![friend_id.as_str(), fof_id.as_str()]);
+    assert!(trust[0].score > trust[1].score);
+
+    // The depth limits the scored network
+    let trust = TrustScores::get_by_id(viewer_id, Some(1), None, None)
+        .await
+        .unwrap()
+        .expect("The viewer should have trust scores");
+    assert_eq!(trust.len(), 1);
+    assert_eq!(trust[0].user_id, *friend_id);
+
+    // The trust weighted stream includes the posts of the trusted users
+    let stream = PostStream::get_posts(
+        StreamSource::All,
+        Pagination::default(),
+        StreamSorting::TrustWeighted,
+        Some(viewer_id.to_string()),
+        None,
+        None,
+        None,
+    )
+    .await
+    .unwrap()
+    .expect("The trust weighted stream should have posts");
+    assert!(stream
+        .0
+        .iter()
+        .any(|post| post.details.author == *fof_id && post.details.id == post_id));
+
+    // Cleanup
+    test.cleanup_post(fof_id, &post_id).await?;
+    for follow_uri in &follow_uris {
+        test.del(follow_uri).await?;
+    }
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use crate::watcher::utils::watcher::WatcherTest;
+use anyhow::Result;
+use axum::{extract::State, http::HeaderMap, http::StatusCode, routing::post, Router};
+use chrono::Utc;
+use pubky::Keypair;
+use pubky_app_specs::{traits::HashId, PubkyAppTag, PubkyAppUser};
+use pubky_nexus::models::notification::webhook::{
+    Webhook, WebhookDelivery, WebhookPayload, WEBHOOK_SIGNATURE_HEADER,
+};
+use pubky_nexus::models::notification::NotificationBody;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+/// Local stand-in for an external endpoint. It fails the first request to exercise the retries
+async fn receive(State(received): State<Received>, headers: HeaderMap, body: String) -> StatusCode {
+    let mut received = received.lock().unwrap();
+    received.push((headers, body));
+    match received.len() {
+        1 => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => StatusCode::OK,
+    }
+}
+
+#[tokio_shared_rt::test(shared)]
+async fn test_webhook_delivery_with_retries() -> Result<()> {
+    let mut test = WatcherTest::setup().await?;
+
+    let received: Received = Arc::new(Mutex::new(Vec::new()));
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(received.clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    tokio::spawn(async move { axum::serve(listener, app).await });
+
+    let mut user_ids = Vec::with_capacity(2);
+    for name in ["Follower", "Followee"] {
+        let keypair = Keypair::random();
+        let user = PubkyAppUser {
+            bio: Some("test_webhook_delivery_with_retries".to_string()),
+            image: None,
+            links: None,
+            name: format!("Watcher:Webhook:{name}"),
+            status: None,
+        };
+        user_ids.push(test.create_user(&keypair, &user).await?);
+    }
+    let (follower_id, followee_id) = (&user_ids[0], &user_ids[1]);
+
+    // Only follow notifications of the followee are delivered
+    let webhook = Webhook {
+        id: format!("test-webhook-{followee_id}"),
+        url: format!("http://{address}/hook"),
+        secret: "test-secret".to_string(),
+        user_id: Some(followee_id.to_string()),
+        types: vec!["follow".to_string()],
+        max_retries: 2,
+        backoff: 10,
+    };
+    webhook.put_to_index().await.unwrap();
+
+    // The secret is not stored with the webhook
+    let stored = Webhook::get_by_id(&webhook.id).await.unwrap().unwrap();
+    assert!(stored.secret.is_empty());
+
+    // A tag on the profile does not match the filter
+    let tag = PubkyAppTag {
+        uri: format!("pubky://{followee_id}/pub/pubky.app/profile.json"),
+        label: "webhook".to_string(),
+        created_at: Utc::now().timestamp_millis(),
+    };
+    let tag_url = format!(
+        "pubky://{follower_id}/pub/pubky.app/tags/{}",
+        tag.create_id()
+    );
+    test.put(&tag_url, tag).await?;
+
+    let follow_uri = test.create_follow(follower_id, followee_id).await?;
+
+    // Wait until the background delivery succeeds
+    let mut deliveries = Vec::new();
+    for _ in 0..50 {
+        deliveries = WebhookDelivery::get_by_webhook(&webhook.id, None, None)
+            .await
+            .unwrap();
+        if deliveries.iter().any(|delivery| delivery.delivered) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    // The failed attempt and the successful retry are in the delivery log
+    assert_eq!(deliveries.len(), 2);
+    assert!(deliveries[0].delivered);
+    assert_eq!(deliveries[0].attempt, 2);
+    assert_eq!(deliveries[0].status_code, Some(200));
+    assert!(!deliveries[1].delivered);
+    assert_eq!(deliveries[1].status_code, Some(500));
+
+    let received = received.lock().unwrap().clone();
+    assert_eq!(
+        received.len(),
+        2,
+        "Only the follow notification is delivered"
+    );
+
+    let (headers, body) = &received[1];
+    let signature = headers
+        .get(WEBHOOK_SIGNATURE_HEADER)
+        .expect("The payload should be signed")
+        .to_str()?;
+    assert_eq!(signature, format!("sha256={}", webhook.sign(body).unwrap()));
+
+    let payload: WebhookPayload = serde_json::from_str(body)?;
+    assert_eq!(&payload.user_id, followee_id);
+    if let NotificationBody::Follow { followed_by } = &payload.notification.body {
+        assert_eq!(followed_by, follower_id);
+    } else {
+        panic!("Expected a follow notification, found something else");
+    }
+
+    // Cleanup
+    Webhook::delete(&webhook.id).await.unwrap();
+    test.del(&follow_uri).await?;
+    test.del(&tag_url).await?;
+    for user_id in &user_ids {
+        test.cleanup_user(user_id).await?;
+    }
+
+    Ok(())
+}